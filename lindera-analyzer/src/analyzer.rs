@@ -1,14 +1,18 @@
+use std::str::SplitInclusive;
 use std::{fs, path::Path};
 
+use rayon::prelude::*;
 use serde::Serialize;
 use serde_json::Value;
 
-use lindera_core::error::LinderaErrorKind;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+use lindera_core::offsets::OffsetCounter;
+use lindera_core::viterbi::Lattice;
 use lindera_core::LinderaResult;
 use lindera_filter::character_filter::{correct_offset, BoxCharacterFilter, CharacterFilterLoader};
 use lindera_filter::token::Token;
 use lindera_filter::token_filter::{BoxTokenFilter, TokenFilterLoader};
-use lindera_tokenizer::tokenizer::Tokenizer;
+use lindera_tokenizer::tokenizer::{ConfigFormat, Tokenizer};
 
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct AnalyzerConfig {
@@ -16,15 +20,23 @@ pub struct AnalyzerConfig {
 }
 
 impl AnalyzerConfig {
+    /// Read an analyzer config from a file. TOML and YAML files are detected from the extension,
+    /// and the other files are read as JSON.
     pub fn from_file(path: &Path) -> LinderaResult<Self> {
         let bytes = fs::read(path).map_err(|err| LinderaErrorKind::Io.with_error(err))?;
+        let format = ConfigFormat::from_path(path).unwrap_or(ConfigFormat::Json);
 
-        Self::from_slice(&bytes)
+        Self::from_slice_with_format(&bytes, format)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(path)))
     }
 
     pub fn from_slice(data: &[u8]) -> LinderaResult<Self> {
-        let args = serde_json::from_slice::<Value>(data)
-            .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))?;
+        Self::from_slice_with_format(data, ConfigFormat::Json)
+    }
+
+    /// Read an analyzer config from data in the given format.
+    pub fn from_slice_with_format(data: &[u8], format: ConfigFormat) -> LinderaResult<Self> {
+        let args = format.parse::<Value>(data)?;
 
         Ok(Self { inner: args })
     }
@@ -39,6 +51,10 @@ pub struct Analyzer {
 
     /// Token filters
     pub token_filters: Vec<BoxTokenFilter>,
+
+    /// The name of a token filter that needs the details of the tokens, if the tokenizer only
+    /// segments the text. It is found once when the analyzer is created.
+    details_filter: Option<String>,
 }
 
 impl Analyzer {
@@ -84,22 +100,119 @@ impl Analyzer {
             }
         }
 
-        Ok(Self::new(character_filters, tokenizer, token_filters))
+        Self::try_new(character_filters, tokenizer, token_filters)
+    }
+
+    /// Create an analyzer, checking that the token filters can be used with the tokenizer.
+    /// The filters that read the details of the tokens are rejected if the tokenizer only
+    /// segments the text.
+    pub fn try_new(
+        character_filters: Vec<BoxCharacterFilter>,
+        tokenizer: Tokenizer,
+        token_filters: Vec<BoxTokenFilter>,
+    ) -> LinderaResult<Self> {
+        let analyzer = Self::build(character_filters, tokenizer, token_filters);
+        analyzer.check_token_filters()?;
+        Ok(analyzer)
+    }
+
+    fn check_token_filters(&self) -> LinderaResult<()> {
+        match &self.details_filter {
+            Some(name) => Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "The {} token filter needs the details of the tokens, but the tokenizer only segments the text",
+                name
+            ))),
+            None => Ok(()),
+        }
     }
 
+    /// Create an analyzer without checking the token filters. The check is deferred to the first
+    /// analysis, which fails if a token filter needs the details that the tokenizer does not
+    /// read.
+    #[deprecated(note = "use `Analyzer::try_new`, which rejects the token filters at construction")]
     pub fn new(
         character_filters: Vec<BoxCharacterFilter>,
         tokenizer: Tokenizer,
         token_filters: Vec<BoxTokenFilter>,
     ) -> Self {
+        Self::build(character_filters, tokenizer, token_filters)
+    }
+
+    fn build(
+        character_filters: Vec<BoxCharacterFilter>,
+        tokenizer: Tokenizer,
+        token_filters: Vec<BoxTokenFilter>,
+    ) -> Self {
+        let details_filter = if tokenizer.is_segmentation_only() {
+            token_filters
+                .iter()
+                .find(|token_filter| token_filter.uses_details())
+                .map(|token_filter| token_filter.name().to_string())
+        } else {
+            None
+        };
         Self {
             character_filters,
             tokenizer,
             token_filters,
+            details_filter,
         }
     }
 
     pub fn analyze(&self, text: &str) -> LinderaResult<Vec<Token>> {
+        self.analyze_with_lattice(&mut Lattice::default(), text)
+    }
+
+    /// Analyze the texts in parallel on the rayon thread pool.
+    /// Each thread reuses a lattice, and the analyzer is shared instead of cloned.
+    /// The results are in the order of `texts`.
+    pub fn analyze_batch<T>(&self, texts: &[T]) -> LinderaResult<Vec<Vec<Token>>>
+    where
+        T: AsRef<str> + Sync,
+    {
+        texts
+            .par_iter()
+            .map_init(Lattice::default, |lattice, text| {
+                self.analyze_with_lattice(lattice, text.as_ref())
+            })
+            .collect()
+    }
+
+    /// Analyze the text one sentence at a time, as split by the sentence delimiters of the
+    /// tokenizer. The sentences are split in the original text, before the character filters are
+    /// applied, and the offsets and positions of the tokens are counted from the start of the
+    /// text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be analyzed.
+    ///
+    /// returns: AnalyzedSentences
+    ///
+    pub fn analyze_sentences<'a>(&'a self, text: &'a str) -> AnalyzedSentences<'a> {
+        AnalyzedSentences {
+            analyzer: self,
+            sentences: text.split_inclusive(self.tokenizer.sentence_delimiters.as_slice()),
+            lattice: Lattice::default(),
+            byte_position: 0,
+            position: 0,
+            offset_counter: self
+                .tokenizer
+                .compute_offsets
+                .then(|| OffsetCounter::new(text)),
+        }
+    }
+
+    fn analyze_with_lattice(&self, lattice: &mut Lattice, text: &str) -> LinderaResult<Vec<Token>> {
+        self.analyze_part(lattice, text).map(|(tokens, _)| tokens)
+    }
+
+    /// Analyze a text and return the tokens with the number of positions of the tokenizer.
+    fn analyze_part(
+        &self,
+        lattice: &mut Lattice,
+        text: &str,
+    ) -> LinderaResult<(Vec<Token>, usize)> {
         let mut normalized_text = text.to_string();
 
         let mut text_len_vec: Vec<usize> = Vec::new();
@@ -124,8 +237,16 @@ impl Analyzer {
             normalized_text = new_text;
         }
 
+        // The analyzers created by `new` are not rejected, so the filters are checked here.
+        self.check_token_filters()?;
+        let segmentation_only = self.tokenizer.is_segmentation_only();
+
         // Tokenize.
-        let mut tmp_tokens = self.tokenizer.tokenize(&normalized_text)?;
+        let mut tmp_tokens = self
+            .tokenizer
+            .tokenize_with_lattice(lattice, &normalized_text)?;
+
+        let positions = tmp_tokens.len();
 
         // Make analyzed tokens.
         let mut tokens = Vec::new();
@@ -137,14 +258,20 @@ impl Analyzer {
                 position: token.position,
                 position_length: token.position_length,
                 word_id: token.word_id,
-                details: token
-                    .get_details()
-                    .ok_or_else(|| {
-                        LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error"))
-                    })?
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>(),
+                offsets: token.offsets,
+                edge_type: token.edge_type,
+                details: if segmentation_only {
+                    Vec::new()
+                } else {
+                    token
+                        .get_details()
+                        .ok_or_else(|| {
+                            LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error"))
+                        })?
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<String>>()
+                },
             });
         }
 
@@ -167,7 +294,55 @@ impl Analyzer {
             }
         }
 
-        Ok(tokens)
+        // The offsets are counted in the filtered text, so count them again in the original text.
+        if !offsets_vec.is_empty() && self.tokenizer.compute_offsets {
+            let mut offset_counter = OffsetCounter::new(text);
+            for token in tokens.iter_mut() {
+                token.offsets = Some(offset_counter.offsets(token.byte_start, token.byte_end));
+            }
+        }
+
+        Ok((tokens, positions))
+    }
+}
+
+/// The tokens of the sentences of a text, analyzed one sentence at a time.
+pub struct AnalyzedSentences<'a> {
+    analyzer: &'a Analyzer,
+    sentences: SplitInclusive<'a, &'a [char]>,
+    lattice: Lattice,
+    /// The byte position of the next sentence in the text.
+    byte_position: usize,
+    /// The position of the first token of the next sentence.
+    position: usize,
+    /// Counts the offsets of the tokens in the text, if the tokenizer computes them.
+    offset_counter: Option<OffsetCounter<'a>>,
+}
+
+impl<'a> Iterator for AnalyzedSentences<'a> {
+    type Item = LinderaResult<Vec<Token>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sentence = self.sentences.next()?;
+        let byte_position = self.byte_position;
+        self.byte_position += sentence.len();
+
+        let (mut tokens, positions) = match self.analyzer.analyze_part(&mut self.lattice, sentence)
+        {
+            Ok(result) => result,
+            Err(err) => return Some(Err(err)),
+        };
+        for token in tokens.iter_mut() {
+            token.byte_start += byte_position;
+            token.byte_end += byte_position;
+            token.position += self.position;
+            if let Some(offset_counter) = self.offset_counter.as_mut() {
+                token.offsets = Some(offset_counter.offsets(token.byte_start, token.byte_end));
+            }
+        }
+        self.position += positions;
+
+        Some(Ok(tokens))
     }
 }
 
@@ -187,18 +362,279 @@ impl Clone for Analyzer {
             character_filters,
             tokenizer: self.tokenizer.clone(),
             token_filters,
+            details_filter: self.details_filter.clone(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    #[cfg(all(
-        any(feature = "ipadic", feature = "ipadic-neologd"),
-        feature = "filter"
-    ))]
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::mode::Mode;
+    use lindera_core::offsets::TokenOffsets;
+    use lindera_core::LinderaResult;
+    use lindera_dictionary::{DictionaryBuilderResolver, DictionaryLoader};
+    use lindera_filter::character_filter::unicode_normalize::{
+        UnicodeNormalizeCharacterFilter, UnicodeNormalizeCharacterFilterConfig,
+        UnicodeNormalizeKind,
+    };
+    use lindera_filter::character_filter::BoxCharacterFilter;
+    use lindera_filter::token_filter::korean_stop_tags::{
+        KoreanStopTagsTokenFilter, KoreanStopTagsTokenFilterConfig,
+    };
+    use lindera_filter::token_filter::lowercase::LowercaseTokenFilter;
+    use lindera_filter::token_filter::BoxTokenFilter;
+    use lindera_tokenizer::tokenizer::{ConfigFormat, Tokenizer};
+
     use crate::analyzer::{Analyzer, AnalyzerConfig};
 
+    fn tokenizer(dir: &Path) -> Tokenizer {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("char.def"),
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("unk.def"),
+            "DEFAULT,0,0,1000,未知語\nKANJI,0,0,5000,未知語\n",
+        )
+        .unwrap();
+        fs::write(dir.join("matrix.def"), "1 1\n0 0 0\n").unwrap();
+        fs::write(dir.join("lex.csv"), "東京,0,0,100,地名\n都,0,0,100,接尾\n").unwrap();
+        fs::write(dir.join("schema.json"), r#"{"fields": ["pos"]}"#).unwrap();
+        let builder =
+            DictionaryBuilderResolver::resolve_builder_from_schema(&dir.join("schema.json"))
+                .unwrap();
+        let dictionary =
+            DictionaryLoader::load_dictionary_from_source(builder.as_ref(), dir).unwrap();
+        Tokenizer::new(dictionary, None, Mode::Normal)
+    }
+
+    #[test]
+    fn test_analyze_with_offsets() {
+        let tmp_dir = std::env::temp_dir().join("lindera-analyzer-offsets");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir).with_offsets(true);
+
+        // The half-width "ｶﾞ" is normalized to a single character.
+        let character_filter = BoxCharacterFilter::from(UnicodeNormalizeCharacterFilter::new(
+            UnicodeNormalizeCharacterFilterConfig::new(UnicodeNormalizeKind::NFKC),
+        ));
+        let analyzer = Analyzer::try_new(vec![character_filter], tokenizer, Vec::new()).unwrap();
+
+        let tokens = analyzer.analyze("ｶﾞ東京都").unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| (token.text.as_str(), token.byte_start, token.byte_end))
+                .collect::<Vec<_>>(),
+            vec![("ガ", 0, 6), ("東京", 6, 12), ("都", 12, 15)]
+        );
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.offsets.unwrap())
+                .collect::<Vec<_>>(),
+            // The half-width voiced sound mark extends the grapheme cluster.
+            vec![(0, 2, 0, 1), (2, 4, 1, 3), (4, 5, 3, 4)]
+                .into_iter()
+                .map(|(start, end, grapheme_start, grapheme_end)| TokenOffsets {
+                    char_start: start,
+                    char_end: end,
+                    utf16_start: start,
+                    utf16_end: end,
+                    grapheme_start,
+                    grapheme_end,
+                })
+                .collect::<Vec<_>>()
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_analyze_batch() {
+        let tmp_dir = std::env::temp_dir().join("lindera-analyzer-batch");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        let texts = (0..100).map(|i| "東京都".repeat(i % 7)).collect::<Vec<_>>();
+
+        let tokens = tokenizer.tokenize_batch(&texts).unwrap();
+        assert_eq!(tokens.len(), texts.len());
+        for (text, tokens) in texts.iter().zip(tokens.iter()) {
+            let expected = tokenizer.tokenize(text).unwrap();
+            assert_eq!(
+                tokens
+                    .iter()
+                    .map(|token| (token.text, token.byte_start, token.word_id))
+                    .collect::<Vec<_>>(),
+                expected
+                    .iter()
+                    .map(|token| (token.text, token.byte_start, token.word_id))
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        let analyzer = Analyzer::try_new(Vec::new(), tokenizer, Vec::new()).unwrap();
+        let tokens = analyzer.analyze_batch(&texts).unwrap();
+        assert_eq!(tokens.len(), texts.len());
+        for (text, tokens) in texts.iter().zip(tokens.iter()) {
+            assert_eq!(
+                serde_json::to_value(tokens).unwrap(),
+                serde_json::to_value(analyzer.analyze(text).unwrap()).unwrap()
+            );
+        }
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_analyze_sentences() {
+        let tmp_dir = std::env::temp_dir().join("lindera-analyzer-sentences");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir).with_offsets(true);
+        let character_filter = BoxCharacterFilter::from(UnicodeNormalizeCharacterFilter::new(
+            UnicodeNormalizeCharacterFilterConfig::new(UnicodeNormalizeKind::NFKC),
+        ));
+        let analyzer = Analyzer::try_new(vec![character_filter], tokenizer, Vec::new()).unwrap();
+
+        let text = "東京都。ｶﾞ東京\n都";
+        let sentences = analyzer
+            .analyze_sentences(text)
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            sentences
+                .iter()
+                .map(|tokens| tokens.len())
+                .collect::<Vec<_>>(),
+            vec![3, 3, 1]
+        );
+        assert_eq!(
+            serde_json::to_value(sentences.concat()).unwrap(),
+            serde_json::to_value(analyzer.analyze(text).unwrap()).unwrap()
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_analyze_segmentation_only() {
+        let tmp_dir = std::env::temp_dir().join("lindera-analyzer-segmentation-only");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        tokenizer(&tmp_dir);
+        let builder =
+            DictionaryBuilderResolver::resolve_builder_from_schema(&tmp_dir.join("schema.json"))
+                .unwrap();
+        builder
+            .build_dictionary(&tmp_dir, &tmp_dir.join("dict"))
+            .unwrap();
+        let dictionary =
+            DictionaryLoader::load_dictionary_without_details(tmp_dir.join("dict")).unwrap();
+        let tokenizer = Tokenizer::new(dictionary, None, Mode::Normal);
+        assert!(tokenizer.is_segmentation_only());
+
+        let analyzer = Analyzer::try_new(
+            Vec::new(),
+            tokenizer.clone(),
+            vec![BoxTokenFilter::from(LowercaseTokenFilter::new())],
+        )
+        .unwrap();
+        let tokens = analyzer.analyze("東京都𠮷").unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| token.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["東京", "都", "𠮷"]
+        );
+        assert!(tokens.iter().all(|token| token.details.is_empty()));
+
+        // The filters that read the details are rejected.
+        let stop_tags = BoxTokenFilter::from(KoreanStopTagsTokenFilter::new(
+            KoreanStopTagsTokenFilterConfig::new(["NNG".to_string()].into_iter().collect()),
+        ));
+        assert!(
+            Analyzer::try_new(Vec::new(), tokenizer.clone(), vec![stop_tags.box_clone()]).is_err()
+        );
+        // The deprecated constructor defers the check to the analysis.
+        #[allow(deprecated)]
+        let analyzer = Analyzer::new(Vec::new(), tokenizer, vec![stop_tags]);
+        assert!(analyzer.analyze("東京都").is_err());
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_analyzer_config_formats() {
+        let json = r#"
+        {
+            "character_filters": [
+                {
+                    "kind": "unicode_normalize",
+                    "args": {
+                        "kind": "nfkc"
+                    }
+                }
+            ],
+            "tokenizer": {
+                "dictionary": {
+                    "path": "/path/to/dictionary"
+                },
+                "mode": {
+                    "decompose": {
+                        "kanji_penalty_length_threshold": 3
+                    }
+                }
+            }
+        }
+        "#;
+        let yaml = r#"
+character_filters:
+  - kind: unicode_normalize
+    args:
+      kind: nfkc
+tokenizer:
+  dictionary:
+    path: /path/to/dictionary
+  mode:
+    decompose:
+      kanji_penalty_length_threshold: 3
+"#;
+        let toml = r#"
+[[character_filters]]
+kind = "unicode_normalize"
+args = { kind = "nfkc" }
+
+[tokenizer.dictionary]
+path = "/path/to/dictionary"
+
+[tokenizer.mode.decompose]
+kanji_penalty_length_threshold = 3
+"#;
+        let expected = AnalyzerConfig::from_slice(json.as_bytes()).unwrap();
+        assert_eq!(
+            AnalyzerConfig::from_slice_with_format(yaml.as_bytes(), ConfigFormat::Yaml).unwrap(),
+            expected
+        );
+        assert_eq!(
+            AnalyzerConfig::from_slice_with_format(toml.as_bytes(), ConfigFormat::Toml).unwrap(),
+            expected
+        );
+
+        let tmp_dir = std::env::temp_dir().join("lindera-analyzer-config-formats");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        fs::create_dir_all(&tmp_dir).unwrap();
+        let path = tmp_dir.join("analyzer.yml");
+        fs::write(&path, yaml).unwrap();
+        assert_eq!(AnalyzerConfig::from_file(&path).unwrap(), expected);
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
     #[test]
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     fn test_analyzer_config_from_slice() {
@@ -451,7 +887,8 @@ mod tests {
                 assert_eq!(token.byte_end, 15);
                 assert_eq!(token.position, 0);
                 assert_eq!(token.position_length, 1);
-                assert_eq!(token.details, vec!["UNK".to_string()]);
+                assert!(token.word_id.is_unknown());
+                assert_eq!(token.details[0], "名詞");
             }
             {
                 let token = tokens_iter.next().unwrap();
@@ -537,32 +974,24 @@ mod tests {
             let mut tokens_iter = tokens.iter_mut();
             {
                 let token = tokens_iter.next().unwrap();
-                assert_eq!(token.text, "10".to_string());
+                // The unknown number has the features of unk.def (名詞,数), so it is compounded.
+                assert_eq!(token.text, "10ガロン".to_string());
                 assert_eq!(token.byte_start, 0);
-                assert_eq!(token.byte_end, 6);
-                assert_eq!(token.position, 0);
-                assert_eq!(token.position_length, 1);
-                assert_eq!(token.details, vec!["UNK".to_string()]);
-            }
-            {
-                let token = tokens_iter.next().unwrap();
-                assert_eq!(token.text, "ガロン".to_string());
-                assert_eq!(token.byte_start, 6);
                 assert_eq!(token.byte_end, 9);
-                assert_eq!(token.position, 1);
-                assert_eq!(token.position_length, 1);
+                assert_eq!(token.position, 0);
+                assert_eq!(token.position_length, 2);
                 assert_eq!(
                     token.details,
                     vec![
-                        "名詞".to_string(),
-                        "接尾".to_string(),
-                        "助数詞".to_string(),
+                        "複合語".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
                         "*".to_string(),
                         "*".to_string(),
                         "*".to_string(),
-                        "ガロン".to_string(),
-                        "ガロン".to_string(),
-                        "ガロン".to_string()
+                        "*".to_string(),
+                        "*".to_string()
                     ]
                 );
             }
@@ -594,15 +1023,8 @@ mod tests {
                 let token = tokens_iter.next().unwrap();
                 let start = token.byte_start;
                 let end = token.byte_end;
-                assert_eq!(token.text, "10".to_string());
-                assert_eq!(&text[start..end], "１０");
-            }
-            {
-                let token = tokens_iter.next().unwrap();
-                let start = token.byte_start;
-                let end = token.byte_end;
-                assert_eq!(token.text, "ガロン".to_string());
-                assert_eq!(&text[start..end], "㌎");
+                assert_eq!(token.text, "10ガロン".to_string());
+                assert_eq!(&text[start..end], "１０㌎");
             }
             {
                 let token = tokens_iter.next().unwrap();
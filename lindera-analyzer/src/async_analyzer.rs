@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::sync::mpsc;
+use tokio::task::{self, JoinError, JoinHandle};
+
+use lindera_core::error::{LinderaError, LinderaErrorKind};
+use lindera_core::LinderaResult;
+use lindera_filter::token::Token;
+
+use crate::analyzer::Analyzer;
+
+/// The number of sentences that are analyzed ahead of the consumer of a stream.
+const SENTENCE_BUFFER_SIZE: usize = 16;
+
+fn join_error(err: JoinError) -> LinderaError {
+    LinderaErrorKind::Content.with_error(err)
+}
+
+/// Runs an `Analyzer` on the blocking thread pool of Tokio, so that analyzing large texts does not
+/// block the threads of the async runtime. The analyzer is shared by the clones.
+#[derive(Clone)]
+pub struct AsyncAnalyzer {
+    analyzer: Arc<Analyzer>,
+}
+
+impl AsyncAnalyzer {
+    pub fn new(analyzer: Analyzer) -> Self {
+        Self::from(Arc::new(analyzer))
+    }
+
+    pub fn analyzer(&self) -> &Analyzer {
+        &self.analyzer
+    }
+
+    /// Analyze the text on the blocking thread pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be analyzed.
+    ///
+    /// returns: LinderaResult<Vec<Token>>
+    ///
+    pub async fn analyze(&self, text: String) -> LinderaResult<Vec<Token>> {
+        let analyzer = Arc::clone(&self.analyzer);
+        task::spawn_blocking(move || analyzer.analyze(&text))
+            .await
+            .map_err(join_error)?
+    }
+
+    /// Analyze the text on the blocking thread pool one sentence at a time, and return the tokens
+    /// as a `Stream` while the next sentences are analyzed.
+    ///
+    /// The analysis stops before the next sentence when the stream is cancelled or dropped, or
+    /// after an error, which is the last item of the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be analyzed.
+    ///
+    /// returns: AnalyzeStream
+    ///
+    pub fn analyze_stream(&self, text: String) -> AnalyzeStream {
+        let analyzer = Arc::clone(&self.analyzer);
+        let cancel_handle = CancelHandle::default();
+        let (sender, receiver) = mpsc::channel(SENTENCE_BUFFER_SIZE);
+
+        let task_cancel_handle = cancel_handle.clone();
+        let task = task::spawn_blocking(move || {
+            let mut sentences = analyzer.analyze_sentences(&text);
+            while !task_cancel_handle.is_cancelled() {
+                let Some(tokens) = sentences.next() else {
+                    break;
+                };
+                let is_err = tokens.is_err();
+                // The stream was dropped if the tokens cannot be sent.
+                if sender.blocking_send(tokens).is_err() || is_err {
+                    break;
+                }
+            }
+        });
+
+        AnalyzeStream {
+            receiver,
+            task: Some(task),
+            cancel_handle,
+            tokens: VecDeque::new(),
+        }
+    }
+}
+
+impl From<Arc<Analyzer>> for AsyncAnalyzer {
+    fn from(analyzer: Arc<Analyzer>) -> Self {
+        Self { analyzer }
+    }
+}
+
+/// Cancels the analysis of an `AnalyzeStream`, e.g. from another task.
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    /// Stop the analysis before the next sentence. The stream ends without returning the tokens
+    /// that are not returned yet.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The tokens of a text analyzed on the blocking thread pool, in the order of the text.
+pub struct AnalyzeStream {
+    /// The tokens of the sentences, or the error that stopped the analysis.
+    receiver: mpsc::Receiver<LinderaResult<Vec<Token>>>,
+    /// The analysis, until it is found to have finished.
+    task: Option<JoinHandle<()>>,
+    cancel_handle: CancelHandle,
+    /// The tokens of the current sentence that are not returned yet.
+    tokens: VecDeque<Token>,
+}
+
+impl AnalyzeStream {
+    /// Returns a handle that cancels the analysis.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel_handle.clone()
+    }
+
+    /// Stop the analysis before the next sentence.
+    pub fn cancel(&self) {
+        self.cancel_handle.cancel();
+    }
+
+    /// Returns the next token, or `None` at the end of the text or after a cancellation.
+    pub async fn next_token(&mut self) -> Option<LinderaResult<Token>> {
+        future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl Stream for AnalyzeStream {
+    type Item = LinderaResult<Token>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.cancel_handle.is_cancelled() {
+                return Poll::Ready(None);
+            }
+            if let Some(token) = this.tokens.pop_front() {
+                return Poll::Ready(Some(Ok(token)));
+            }
+
+            match this.receiver.poll_recv(cx) {
+                Poll::Ready(Some(Ok(tokens))) => this.tokens.extend(tokens),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => {
+                    // The analysis has finished. Report it if it panicked.
+                    let Some(task) = this.task.as_mut() else {
+                        return Poll::Ready(None);
+                    };
+                    return match Pin::new(task).poll(cx) {
+                        Poll::Ready(result) => {
+                            this.task = None;
+                            Poll::Ready(result.err().map(|err| Err(join_error(err))))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    };
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Drop for AnalyzeStream {
+    fn drop(&mut self) {
+        // Do not analyze the next sentences for nobody.
+        self.cancel_handle.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::mode::Mode;
+    use lindera_dictionary::{DictionaryBuilderResolver, DictionaryLoader};
+    use lindera_tokenizer::tokenizer::Tokenizer;
+
+    use crate::analyzer::Analyzer;
+    use crate::async_analyzer::AsyncAnalyzer;
+
+    fn analyzer(dir: &Path) -> Analyzer {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("char.def"),
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("unk.def"),
+            "DEFAULT,0,0,1000,未知語\nKANJI,0,0,5000,未知語\n",
+        )
+        .unwrap();
+        fs::write(dir.join("matrix.def"), "1 1\n0 0 0\n").unwrap();
+        fs::write(
+            dir.join("lex.csv"),
+            "東京,0,0,100,地名\n都,0,0,100,接尾\n。,0,0,100,記号\n",
+        )
+        .unwrap();
+        fs::write(dir.join("schema.json"), r#"{"fields": ["pos"]}"#).unwrap();
+        let builder =
+            DictionaryBuilderResolver::resolve_builder_from_schema(&dir.join("schema.json"))
+                .unwrap();
+        let dictionary =
+            DictionaryLoader::load_dictionary_from_source(builder.as_ref(), dir).unwrap();
+        Analyzer::try_new(
+            Vec::new(),
+            Tokenizer::new(dictionary, None, Mode::Normal),
+            Vec::new(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_async_analyzer() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let analyzer = AsyncAnalyzer::new(analyzer(tmp_dir.path()));
+
+        let text = "東京都。".repeat(100);
+        let expected = serde_json::to_value(analyzer.analyzer().analyze(&text).unwrap()).unwrap();
+        let tokens = analyzer.analyze(text.clone()).await.unwrap();
+        assert_eq!(serde_json::to_value(&tokens).unwrap(), expected);
+
+        let mut stream = analyzer.analyze_stream(text.clone());
+        let mut tokens = Vec::new();
+        while let Some(token) = stream.next_token().await {
+            tokens.push(token.unwrap());
+        }
+        assert_eq!(serde_json::to_value(&tokens).unwrap(), expected);
+        assert_eq!(tokens[299].byte_start, text.len() - "。".len());
+
+        // The analysis stops after a cancellation.
+        let mut stream = analyzer.analyze_stream("東京都。".repeat(100_000));
+        let token = stream.next_token().await.unwrap().unwrap();
+        assert_eq!(token.text, "東京");
+        stream.cancel_handle().cancel();
+        assert!(stream.next_token().await.is_none());
+    }
+}
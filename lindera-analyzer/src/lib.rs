@@ -1 +1,3 @@
 pub mod analyzer;
+#[cfg(feature = "tokio")]
+pub mod async_analyzer;
@@ -1,6 +1,8 @@
 use std::error::Error;
 use std::path::Path;
 
+use sha2::{Digest, Sha256};
+
 use lindera_core::dictionary_builder::DictionaryBuilder;
 
 pub struct FetchParams {
@@ -16,6 +18,90 @@ pub struct FetchParams {
 
     /// URL from which to fetch the asset
     pub download_url: &'static str,
+
+    /// Prefix of the environment variables that control the fetch, e.g. `LINDERA_IPADIC`.
+    ///
+    /// `<PREFIX>_SOURCE` points to a local tarball or a pre-extracted source directory
+    /// to use instead of downloading `download_url`.
+    /// `<PREFIX>_SHA256` overrides the pinned SHA-256 of the tarball.
+    pub env_prefix: &'static str,
+
+    /// Pinned SHA-256 of the tarball as a hex string
+    pub sha256: Option<&'static str>,
+}
+
+/// Where the dictionary source comes from.
+enum Source {
+    /// Download the tarball from `download_url`
+    Download,
+    /// Local tarball
+    Archive(std::path::PathBuf),
+    /// Local pre-extracted source directory
+    Directory(std::path::PathBuf),
+}
+
+/// The identity of the tarball a cached dictionary is built from: its location, i.e. the download
+/// URL or the local path, and its SHA-256.
+///
+/// A source directory has no identity, since it cannot be verified.
+fn source_identity(source: &Source, download_url: &str, sha256: &str) -> Option<String> {
+    let location = match source {
+        Source::Download => download_url.to_string(),
+        Source::Archive(archive_path) => archive_path.display().to_string(),
+        Source::Directory(_) => return None,
+    };
+    Some(format!(
+        "{}\n{}\n",
+        location,
+        sha256.trim().to_ascii_lowercase()
+    ))
+}
+
+/// Whether the cached dictionary was built from the expected tarball, according to the identity
+/// stored next to it.
+///
+/// Without an expected SHA-256, the tarball cannot be identified, and the dictionary is rebuilt.
+fn is_cache_valid(
+    identity_path: &Path,
+    source: &Source,
+    download_url: &str,
+    expected_sha256: Option<&str>,
+) -> bool {
+    let Some(expected_identity) = expected_sha256
+        .and_then(|expected_sha256| source_identity(source, download_url, expected_sha256))
+    else {
+        return false;
+    };
+    std::fs::read_to_string(identity_path)
+        .map(|cached_identity| cached_identity == expected_identity)
+        .unwrap_or(false)
+}
+
+/// The SHA-256 of `data` as a hex string.
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>()
+}
+
+/// Verify the SHA-256 of the tarball if an expected value is given.
+fn verify_sha256(data: &[u8], expected: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+
+    let actual = sha256_hex(data);
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(format!(
+            "SHA-256 mismatch: expected {}, actual {}",
+            expected.trim(),
+            actual
+        )
+        .into());
+    }
+
+    Ok(())
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -34,7 +120,6 @@ fn empty_directory(dir: &Path) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-#[cfg(target_os = "windows")]
 fn copy_dir_all(src: &Path, dst: &Path) -> Result<(), Box<dyn Error>> {
     if !dst.exists() {
         std::fs::create_dir(dst)?;
@@ -69,6 +154,30 @@ pub fn fetch(params: FetchParams, builder: impl DictionaryBuilder) -> Result<(),
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=Cargo.toml");
 
+    // A local tarball or source directory given by `<PREFIX>_SOURCE` is used instead of downloading
+    let source_env = format!("{}_SOURCE", params.env_prefix);
+    let sha256_env = format!("{}_SHA256", params.env_prefix);
+    println!("cargo:rerun-if-env-changed={}", source_env);
+    println!("cargo:rerun-if-env-changed={}", sha256_env);
+
+    let source = match env::var_os(&source_env).map(PathBuf::from) {
+        Some(path) if path.is_dir() => {
+            println!("cargo:rerun-if-changed={}", path.display());
+            Source::Directory(path)
+        }
+        Some(path) if path.is_file() => {
+            println!("cargo:rerun-if-changed={}", path.display());
+            Source::Archive(path)
+        }
+        Some(path) => {
+            return Err(format!("{} does not exist: {}", source_env, path.display()).into());
+        }
+        None => Source::Download,
+    };
+    let expected_sha256 = env::var(&sha256_env)
+        .ok()
+        .or_else(|| params.sha256.map(|s| s.to_string()));
+
     // Directory path for build package
     // if the `LINDERA_CACHE` variable is defined, behaves like a cache, where data is invalidated only:
     // - on new lindera-assets version
@@ -95,10 +204,24 @@ pub fn fetch(params: FetchParams, builder: impl DictionaryBuilder) -> Result<(),
 
     let output_dir = build_dir.join(params.output_dir);
 
-    // Fast path where the data is already in cache
-    if is_cache && output_dir.is_dir() {
+    // Identity of the tarball the cached dictionary was built from
+    let cache_identity_path = build_dir.join(format!("{}.source", params.output_dir));
+
+    // Fast path where the data is already in cache.
+    // The cache is only used if it was built from the same tarball with the expected SHA-256.
+    if is_cache
+        && output_dir.is_dir()
+        && is_cache_valid(
+            &cache_identity_path,
+            &source,
+            params.download_url,
+            expected_sha256.as_deref(),
+        )
+    {
         return Ok(());
     }
+    let _ = std::fs::remove_file(&cache_identity_path);
+    let mut source_sha256 = None;
 
     if std::env::var("DOCS_RS").is_ok() {
         // Create directory for dummy input directory for build docs
@@ -121,40 +244,49 @@ pub fn fetch(params: FetchParams, builder: impl DictionaryBuilder) -> Result<(),
         let mut dummy_matrix_def = File::create(input_dir.join("matrix.def"))?;
         dummy_matrix_def.write_all(b"0 1 0\n")?;
     } else {
-        // Source file path for build package
-        let source_path_for_build = &build_dir.join(params.file_name);
-
-        // Download source file to build directory
-        // copy(&source_path, &source_path_for_build)?;
-        let tmp_path = Path::new(&build_dir).join(params.file_name.to_owned() + ".download");
-
-        // Download a tarball
-        let resp = ureq::get(params.download_url).call()?;
-        let mut dest = File::create(&tmp_path)?;
-
-        io::copy(&mut resp.into_reader(), &mut dest)?;
-        dest.flush()?;
-
-        rename(tmp_path, source_path_for_build).expect("Failed to rename temporary file");
-
-        // Decompress a tar.gz file
-        let tmp_extract_path =
-            Path::new(&build_dir).join(format!("tmp-archive-{}", params.input_dir));
-        let tmp_extracted_path = tmp_extract_path.join(params.input_dir);
-        let _ = std::fs::remove_dir_all(&tmp_extract_path);
-        std::fs::create_dir_all(&tmp_extract_path)?;
-
-        let mut tar_gz = File::open(source_path_for_build)?;
-        let mut buffer = Vec::new();
-        tar_gz.read_to_end(&mut buffer)?;
-        let cursor = Cursor::new(buffer);
-        let decoder = GzDecoder::new(cursor);
-        let mut archive = Archive::new(decoder);
-        archive.unpack(&tmp_extract_path)?;
-        rename(tmp_extracted_path, &input_dir).expect("Failed to rename archive directory");
-        let _ = std::fs::remove_dir_all(&tmp_extract_path);
-        drop(dest);
-        let _ = std::fs::remove_file(source_path_for_build);
+        match &source {
+            Source::Directory(source_dir) => {
+                // Copy the pre-extracted source directory, so that the original is left untouched
+                let _ = std::fs::remove_dir_all(&input_dir);
+                copy_dir_all(source_dir, &input_dir)?;
+            }
+            Source::Archive(_) | Source::Download => {
+                let mut buffer = Vec::new();
+                if let Source::Archive(archive_path) = &source {
+                    // Read a local tarball
+                    File::open(archive_path)?.read_to_end(&mut buffer)?;
+                } else {
+                    // Download a tarball
+                    let resp = ureq::get(params.download_url).call()?;
+                    io::copy(&mut resp.into_reader(), &mut buffer)?;
+                }
+
+                verify_sha256(&buffer, expected_sha256.as_deref())
+                    .map_err(|err| format!("{}: {}", params.file_name, err))?;
+                if expected_sha256.is_none() {
+                    println!(
+                        "cargo:warning={} is not verified; set {} to pin its SHA-256",
+                        params.file_name, sha256_env
+                    );
+                }
+                source_sha256 = Some(sha256_hex(&buffer));
+
+                // Decompress a tar.gz file
+                let tmp_extract_path =
+                    Path::new(&build_dir).join(format!("tmp-archive-{}", params.input_dir));
+                let tmp_extracted_path = tmp_extract_path.join(params.input_dir);
+                let _ = std::fs::remove_dir_all(&tmp_extract_path);
+                std::fs::create_dir_all(&tmp_extract_path)?;
+
+                let cursor = Cursor::new(buffer);
+                let decoder = GzDecoder::new(cursor);
+                let mut archive = Archive::new(decoder);
+                archive.unpack(&tmp_extract_path)?;
+                let _ = std::fs::remove_dir_all(&input_dir);
+                rename(tmp_extracted_path, &input_dir).expect("Failed to rename archive directory");
+                let _ = std::fs::remove_dir_all(&tmp_extract_path);
+            }
+        }
     }
 
     let tmp_path = build_dir.join(format!("tmp-output-{}", params.output_dir));
@@ -191,5 +323,100 @@ pub fn fetch(params: FetchParams, builder: impl DictionaryBuilder) -> Result<(),
 
     let _ = std::fs::remove_dir_all(&input_dir);
 
+    if is_cache {
+        if let Some(identity) = source_sha256
+            .and_then(|source_sha256| source_identity(&source, params.download_url, &source_sha256))
+        {
+            std::fs::write(&cache_identity_path, identity)?;
+        }
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::{is_cache_valid, sha256_hex, source_identity, verify_sha256, Source};
+
+    #[test]
+    fn test_verify_sha256() {
+        let data = b"lindera";
+        let sha256 = "10a64787a13a0c45d70f07e2afee32149590d92610e59db48df6bf40211c1086";
+
+        assert!(verify_sha256(data, None).is_ok());
+        assert!(verify_sha256(data, Some(sha256)).is_ok());
+        assert!(verify_sha256(data, Some(&sha256.to_uppercase())).is_ok());
+        assert!(verify_sha256(b"lindera-assets", Some(sha256)).is_err());
+        assert_eq!(sha256_hex(data), sha256);
+    }
+
+    #[test]
+    fn test_is_cache_valid() {
+        let url = "https://example.com/dict.tar.gz";
+        let sha256 = "10a64787a13a0c45d70f07e2afee32149590d92610e59db48df6bf40211c1086";
+        let other_sha256 = "0000000000000000000000000000000000000000000000000000000000000000";
+
+        let tmp_dir = std::env::temp_dir().join("lindera-assets-cache");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        fs::create_dir_all(&tmp_dir).unwrap();
+        let identity_path = tmp_dir.join("dict.source");
+
+        // Nothing is cached yet.
+        assert!(!is_cache_valid(
+            &identity_path,
+            &Source::Download,
+            url,
+            Some(sha256)
+        ));
+
+        fs::write(
+            &identity_path,
+            source_identity(&Source::Download, url, sha256).unwrap(),
+        )
+        .unwrap();
+        assert!(is_cache_valid(
+            &identity_path,
+            &Source::Download,
+            url,
+            Some(&sha256.to_uppercase())
+        ));
+
+        // A missing expected SHA-256 is rebuilt.
+        assert!(!is_cache_valid(
+            &identity_path,
+            &Source::Download,
+            url,
+            None
+        ));
+        // Another SHA-256.
+        assert!(!is_cache_valid(
+            &identity_path,
+            &Source::Download,
+            url,
+            Some(other_sha256)
+        ));
+        // Another URL.
+        assert!(!is_cache_valid(
+            &identity_path,
+            &Source::Download,
+            "https://example.com/other.tar.gz",
+            Some(sha256)
+        ));
+        // A local tarball with the same SHA-256.
+        let archive = Source::Archive(PathBuf::from("/tmp/dict.tar.gz"));
+        assert!(!is_cache_valid(&identity_path, &archive, url, Some(sha256)));
+        // A source directory is always rebuilt.
+        let directory = Source::Directory(PathBuf::from("/tmp/dict"));
+        assert!(!is_cache_valid(
+            &identity_path,
+            &directory,
+            url,
+            Some(sha256)
+        ));
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
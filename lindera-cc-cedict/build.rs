@@ -7,6 +7,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         input_dir: "CC-CEDICT-MeCab-0.1.0-20200409",
         output_dir: "lindera-cc-cedict",
         download_url: "https://dlwqk3ibdg1xh.cloudfront.net/CC-CEDICT-MeCab-0.1.0-20200409.tar.gz",
+        env_prefix: "LINDERA_CC_CEDICT",
+        sha256: None,
         dummy_input:
         "测试,0,0,-1131,*,*,*,*,ce4 shi4,測試,测试,to test (machinery etc)/to test (students)/test/quiz/exam/beta (software)/\n",
     },
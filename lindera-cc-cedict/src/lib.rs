@@ -4,8 +4,8 @@ use std::env;
 
 use lindera_core::{
     character_definition::CharacterDefinitions, connection::ConnectionCostMatrix,
-    dictionary::Dictionary, prefix_dict::PrefixDict, unknown_dictionary::UnknownDictionary,
-    LinderaResult,
+    dictionary::Dictionary, id_definition::IdDefinitions, prefix_dict::PrefixDict,
+    unknown_dictionary::UnknownDictionary, LinderaResult,
 };
 #[cfg(feature = "compress")]
 use lindera_decompress::decompress;
@@ -107,6 +107,18 @@ decompress_data!(
 #[cfg(not(feature = "cc-cedict"))]
 decompress_data!(WORDS_DATA, &[], "dict.words");
 
+#[cfg(feature = "cc-cedict")]
+decompress_data!(
+    ID_DEFINITION_DATA,
+    include_bytes!(concat!(
+        env!("LINDERA_WORKDIR"),
+        "/lindera-cc-cedict/id_def.bin"
+    )),
+    "id_def.bin"
+);
+#[cfg(not(feature = "cc-cedict"))]
+decompress_data!(ID_DEFINITION_DATA, &[], "id_def.bin");
+
 pub fn load_dictionary() -> LinderaResult<Dictionary> {
     Ok(Dictionary {
         dict: prefix_dict(),
@@ -115,6 +127,21 @@ pub fn load_dictionary() -> LinderaResult<Dictionary> {
         unknown_dictionary: unknown_dict()?,
         words_idx_data: words_idx_data(),
         words_data: words_data(),
+        id_definitions: id_def()?,
+    })
+}
+
+/// Load the dictionary without the details of the words, for tokenizers that only segment the
+/// text. `dict.words` and `dict.wordsidx` are neither copied nor decompressed.
+pub fn load_dictionary_without_details() -> LinderaResult<Dictionary> {
+    Ok(Dictionary {
+        dict: prefix_dict(),
+        cost_matrix: connection(),
+        char_definitions: char_def()?,
+        unknown_dictionary: unknown_dict()?,
+        words_idx_data: Cow::Borrowed(&[]),
+        words_data: Cow::Borrowed(&[]),
+        id_definitions: id_def()?,
     })
 }
 
@@ -123,6 +150,11 @@ pub fn char_def() -> LinderaResult<CharacterDefinitions> {
     CharacterDefinitions::load(&CHAR_DEFINITION_DATA)
 }
 
+pub fn id_def() -> LinderaResult<IdDefinitions> {
+    #[allow(clippy::needless_borrow)]
+    IdDefinitions::load(&ID_DEFINITION_DATA)
+}
+
 pub fn connection() -> ConnectionCostMatrix {
     #[cfg(feature = "compress")]
     {
@@ -13,9 +13,10 @@ use lindera::Analyzer;
 use lindera::{CharacterFilterLoader, TokenFilterLoader};
 
 use lindera::{
-    BoxCharacterFilter, BoxTokenFilter, DictionaryBuilderResolver, DictionaryConfig,
-    DictionaryKind, DictionaryLoader, LinderaError, LinderaErrorKind, LinderaResult, Mode,
-    Tokenizer, UserDictionaryConfig,
+    BoxCharacterFilter, BoxTokenFilter, Dictionary, DictionaryBuilderResolver, DictionaryConfig,
+    DictionaryDiff, DictionaryKind, DictionaryLoader, LinderaError, LinderaErrorKind,
+    LinderaResult, Mode, Tokenizer, TokenizerComparison, TokenizerConfig, TrainerOptions,
+    UserDictionaryConfig, UserDictionaryFormat,
 };
 
 #[derive(Debug, Parser)]
@@ -30,6 +31,9 @@ enum Commands {
     List(ListArgs),
     Tokenize(TokenizeArgs),
     Build(BuildArgs),
+    Train(TrainArgs),
+    Dict(DictArgs),
+    Compare(CompareArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -57,6 +61,21 @@ struct TokenizeArgs {
         help = "User dictionary file path"
     )]
     user_dic_file: Option<PathBuf>,
+    #[clap(
+        long = "user-dic-format",
+        help = "User dictionary format (csv, tsv, json, jsonl or bin). Detected from the extension if not specified"
+    )]
+    user_dic_format: Option<UserDictionaryFormat>,
+    #[clap(
+        long = "user-dic-schema",
+        help = "Schema file path of the dictionary, used to build a user dictionary source for a dictionary built from a schema"
+    )]
+    user_dic_schema: Option<PathBuf>,
+    #[clap(
+        long = "user-dic-estimate-cost",
+        help = "Estimate the context ids and the word costs of the simple entries of a user dictionary source from the system dictionary"
+    )]
+    user_dic_estimate_cost: bool,
     #[clap(
         short = 'm',
         long = "mode",
@@ -71,6 +90,11 @@ struct TokenizeArgs {
         help = "Output format"
     )]
     output_format: String,
+    #[clap(
+        long = "offsets",
+        help = "Include the offsets in characters, UTF-16 code units and grapheme clusters in the JSON output"
+    )]
+    offsets: bool,
     #[clap(short = 'C', long = "character-filter", help = "Character filter")]
     character_filters: Option<Vec<String>>,
     #[clap(short = 'T', long = "token-filter", help = "Token filter")]
@@ -84,14 +108,231 @@ struct TokenizeArgs {
 struct BuildArgs {
     #[clap(short = 'u', long = "build-user-dic", help = "Build user dictionary")]
     build_user_dic: bool,
-    #[clap(short = 't', long = "dic-type", help = "Dictionary type")]
-    dic_type: DictionaryKind,
+    #[clap(
+        short = 't',
+        long = "dic-type",
+        required_unless_present = "schema",
+        help = "Dictionary type"
+    )]
+    dic_type: Option<DictionaryKind>,
+    #[clap(
+        short = 's',
+        long = "schema",
+        conflicts_with = "dic_type",
+        help = "Dictionary schema file path, used to build a dictionary of an arbitrary format"
+    )]
+    schema: Option<PathBuf>,
+    #[clap(
+        short = 'e',
+        long = "estimate-cost",
+        help = "Estimate the costs and context ids of simple user dictionary entries from the system dictionary"
+    )]
+    estimate_cost: bool,
+    #[clap(
+        short = 'd',
+        long = "dic-dir",
+        help = "System dictionary directory path used for the estimation"
+    )]
+    dic_dir: Option<PathBuf>,
+    #[clap(
+        short = 'c',
+        long = "check",
+        conflicts_with = "build_user_dic",
+        help = "Validate the dictionary source and report the problems without building it"
+    )]
+    check: bool,
     #[clap(help = "Dictionary source path")]
     src_path: PathBuf,
-    #[clap(help = "Dictionary destination path")]
+    #[clap(
+        required_unless_present = "check",
+        help = "Dictionary destination path"
+    )]
+    dest_path: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "Train the costs of a dictionary source with an annotated corpus",
+    version
+)]
+struct TrainArgs {
+    #[clap(
+        short = 'e',
+        long = "encoding",
+        default_value = "UTF-8",
+        help = "Encoding of the dictionary source and the corpus"
+    )]
+    encoding: String,
+    #[clap(
+        short = 'i',
+        long = "max-iterations",
+        default_value = "10",
+        help = "Number of iterations over the corpus"
+    )]
+    max_iterations: usize,
+    #[clap(
+        short = 'c',
+        long = "regularization-coefficient",
+        default_value = "1.0",
+        help = "Inverse of the strength of the L2 regularization"
+    )]
+    regularization_coefficient: f64,
+    #[clap(
+        short = 'r',
+        long = "learning-rate",
+        default_value = "0.1",
+        help = "Learning rate"
+    )]
+    learning_rate: f64,
+    #[clap(
+        short = 'f',
+        long = "cost-factor",
+        default_value = "700.0",
+        help = "Factor to convert the trained weights to costs"
+    )]
+    cost_factor: f64,
+    #[clap(
+        short = 'p',
+        long = "pos-fields",
+        default_value = "4",
+        help = "Number of part-of-speech fields of the context attributes without a rewrite rule"
+    )]
+    pos_fields: usize,
+    #[clap(help = "Seed dictionary source path")]
+    seed_path: PathBuf,
+    #[clap(help = "Annotated corpus file path")]
+    corpus_file: PathBuf,
+    #[clap(help = "Trained dictionary source destination path")]
     dest_path: PathBuf,
 }
 
+#[derive(Debug, clap::Args)]
+#[clap(author, about = "Inspect morphological analysis dictionaries", version)]
+struct DictArgs {
+    #[clap(subcommand)]
+    command: DictCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum DictCommands {
+    Diff(DictDiffArgs),
+    Export(DictExportArgs),
+}
+
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "Compare two dictionaries, each of which is a built dictionary or a dictionary source",
+    version
+)]
+struct DictDiffArgs {
+    #[clap(
+        short = 't',
+        long = "dic-type",
+        help = "Dictionary type, used to build the paths that are dictionary sources"
+    )]
+    dic_type: Option<DictionaryKind>,
+    #[clap(
+        short = 's',
+        long = "schema",
+        conflicts_with = "dic_type",
+        help = "Dictionary schema file path, used to build the paths that are dictionary sources"
+    )]
+    schema: Option<PathBuf>,
+    #[clap(long = "summary", help = "Print only the number of changes")]
+    summary: bool,
+    #[clap(help = "Old dictionary path")]
+    old_path: PathBuf,
+    #[clap(help = "New dictionary path")]
+    new_path: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "Export a built dictionary back to a dictionary source",
+    version
+)]
+struct DictExportArgs {
+    #[clap(
+        short = 't',
+        long = "dic-type",
+        help = "Dictionary type, of which the source layout is exported"
+    )]
+    dic_type: Option<DictionaryKind>,
+    #[clap(
+        short = 's',
+        long = "schema",
+        conflicts_with = "dic_type",
+        help = "Dictionary schema file path, of which the source layout is exported"
+    )]
+    schema: Option<PathBuf>,
+    #[clap(
+        short = 'd',
+        long = "dic-dir",
+        help = "Built dictionary directory path. The self-contained dictionary of the dictionary type is exported if not specified"
+    )]
+    dic_dir: Option<PathBuf>,
+    #[clap(help = "Output directory path")]
+    output_dir: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+#[clap(
+    author,
+    about = "Tokenize a corpus with two dictionary configurations and report the sentences that changed",
+    version
+)]
+struct CompareArgs {
+    #[clap(long = "old-dic-type", help = "Old dictionary type")]
+    old_dic_type: Option<DictionaryKind>,
+    #[clap(long = "old-dic-dir", help = "Old dictionary directory path")]
+    old_dic_dir: Option<PathBuf>,
+    #[clap(long = "old-user-dic-file", help = "Old user dictionary file path")]
+    old_user_dic_file: Option<PathBuf>,
+    #[clap(long = "new-dic-type", help = "New dictionary type")]
+    new_dic_type: Option<DictionaryKind>,
+    #[clap(long = "new-dic-dir", help = "New dictionary directory path")]
+    new_dic_dir: Option<PathBuf>,
+    #[clap(long = "new-user-dic-file", help = "New user dictionary file path")]
+    new_user_dic_file: Option<PathBuf>,
+    #[clap(
+        long = "user-dic-estimate-cost",
+        help = "Estimate the context ids and the word costs of the simple entries of the user dictionary sources from the system dictionaries"
+    )]
+    user_dic_estimate_cost: bool,
+    #[clap(
+        short = 'm',
+        long = "mode",
+        default_value = "normal",
+        help = "Tokenization mode. normal"
+    )]
+    mode: Mode,
+    #[clap(
+        short = 'p',
+        long = "pos-fields",
+        help = "Number of leading details compared as the part of speech. All the details by default"
+    )]
+    pos_fields: Option<usize>,
+    #[clap(
+        short = 'e',
+        long = "examples",
+        default_value = "10",
+        help = "Number of changed sentences shown as examples"
+    )]
+    examples: usize,
+    #[clap(
+        short = 'o',
+        long = "output-format",
+        default_value = "text",
+        help = "Output format. text or json"
+    )]
+    output_format: String,
+    #[clap(help = "Corpus file path, one sentence per line")]
+    input_file: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Copy)]
 /// Formatter type
 pub enum Format {
@@ -120,6 +361,12 @@ fn main() -> LinderaResult<()> {
         Commands::List(args) => list(args),
         Commands::Tokenize(args) => tokenize(args),
         Commands::Build(args) => build(args),
+        Commands::Train(args) => train(args),
+        Commands::Compare(args) => compare(args),
+        Commands::Dict(args) => match args.command {
+            DictCommands::Diff(args) => dict_diff(args),
+            DictCommands::Export(args) => dict_export(args),
+        },
     }
 }
 
@@ -189,23 +436,13 @@ fn tokenize(args: TokenizeArgs) -> LinderaResult<()> {
         Some(path) => Some(UserDictionaryConfig {
             kind: args.dic_type,
             path,
+            format: args.user_dic_format,
+            schema: args.user_dic_schema,
+            estimate_cost: args.user_dic_estimate_cost,
         }),
         None => None,
     };
 
-    // Dictionary
-    let dictionary = DictionaryLoader::load_dictionary_from_config(dictionary_conf)?;
-
-    // User dictionary
-    let user_dictionary = match user_dictionary_conf {
-        Some(ud_conf) => Some(DictionaryLoader::load_user_dictionary_from_config(ud_conf)?),
-        None => None,
-    };
-    let mode = args.mode;
-
-    // Tokenizer
-    let tokenizer = Tokenizer::new(dictionary, user_dictionary, mode);
-
     // output format
     let output_format = Format::from_str(args.output_format.as_str())?;
 
@@ -227,7 +464,39 @@ fn tokenize(args: TokenizeArgs) -> LinderaResult<()> {
         token_filters.push(token_filter);
     }
 
-    let analyzer = Analyzer::new(character_filters, tokenizer, token_filters);
+    // The wakati output only needs the surfaces, so the details are not loaded unless a token
+    // filter reads them or the costs of the user dictionary are estimated from them.
+    let segmentation_only = matches!(output_format, Format::Wakati)
+        && !args.user_dic_estimate_cost
+        && !token_filters
+            .iter()
+            .any(|token_filter| token_filter.uses_details());
+
+    // Dictionary
+    let dictionary = if segmentation_only {
+        DictionaryLoader::load_dictionary_from_config_without_details(dictionary_conf)?
+    } else {
+        DictionaryLoader::load_dictionary_from_config(dictionary_conf)?
+    };
+
+    // User dictionary
+    let user_dictionary = match user_dictionary_conf {
+        Some(ud_conf) => Some(if segmentation_only {
+            DictionaryLoader::load_user_dictionary_from_config_without_details(ud_conf)?
+        } else {
+            DictionaryLoader::load_user_dictionary_from_config_with_dictionary(
+                ud_conf,
+                &dictionary,
+            )?
+        }),
+        None => None,
+    };
+    let mode = args.mode;
+
+    // Tokenizer
+    let tokenizer = Tokenizer::new(dictionary, user_dictionary, mode).with_offsets(args.offsets);
+
+    let analyzer = Analyzer::try_new(character_filters, tokenizer, token_filters)?;
 
     // input file
     let mut reader: Box<dyn BufRead> = if let Some(input_file) = args.input_file {
@@ -280,18 +549,202 @@ fn tokenize(args: TokenizeArgs) -> LinderaResult<()> {
 }
 
 fn build(args: BuildArgs) -> LinderaResult<()> {
-    let builder = DictionaryBuilderResolver::resolve_builder(args.dic_type)?;
+    let builder = match (&args.schema, &args.dic_type) {
+        (Some(schema), _) => DictionaryBuilderResolver::resolve_builder_from_schema(schema)?,
+        (None, Some(dic_type)) => DictionaryBuilderResolver::resolve_builder(dic_type.clone())?,
+        (None, None) => {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "Dictionary type or schema must be specified"
+            )))
+        }
+    };
+
+    if args.check {
+        let report = builder.validate_dictionary(&args.src_path)?;
+        print!("{}", report);
+        if !report.is_valid() {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "{} problems found in {}",
+                report.issues.len(),
+                args.src_path.display()
+            )));
+        }
+        return Ok(());
+    }
+
+    let dest_path = args.dest_path.ok_or_else(|| {
+        LinderaErrorKind::Args.with_error(anyhow::anyhow!("Destination path must be specified"))
+    })?;
 
     if args.build_user_dic {
         let output_file = if let Some(filename) = args.src_path.file_name() {
-            let mut output_file = Path::new(&args.dest_path).join(filename);
+            let mut output_file = Path::new(&dest_path).join(filename);
             output_file.set_extension("bin");
             output_file
         } else {
             return Err(LinderaErrorKind::Io.with_error(anyhow::anyhow!("failed to get filename")));
         };
-        builder.build_user_dictionary(&args.src_path, &output_file)
+        if args.estimate_cost {
+            // Use the external system dictionary if specified, otherwise the contained one.
+            let dictionary_conf = match args.dic_dir {
+                Some(path) => DictionaryConfig {
+                    kind: None,
+                    path: Some(path),
+                },
+                None => DictionaryConfig {
+                    kind: args.dic_type,
+                    path: None,
+                },
+            };
+            let dictionary = DictionaryLoader::load_dictionary_from_config(dictionary_conf)?;
+            builder.build_user_dictionary_with_estimation(&args.src_path, &output_file, &dictionary)
+        } else {
+            builder.build_user_dictionary(&args.src_path, &output_file)
+        }
+    } else {
+        builder.build_dictionary(&args.src_path, &dest_path)
+    }
+}
+
+fn train(args: TrainArgs) -> LinderaResult<()> {
+    let trainer = TrainerOptions::default()
+        .encoding(args.encoding)
+        .max_iterations(args.max_iterations)
+        .regularization_coefficient(args.regularization_coefficient)
+        .learning_rate(args.learning_rate)
+        .cost_factor(args.cost_factor)
+        .pos_fields(args.pos_fields)
+        .builder()
+        .map_err(|err| LinderaErrorKind::Args.with_error(anyhow::anyhow!(err)))?;
+
+    trainer.train(&args.seed_path, &args.corpus_file, &args.dest_path)
+}
+
+fn load_dictionary_for_diff(path: &Path, args: &DictDiffArgs) -> LinderaResult<Dictionary> {
+    // A built dictionary is loaded as is, a dictionary source is built first.
+    if path.join("dict.da").exists() {
+        return DictionaryLoader::load_dictionary(path.to_path_buf());
+    }
+
+    let builder = match (&args.schema, &args.dic_type) {
+        (Some(schema), _) => DictionaryBuilderResolver::resolve_builder_from_schema(schema)?,
+        (None, Some(dic_type)) => DictionaryBuilderResolver::resolve_builder(dic_type.clone())?,
+        (None, None) => {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "Dictionary type or schema must be specified to compare the dictionary source {}",
+                path.display()
+            )))
+        }
+    };
+    DictionaryLoader::load_dictionary_from_source(builder.as_ref(), path)
+}
+
+fn dict_diff(args: DictDiffArgs) -> LinderaResult<()> {
+    let old = load_dictionary_for_diff(&args.old_path, &args)?;
+    let new = load_dictionary_for_diff(&args.new_path, &args)?;
+
+    let diff = DictionaryDiff::new(&old, &new);
+    if args.summary {
+        print!("{}", diff.summary());
     } else {
-        builder.build_dictionary(&args.src_path, &args.dest_path)
+        print!("{}", diff);
+    }
+
+    Ok(())
+}
+
+fn dict_export(args: DictExportArgs) -> LinderaResult<()> {
+    let builder = match (&args.schema, &args.dic_type) {
+        (Some(schema), _) => DictionaryBuilderResolver::resolve_builder_from_schema(schema)?,
+        (None, Some(dic_type)) => DictionaryBuilderResolver::resolve_builder(dic_type.clone())?,
+        (None, None) => {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "Dictionary type or schema must be specified to export a dictionary"
+            )))
+        }
+    };
+
+    let dictionary = match (&args.dic_dir, &args.dic_type) {
+        (Some(dic_dir), _) => DictionaryLoader::load_dictionary(dic_dir.clone())?,
+        (None, Some(dic_type)) => DictionaryLoader::load_dictionary_from_kind(dic_type.clone())?,
+        (None, None) => {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "Dictionary directory must be specified to export a dictionary with a schema"
+            )))
+        }
+    };
+
+    builder.export_dictionary(&dictionary, &args.output_dir)
+}
+
+fn load_tokenizer(
+    dic_type: Option<DictionaryKind>,
+    dic_dir: Option<PathBuf>,
+    user_dic_file: Option<PathBuf>,
+    user_dic_estimate_cost: bool,
+    mode: Mode,
+) -> LinderaResult<Tokenizer> {
+    let config = TokenizerConfig {
+        dictionary: DictionaryConfig {
+            kind: dic_type.clone(),
+            path: dic_dir,
+        },
+        user_dictionary: user_dic_file.map(|path| UserDictionaryConfig {
+            kind: dic_type,
+            path,
+            format: None,
+            schema: None,
+            estimate_cost: user_dic_estimate_cost,
+        }),
+        mode,
+        ..TokenizerConfig::default()
+    };
+    Tokenizer::from_config(config)
+}
+
+fn compare(args: CompareArgs) -> LinderaResult<()> {
+    let old = load_tokenizer(
+        args.old_dic_type,
+        args.old_dic_dir,
+        args.old_user_dic_file,
+        args.user_dic_estimate_cost,
+        args.mode.clone(),
+    )?;
+    let new = load_tokenizer(
+        args.new_dic_type,
+        args.new_dic_dir,
+        args.new_user_dic_file,
+        args.user_dic_estimate_cost,
+        args.mode,
+    )?;
+
+    let reader: Box<dyn BufRead> = match args.input_file {
+        Some(input_file) => {
+            Box::new(BufReader::new(File::open(input_file).map_err(|err| {
+                LinderaErrorKind::Io.with_error(anyhow::anyhow!(err))
+            })?))
+        }
+        None => Box::new(BufReader::new(io::stdin())),
+    };
+
+    let report = TokenizerComparison::new(&old, &new)
+        .max_examples(args.examples)
+        .pos_fields_num(args.pos_fields)
+        .compare(reader)?;
+
+    match args.output_format.as_str() {
+        "text" => print!("{}", report),
+        "json" => println!(
+            "{}",
+            serde_json::to_string_pretty(&report)
+                .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?
+        ),
+        format => {
+            return Err(
+                LinderaErrorKind::Args.with_error(anyhow::anyhow!("Invalid format: {}", format))
+            )
+        }
     }
+
+    Ok(())
 }
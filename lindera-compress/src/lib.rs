@@ -1,8 +1,8 @@
-use std::io::Write;
+use std::io::{self, Read, Write};
 
 use flate2::{
-    write::{DeflateEncoder, GzEncoder, ZlibEncoder},
-    Compression,
+    write::{DeflateEncoder, ZlibEncoder},
+    Compression, GzBuilder,
 };
 pub use lindera_decompress::{Algorithm, CompressedData};
 
@@ -26,7 +26,10 @@ pub fn compress(data: &[u8], algorithm: Algorithm) -> anyhow::Result<CompressedD
             Ok(CompressedData::new(algorithm, e.finish()?))
         }
         Algorithm::Gzip => {
-            let mut e = GzEncoder::new(Vec::new(), Compression::default());
+            // No timestamp is embedded, so that the output is reproducible
+            let mut e = GzBuilder::new()
+                .mtime(0)
+                .write(Vec::new(), Compression::default());
             e.write_all(data)?;
             Ok(CompressedData::new(algorithm, e.finish()?))
         }
@@ -34,6 +37,39 @@ pub fn compress(data: &[u8], algorithm: Algorithm) -> anyhow::Result<CompressedD
     }
 }
 
+/// Compress the data read from `reader` into `writer`, without holding the data in memory.
+/// The compressed bytes are the same as the data of [`compress`].
+pub fn compress_stream<R: Read, W: Write>(
+    reader: &mut R,
+    algorithm: Algorithm,
+    writer: W,
+) -> anyhow::Result<W> {
+    match algorithm {
+        Algorithm::Deflate => {
+            let mut e = DeflateEncoder::new(writer, Compression::default());
+            io::copy(reader, &mut e)?;
+            Ok(e.finish()?)
+        }
+        Algorithm::Zlib => {
+            let mut e = ZlibEncoder::new(writer, Compression::default());
+            io::copy(reader, &mut e)?;
+            Ok(e.finish()?)
+        }
+        Algorithm::Gzip => {
+            let mut e = GzBuilder::new()
+                .mtime(0)
+                .write(writer, Compression::default());
+            io::copy(reader, &mut e)?;
+            Ok(e.finish()?)
+        }
+        Algorithm::Raw => {
+            let mut writer = writer;
+            io::copy(reader, &mut writer)?;
+            Ok(writer)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,4 +94,23 @@ mod tests {
 
         assert_eq!(&buf, &data);
     }
+
+    #[test]
+    fn compress_stream_matches_compress() {
+        let mut rng = rand::thread_rng();
+        let buf = (0..100000)
+            .map(|i| if i % 3 == 0 { rng.gen() } else { 0 })
+            .collect::<Vec<u8>>();
+
+        for algorithm in [
+            Algorithm::Deflate,
+            Algorithm::Zlib,
+            Algorithm::Gzip,
+            Algorithm::Raw,
+        ] {
+            let streamed = compress_stream(&mut buf.as_slice(), algorithm, Vec::new()).unwrap();
+            let compressed = compress(&buf, algorithm).unwrap();
+            assert_eq!(streamed, compressed.data());
+        }
+    }
 }
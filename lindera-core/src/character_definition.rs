@@ -4,7 +4,10 @@ use byteorder::{ByteOrder, LittleEndian};
 use encoding_rs::UTF_16LE;
 use serde::{Deserialize, Serialize};
 
-use crate::{error::LinderaErrorKind, LinderaResult};
+use crate::{
+    error::{ErrorLocation, LinderaErrorKind},
+    LinderaResult,
+};
 
 const DEFAULT_CATEGORY_NAME: &str = "DEFAULT";
 
@@ -29,7 +32,7 @@ fn parse_hex_codepoint(s: &str) -> LinderaResult<u32> {
     ucs2_to_unicode(ucs2_codepoint)
 }
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
 pub struct CategoryData {
     pub invoke: bool,
     pub group: bool,
@@ -144,22 +147,26 @@ impl CharacterDefinitionsBuilder {
     }
 
     pub fn parse(&mut self, content: &str) -> LinderaResult<()> {
-        for line in content.lines() {
+        for (index, line) in content.lines().enumerate() {
+            let location = ErrorLocation::new().with_line(index + 1);
             let line_str = line
                 .split('#')
                 .next()
                 .ok_or_else(|| {
-                    LinderaErrorKind::Parse.with_error(anyhow::anyhow!("failed to parse line"))
+                    LinderaErrorKind::Parse
+                        .with_error(anyhow::anyhow!("failed to parse line"))
+                        .add_location(location.clone())
                 })?
                 .trim();
             if line_str.is_empty() {
                 continue;
             }
             if line_str.starts_with("0x") {
-                self.parse_range(line_str)?;
+                self.parse_range(line_str)
             } else {
-                self.parse_category(line_str)?;
+                self.parse_category(line_str)
             }
+            .map_err(|err| err.add_location(location))?;
         }
         Ok(())
     }
@@ -167,17 +174,21 @@ impl CharacterDefinitionsBuilder {
     fn parse_range(&mut self, line: &str) -> LinderaResult<()> {
         let fields: Vec<&str> = line.split_whitespace().collect();
         let range_bounds: Vec<&str> = fields[0].split("..").collect();
+        let field_location = || ErrorLocation::new().with_field(0, fields[0]);
         let lower_bound: u32;
         let higher_bound: u32;
         match range_bounds.len() {
             1 => {
-                lower_bound = parse_hex_codepoint(range_bounds[0])?;
+                lower_bound = parse_hex_codepoint(range_bounds[0])
+                    .map_err(|err| err.add_location(field_location()))?;
                 higher_bound = lower_bound;
             }
             2 => {
-                lower_bound = parse_hex_codepoint(range_bounds[0])?;
+                lower_bound = parse_hex_codepoint(range_bounds[0])
+                    .map_err(|err| err.add_location(field_location()))?;
                 // the right bound is included in the file.
-                higher_bound = parse_hex_codepoint(range_bounds[1])?;
+                higher_bound = parse_hex_codepoint(range_bounds[1])
+                    .map_err(|err| err.add_location(field_location()))?;
             }
             _ => {
                 return Err(
@@ -205,17 +216,21 @@ impl CharacterDefinitionsBuilder {
                 line
             )));
         }
-        let invoke = fields[1]
-            .parse::<u32>()
-            .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?
-            == 1;
-        let group = fields[2]
-            .parse::<u32>()
-            .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?
-            == 1;
-        let length = fields[3]
-            .parse::<u32>()
-            .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?;
+        let invoke = fields[1].parse::<u32>().map_err(|err| {
+            LinderaErrorKind::Parse
+                .with_error(anyhow::anyhow!(err))
+                .add_location(ErrorLocation::new().with_field(1, fields[1]))
+        })? == 1;
+        let group = fields[2].parse::<u32>().map_err(|err| {
+            LinderaErrorKind::Parse
+                .with_error(anyhow::anyhow!(err))
+                .add_location(ErrorLocation::new().with_field(2, fields[2]))
+        })? == 1;
+        let length = fields[3].parse::<u32>().map_err(|err| {
+            LinderaErrorKind::Parse
+                .with_error(anyhow::anyhow!(err))
+                .add_location(ErrorLocation::new().with_field(3, fields[3]))
+        })?;
         let category_data = CategoryData {
             invoke,
             group,
@@ -6,8 +6,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     character_definition::CharacterDefinitions, connection::ConnectionCostMatrix,
-    error::LinderaErrorKind, prefix_dict::PrefixDict, unknown_dictionary::UnknownDictionary,
-    LinderaResult,
+    error::LinderaErrorKind, id_definition::IdDefinitions, prefix_dict::PrefixDict,
+    unknown_dictionary::UnknownDictionary, LinderaResult,
 };
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -18,9 +18,43 @@ pub struct Dictionary {
     pub unknown_dictionary: UnknownDictionary,
     pub words_idx_data: Cow<'static, [u8]>,
     pub words_data: Cow<'static, [u8]>,
+    pub id_definitions: IdDefinitions,
 }
 
 impl Dictionary {
+    /// Create a dictionary without id definitions.
+    /// Use this rather than a struct literal, so that fields added later get their defaults.
+    pub fn new(
+        dict: PrefixDict<Vec<u8>>,
+        cost_matrix: ConnectionCostMatrix,
+        char_definitions: CharacterDefinitions,
+        unknown_dictionary: UnknownDictionary,
+        words_idx_data: Cow<'static, [u8]>,
+        words_data: Cow<'static, [u8]>,
+    ) -> Self {
+        Dictionary {
+            dict,
+            cost_matrix,
+            char_definitions,
+            unknown_dictionary,
+            words_idx_data,
+            words_data,
+            id_definitions: IdDefinitions::default(),
+        }
+    }
+
+    /// Set the id definitions, which map the features of words to their context ids.
+    pub fn with_id_definitions(mut self, id_definitions: IdDefinitions) -> Self {
+        self.id_definitions = id_definitions;
+        self
+    }
+
+    /// Returns whether the details of the words are loaded. They are not if the dictionary was
+    /// loaded for segmentation only.
+    pub fn has_details(&self) -> bool {
+        !self.words_idx_data.is_empty()
+    }
+
     pub fn word_details(&self, word_id: usize) -> Option<Vec<String>> {
         if 4 * word_id >= self.words_idx_data.len() {
             return None;
@@ -54,15 +88,30 @@ impl UserDictionary {
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))
     }
 
+    /// Load only the prefix dictionary of a built user dictionary, leaving the details of the
+    /// words undecoded.
+    pub fn load_without_details(user_dict_data: &[u8]) -> LinderaResult<UserDictionary> {
+        /// The leading field of a serialized [`UserDictionary`].
+        #[derive(Deserialize)]
+        struct PrefixOnly {
+            dict: PrefixDict<Vec<u8>>,
+        }
+
+        let PrefixOnly { dict } = bincode::deserialize(user_dict_data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))?;
+        Ok(UserDictionary {
+            dict,
+            words_idx_data: Vec::new(),
+            words_data: Vec::new(),
+        })
+    }
+
     pub fn word_details(&self, word_id: usize) -> Option<Vec<String>> {
         if 4 * word_id >= self.words_idx_data.len() {
             return None;
         }
         let idx = LittleEndian::read_u32(&self.words_idx_data[4 * word_id..][..4]);
         let data = &self.words_data[idx as usize..];
-        match bincode::deserialize_from(data) {
-            Ok(details) => Some(details),
-            Err(_err) => None,
-        }
+        bincode::deserialize_from(data).ok()
     }
 }
@@ -1,12 +1,24 @@
+use std::io::Read;
 use std::path::Path;
 
 use crate::{
-    character_definition::CharacterDefinitions, dictionary::UserDictionary, LinderaResult,
+    character_definition::CharacterDefinitions,
+    dictionary::{Dictionary, UserDictionary},
+    id_definition::IdDefinitions,
+    user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat},
+    validation::ValidationReport,
+    LinderaResult,
 };
 
 pub trait DictionaryBuilder {
     fn build_dictionary(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()>;
     fn build_user_dictionary(&self, input_path: &Path, output_path: &Path) -> LinderaResult<()>;
+    fn build_user_dictionary_with_estimation(
+        &self,
+        input_path: &Path,
+        output_path: &Path,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<()>;
     fn build_chardef(
         &self,
         input_dir: &Path,
@@ -20,5 +32,47 @@ pub trait DictionaryBuilder {
     ) -> LinderaResult<()>;
     fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()>;
     fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()>;
+    fn build_id_def(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<IdDefinitions>;
+    /// Build a user dictionary, of which the format is detected from the extension of the file.
     fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary>;
+    /// Build a user dictionary from a source in the given format.
+    fn build_user_dict_with_format(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary>;
+    /// Build a user dictionary from entries with named fields, without reading a file.
+    fn build_user_dict_from_entries(
+        &self,
+        entries: Vec<UserDictionaryEntry>,
+    ) -> LinderaResult<UserDictionary>;
+    /// Build a user dictionary from a source in the given format read from `reader`.
+    fn build_user_dict_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary>;
+    /// Build a user dictionary from a source in the given format, estimating the context ids and
+    /// the word costs of simple entries from the entries of the system dictionary with the same
+    /// part-of-speech.
+    /// If the system dictionary has id definitions, context ids are assigned from the features
+    /// instead, and detailed entries may omit their context ids and word cost.
+    fn build_user_dict_with_estimation(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<UserDictionary>;
+    /// Build a user dictionary from a source in the given format without the details of the
+    /// words, for tokenizers that only segment the text.
+    fn build_user_dict_without_details(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary>;
+    /// Export a built dictionary back to a source that this builder can build it from.
+    fn export_dictionary(&self, dictionary: &Dictionary, output_dir: &Path) -> LinderaResult<()>;
+    /// Validate a dictionary source without building it, reporting all the problems found
+    /// with their locations.
+    fn validate_dictionary(&self, input_dir: &Path) -> LinderaResult<ValidationReport>;
 }
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
@@ -27,19 +28,112 @@ impl LinderaErrorKind {
     {
         LinderaError {
             kind: self,
+            location: None,
             source: From::from(source),
         }
     }
 }
 
+/// The location in a source file (a dictionary CSV, a definition file or a config file)
+/// where an error occurred.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ErrorLocation {
+    /// The source file
+    pub path: Option<PathBuf>,
+    /// The line number, starting from 1
+    pub line: Option<usize>,
+    /// The column number, starting from 1
+    pub column: Option<usize>,
+    /// The index of the field in the row, starting from 0
+    pub field: Option<usize>,
+    /// The offending value
+    pub value: Option<String>,
+}
+
+impl ErrorLocation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_path_buf());
+        self
+    }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    pub fn with_field(mut self, field: usize, value: &str) -> Self {
+        self.field = Some(field);
+        self.value = Some(value.to_string());
+        self
+    }
+
+    /// Fill the unknown parts of the location with `other`.
+    fn merge(mut self, other: ErrorLocation) -> Self {
+        self.path = self.path.or(other.path);
+        self.line = self.line.or(other.line);
+        self.column = self.column.or(other.column);
+        self.field = self.field.or(other.field);
+        self.value = self.value.or(other.value);
+        self
+    }
+}
+
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(path) = &self.path {
+            parts.push(path.display().to_string());
+        }
+        if let Some(line) = self.line {
+            parts.push(format!("line {}", line));
+        }
+        if let Some(column) = self.column {
+            parts.push(format!("column {}", column));
+        }
+        if let Some(field) = self.field {
+            parts.push(format!("field {}", field));
+        }
+        if let Some(value) = &self.value {
+            parts.push(format!("value {:?}", value));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
-#[error("LinderaError(kind={kind:?}, source={source})")]
 pub struct LinderaError {
     pub kind: LinderaErrorKind,
+    location: Option<ErrorLocation>,
     #[source]
     source: anyhow::Error,
 }
 
+impl fmt::Display for LinderaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(
+                f,
+                "LinderaError(kind={:?}, location={}, source={})",
+                self.kind, location, self.source
+            ),
+            None => write!(
+                f,
+                "LinderaError(kind={:?}, source={})",
+                self.kind, self.source
+            ),
+        }
+    }
+}
+
 impl LinderaError {
     pub fn add_context<C>(self, ctx: C) -> Self
     where
@@ -47,11 +141,64 @@ impl LinderaError {
     {
         LinderaError {
             kind: self.kind,
+            location: self.location,
             source: self.source.context(ctx),
         }
     }
 
+    /// Add the location where the error occurred.
+    /// The parts of the location that are already known are kept, so that the innermost caller
+    /// can add the line and the field, and an outer caller the path.
+    pub fn add_location(self, location: ErrorLocation) -> Self {
+        LinderaError {
+            kind: self.kind,
+            location: Some(match self.location {
+                Some(known) => known.merge(location),
+                None => location,
+            }),
+            source: self.source,
+        }
+    }
+
     pub fn kind(&self) -> LinderaErrorKind {
         self.kind
     }
+
+    /// Returns the location where the error occurred, if known.
+    pub fn location(&self) -> Option<&ErrorLocation> {
+        self.location.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::error::{ErrorLocation, LinderaErrorKind};
+
+    #[test]
+    fn test_add_location() {
+        let err = LinderaErrorKind::Parse
+            .with_error(anyhow::anyhow!("failed to parse word cost"))
+            .add_location(ErrorLocation::new().with_line(3).with_field(3, "hoge"))
+            .add_location(
+                ErrorLocation::new()
+                    .with_path(Path::new("userdic.csv"))
+                    .with_line(1),
+            );
+
+        assert_eq!(
+            err.location(),
+            Some(
+                &ErrorLocation::new()
+                    .with_path(Path::new("userdic.csv"))
+                    .with_line(3)
+                    .with_field(3, "hoge")
+            )
+        );
+        assert_eq!(
+            err.to_string(),
+            "LinderaError(kind=Parse, location=userdic.csv, line 3, field 3, value \"hoge\", source=failed to parse word cost)"
+        );
+    }
 }
@@ -0,0 +1,351 @@
+use serde::Serialize;
+
+/// The details of the tokens that are not in the dictionaries, when the tokenizer does not look
+/// up the features of the unknown words.
+const UNKNOWN_DETAILS: &str = "UNK";
+
+/// A typed view of the details of a token, for the layout of the details of a dictionary.
+///
+/// The fields are `None` if the details do not have them, or if their value is `*` or empty.
+pub trait Features<'a>: Sized {
+    fn from_details<S: AsRef<str>>(details: &'a [S]) -> Self;
+}
+
+/// Returns the field of the details at `index`, or `None` if it is missing or unspecified.
+fn field<S: AsRef<str>>(details: &[S], index: usize) -> Option<&str> {
+    if details.len() == 1 && details[0].as_ref() == UNKNOWN_DETAILS {
+        return None;
+    }
+    details
+        .get(index)
+        .map(AsRef::as_ref)
+        .filter(|value| !value.is_empty() && *value != "*")
+}
+
+/// The features of IPADIC and IPADIC NEologd.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct IpadicFeatures<'a> {
+    /// 品詞
+    pub pos: Option<&'a str>,
+    /// 品詞細分類1
+    pub pos_subcategory_1: Option<&'a str>,
+    /// 品詞細分類2
+    pub pos_subcategory_2: Option<&'a str>,
+    /// 品詞細分類3
+    pub pos_subcategory_3: Option<&'a str>,
+    /// 活用型
+    pub conjugation_type: Option<&'a str>,
+    /// 活用形
+    pub conjugation_form: Option<&'a str>,
+    /// 原形
+    pub base_form: Option<&'a str>,
+    /// 読み
+    pub reading: Option<&'a str>,
+    /// 発音
+    pub pronunciation: Option<&'a str>,
+}
+
+impl<'a> Features<'a> for IpadicFeatures<'a> {
+    fn from_details<S: AsRef<str>>(details: &'a [S]) -> Self {
+        IpadicFeatures {
+            pos: field(details, 0),
+            pos_subcategory_1: field(details, 1),
+            pos_subcategory_2: field(details, 2),
+            pos_subcategory_3: field(details, 3),
+            conjugation_type: field(details, 4),
+            conjugation_form: field(details, 5),
+            base_form: field(details, 6),
+            reading: field(details, 7),
+            pronunciation: field(details, 8),
+        }
+    }
+}
+
+impl<'a> IpadicFeatures<'a> {
+    /// Returns the part-of-speech and its subcategories, from the most general one.
+    pub fn pos_hierarchy(&self) -> Vec<&'a str> {
+        [
+            self.pos,
+            self.pos_subcategory_1,
+            self.pos_subcategory_2,
+            self.pos_subcategory_3,
+        ]
+        .into_iter()
+        .map_while(|pos| pos)
+        .collect()
+    }
+}
+
+/// The features of UniDic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct UnidicFeatures<'a> {
+    /// 品詞大分類
+    pub pos: Option<&'a str>,
+    /// 品詞中分類
+    pub pos_subcategory_1: Option<&'a str>,
+    /// 品詞小分類
+    pub pos_subcategory_2: Option<&'a str>,
+    /// 品詞細分類
+    pub pos_subcategory_3: Option<&'a str>,
+    /// 活用型
+    pub conjugation_type: Option<&'a str>,
+    /// 活用形
+    pub conjugation_form: Option<&'a str>,
+    /// 語彙素読み
+    pub lexeme_reading: Option<&'a str>,
+    /// 語彙素 (語彙素表記 + 語彙素細分類)
+    pub lexeme: Option<&'a str>,
+    /// 書字形出現形
+    pub orthographic_surface_form: Option<&'a str>,
+    /// 発音形出現形
+    pub phonological_surface_form: Option<&'a str>,
+    /// 書字形基本形
+    pub orthographic_base_form: Option<&'a str>,
+    /// 発音形基本形
+    pub phonological_base_form: Option<&'a str>,
+    /// 語種
+    pub word_type: Option<&'a str>,
+    /// 語頭変化型
+    pub initial_transformation_type: Option<&'a str>,
+    /// 語頭変化形
+    pub initial_transformation_form: Option<&'a str>,
+    /// 語末変化型
+    pub final_transformation_type: Option<&'a str>,
+    /// 語末変化形
+    pub final_transformation_form: Option<&'a str>,
+}
+
+impl<'a> Features<'a> for UnidicFeatures<'a> {
+    fn from_details<S: AsRef<str>>(details: &'a [S]) -> Self {
+        UnidicFeatures {
+            pos: field(details, 0),
+            pos_subcategory_1: field(details, 1),
+            pos_subcategory_2: field(details, 2),
+            pos_subcategory_3: field(details, 3),
+            conjugation_type: field(details, 4),
+            conjugation_form: field(details, 5),
+            lexeme_reading: field(details, 6),
+            lexeme: field(details, 7),
+            orthographic_surface_form: field(details, 8),
+            phonological_surface_form: field(details, 9),
+            orthographic_base_form: field(details, 10),
+            phonological_base_form: field(details, 11),
+            word_type: field(details, 12),
+            initial_transformation_type: field(details, 13),
+            initial_transformation_form: field(details, 14),
+            final_transformation_type: field(details, 15),
+            final_transformation_form: field(details, 16),
+        }
+    }
+}
+
+impl<'a> UnidicFeatures<'a> {
+    /// Returns the part-of-speech and its subcategories, from the most general one.
+    pub fn pos_hierarchy(&self) -> Vec<&'a str> {
+        [
+            self.pos,
+            self.pos_subcategory_1,
+            self.pos_subcategory_2,
+            self.pos_subcategory_3,
+        ]
+        .into_iter()
+        .map_while(|pos| pos)
+        .collect()
+    }
+
+    /// Returns the base form, which is the orthographic base form as `japanese_base_form` uses.
+    pub fn base_form(&self) -> Option<&'a str> {
+        self.orthographic_base_form
+    }
+
+    /// Returns the reading, which is the lexeme reading as `japanese_reading_form` uses.
+    pub fn reading(&self) -> Option<&'a str> {
+        self.lexeme_reading
+    }
+}
+
+/// The features of ko-dic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct KoDicFeatures<'a> {
+    /// 품사 태그, such as `VV+EM+VX+EP` for the inflected words
+    pub pos_tag: Option<&'a str>,
+    /// 의미 부류
+    pub meaning: Option<&'a str>,
+    /// 종성 유무, which is `T` or `F`
+    pub final_consonant: Option<&'a str>,
+    /// 읽기
+    pub reading: Option<&'a str>,
+    /// 타입, which is `Inflect`, `Compound` or `Preanalysis`
+    pub word_type: Option<&'a str>,
+    /// 첫번째 품사
+    pub first_pos: Option<&'a str>,
+    /// 마지막 품사
+    pub last_pos: Option<&'a str>,
+    /// 표현
+    pub expression: Option<&'a str>,
+}
+
+impl<'a> Features<'a> for KoDicFeatures<'a> {
+    fn from_details<S: AsRef<str>>(details: &'a [S]) -> Self {
+        KoDicFeatures {
+            pos_tag: field(details, 0),
+            meaning: field(details, 1),
+            final_consonant: field(details, 2),
+            reading: field(details, 3),
+            word_type: field(details, 4),
+            first_pos: field(details, 5),
+            last_pos: field(details, 6),
+            expression: field(details, 7),
+        }
+    }
+}
+
+impl<'a> KoDicFeatures<'a> {
+    /// Returns the part-of-speech tags of the parts of the word.
+    pub fn pos_tags(&self) -> Vec<&'a str> {
+        self.pos_tag
+            .map(|pos_tag| pos_tag.split('+').collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether the word ends with a final consonant (종성), if it is known.
+    pub fn has_final_consonant(&self) -> Option<bool> {
+        match self.final_consonant {
+            Some("T") => Some(true),
+            Some("F") => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// The features of CC-CEDICT.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CcCedictFeatures<'a> {
+    /// 词类
+    pub pos: Option<&'a str>,
+    /// 词类1
+    pub pos_subcategory_1: Option<&'a str>,
+    /// 词类2
+    pub pos_subcategory_2: Option<&'a str>,
+    /// 词类3
+    pub pos_subcategory_3: Option<&'a str>,
+    /// 併音
+    pub pinyin: Option<&'a str>,
+    /// 繁体字
+    pub traditional: Option<&'a str>,
+    /// 簡体字
+    pub simplified: Option<&'a str>,
+    /// 定义
+    pub definition: Option<&'a str>,
+}
+
+impl<'a> Features<'a> for CcCedictFeatures<'a> {
+    fn from_details<S: AsRef<str>>(details: &'a [S]) -> Self {
+        CcCedictFeatures {
+            pos: field(details, 0),
+            pos_subcategory_1: field(details, 1),
+            pos_subcategory_2: field(details, 2),
+            pos_subcategory_3: field(details, 3),
+            pinyin: field(details, 4),
+            traditional: field(details, 5),
+            simplified: field(details, 6),
+            definition: field(details, 7),
+        }
+    }
+}
+
+impl<'a> CcCedictFeatures<'a> {
+    /// Returns the part-of-speech and its subcategories, from the most general one.
+    pub fn pos_hierarchy(&self) -> Vec<&'a str> {
+        [
+            self.pos,
+            self.pos_subcategory_1,
+            self.pos_subcategory_2,
+            self.pos_subcategory_3,
+        ]
+        .into_iter()
+        .map_while(|pos| pos)
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::{
+        CcCedictFeatures, Features, IpadicFeatures, KoDicFeatures, UnidicFeatures,
+    };
+
+    #[test]
+    fn test_ipadic_features() {
+        let details = vec![
+            "動詞",
+            "自立",
+            "*",
+            "*",
+            "五段・タ行",
+            "連用形",
+            "待つ",
+            "マチ",
+            "マチ",
+        ];
+        let features = IpadicFeatures::from_details(&details);
+        assert_eq!(features.pos, Some("動詞"));
+        assert_eq!(features.pos_hierarchy(), vec!["動詞", "自立"]);
+        assert_eq!(features.pos_subcategory_2, None);
+        assert_eq!(features.base_form, Some("待つ"));
+        assert_eq!(features.reading, Some("マチ"));
+
+        // Missing fields of a short user dictionary entry
+        let details = vec!["カスタム名詞".to_string()];
+        let features = IpadicFeatures::from_details(&details);
+        assert_eq!(features.pos, Some("カスタム名詞"));
+        assert_eq!(features.pronunciation, None);
+
+        // Unknown words
+        let features = IpadicFeatures::from_details(&["UNK"][..]);
+        assert_eq!(features, IpadicFeatures::default());
+    }
+
+    #[test]
+    fn test_unidic_features() {
+        let details = "名詞,固有名詞,地名,一般,*,*,トウキョウ,トウキョウ,東京,トーキョー,東京,トーキョー,固,*,*,*,*"
+            .split(',')
+            .collect::<Vec<_>>();
+        let features = UnidicFeatures::from_details(&details);
+        assert_eq!(
+            features.pos_hierarchy(),
+            vec!["名詞", "固有名詞", "地名", "一般"]
+        );
+        assert_eq!(features.conjugation_type, None);
+        assert_eq!(features.reading(), Some("トウキョウ"));
+        assert_eq!(features.base_form(), Some("東京"));
+        assert_eq!(features.word_type, Some("固"));
+        assert_eq!(features.final_transformation_form, None);
+    }
+
+    #[test]
+    fn test_ko_dic_features() {
+        let details = "VV+EP,*,F,했,Inflect,VV,EP,하/VV/*+았/EP/*"
+            .split(',')
+            .collect::<Vec<_>>();
+        let features = KoDicFeatures::from_details(&details);
+        assert_eq!(features.pos_tags(), vec!["VV", "EP"]);
+        assert_eq!(features.meaning, None);
+        assert_eq!(features.has_final_consonant(), Some(false));
+        assert_eq!(features.reading, Some("했"));
+        assert_eq!(features.last_pos, Some("EP"));
+    }
+
+    #[test]
+    fn test_cc_cedict_features() {
+        let details = "*,*,*,*,Zhong1 guo2,中國,中国,China/Middle Kingdom/"
+            .split(',')
+            .collect::<Vec<_>>();
+        let features = CcCedictFeatures::from_details(&details);
+        assert!(features.pos_hierarchy().is_empty());
+        assert_eq!(features.pinyin, Some("Zhong1 guo2"));
+        assert_eq!(features.traditional, Some("中國"));
+        assert_eq!(features.simplified, Some("中国"));
+        assert_eq!(features.definition, Some("China/Middle Kingdom/"));
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{ErrorLocation, LinderaErrorKind},
+    LinderaResult,
+};
+
+fn split_features(features: &str) -> Vec<String> {
+    features.split(',').map(|s| s.to_string()).collect()
+}
+
+fn join_features<S: AsRef<str>>(features: &[S]) -> String {
+    features
+        .iter()
+        .map(|field| field.as_ref())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+/// Returns whether a field of a feature matches a field of a pattern.
+/// `*` matches any value, and `(a|b|c)` matches one of the listed values.
+fn match_field(pattern: &str, field: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    if pattern.len() > 2 && pattern.starts_with('(') && pattern.ends_with(')') {
+        return pattern[1..pattern.len() - 1]
+            .split('|')
+            .any(|candidate| candidate == field);
+    }
+    pattern == field
+}
+
+/// A pattern over the fields of a feature, as used in `rewrite.def` and `pos-id.def`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeaturePattern(Vec<String>);
+
+impl FeaturePattern {
+    pub fn new(pattern: &str) -> Self {
+        FeaturePattern(split_features(pattern))
+    }
+
+    /// The pattern matches if every field of the pattern matches the corresponding field of the
+    /// features. The features may have more fields than the pattern.
+    pub fn matches<S: AsRef<str>>(&self, features: &[S]) -> bool {
+        self.0.len() <= features.len()
+            && self
+                .0
+                .iter()
+                .zip(features.iter())
+                .all(|(pattern, field)| match_field(pattern, field.as_ref()))
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RewriteRule {
+    pub pattern: FeaturePattern,
+    pub rewrite: Vec<String>,
+}
+
+impl RewriteRule {
+    /// Rewrite the features if they match the pattern.
+    /// `$1`, `$2`, ... in the rewrite fields are replaced by the corresponding fields of the features.
+    pub fn rewrite<S: AsRef<str>>(&self, features: &[S]) -> Option<String> {
+        if !self.pattern.matches(features) {
+            return None;
+        }
+
+        let fields = self
+            .rewrite
+            .iter()
+            .map(|field| match field.strip_prefix('$') {
+                Some(index) => match usize::from_str(index) {
+                    Ok(index) if index > 0 => features
+                        .get(index - 1)
+                        .map(|f| f.as_ref().to_string())
+                        .unwrap_or_default(),
+                    _ => field.to_string(),
+                },
+                None => field.to_string(),
+            })
+            .collect::<Vec<String>>();
+        Some(fields.join(","))
+    }
+}
+
+/// The rules of `rewrite.def`. The first matching rule of each section is applied.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RewriteRules {
+    pub unigram: Vec<RewriteRule>,
+    pub left: Vec<RewriteRule>,
+    pub right: Vec<RewriteRule>,
+}
+
+fn rewrite_with<S: AsRef<str>>(rules: &[RewriteRule], features: &[S]) -> Option<String> {
+    rules.iter().find_map(|rule| rule.rewrite(features))
+}
+
+impl RewriteRules {
+    pub fn rewrite_unigram<S: AsRef<str>>(&self, features: &[S]) -> Option<String> {
+        rewrite_with(&self.unigram, features)
+    }
+
+    pub fn rewrite_left<S: AsRef<str>>(&self, features: &[S]) -> Option<String> {
+        rewrite_with(&self.left, features)
+    }
+
+    pub fn rewrite_right<S: AsRef<str>>(&self, features: &[S]) -> Option<String> {
+        rewrite_with(&self.right, features)
+    }
+}
+
+/// The id definitions of a MeCab dictionary.
+///
+/// It holds `left-id.def`, `right-id.def`, `rewrite.def` and `pos-id.def`, which map the
+/// features of a word to its context ids and part-of-speech id.
+/// Every definition is empty if the dictionary source does not provide the file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IdDefinitions {
+    pub left_ids: BTreeMap<String, u16>,
+    pub right_ids: BTreeMap<String, u16>,
+    pub rewrite_rules: RewriteRules,
+    pub pos_ids: Vec<(FeaturePattern, u16)>,
+}
+
+impl IdDefinitions {
+    pub fn load(id_def_data: &[u8]) -> LinderaResult<IdDefinitions> {
+        bincode::deserialize(id_def_data)
+            .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))
+    }
+
+    /// Returns the left context id of the features.
+    /// As in MeCab, the features are looked up as they are if no left rewrite rule matches.
+    pub fn left_id<S: AsRef<str>>(&self, features: &[S]) -> Option<u16> {
+        let left_features = self
+            .rewrite_rules
+            .rewrite_left(features)
+            .unwrap_or_else(|| join_features(features));
+        self.left_ids.get(&left_features).copied()
+    }
+
+    /// Returns the right context id of the features.
+    /// As in MeCab, the features are looked up as they are if no right rewrite rule matches.
+    pub fn right_id<S: AsRef<str>>(&self, features: &[S]) -> Option<u16> {
+        let right_features = self
+            .rewrite_rules
+            .rewrite_right(features)
+            .unwrap_or_else(|| join_features(features));
+        self.right_ids.get(&right_features).copied()
+    }
+
+    /// Returns the left and right context ids of the features.
+    pub fn context_ids<S: AsRef<str>>(&self, features: &[S]) -> Option<(u16, u16)> {
+        Some((self.left_id(features)?, self.right_id(features)?))
+    }
+
+    /// Returns the part-of-speech id of the features, like `posid` of MeCab.
+    pub fn pos_id<S: AsRef<str>>(&self, features: &[S]) -> Option<u16> {
+        self.pos_ids
+            .iter()
+            .find(|(pattern, _)| pattern.matches(features))
+            .map(|(_, pos_id)| *pos_id)
+    }
+}
+
+/// Parse `left-id.def` or `right-id.def`.
+///
+/// Each line consists of a context id and the features, separated by a space.
+pub fn parse_id_def(content: &str) -> LinderaResult<BTreeMap<String, u16>> {
+    let mut ids = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let location = ErrorLocation::new().with_line(index + 1);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (id, features) = line.split_once(char::is_whitespace).ok_or_else(|| {
+            LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("Invalid line: {}", line))
+                .add_location(location.clone())
+        })?;
+        let id = u16::from_str(id).map_err(|err| {
+            LinderaErrorKind::Parse
+                .with_error(anyhow::anyhow!(err))
+                .add_location(location.with_field(0, id))
+        })?;
+        ids.insert(features.trim().to_string(), id);
+    }
+    Ok(ids)
+}
+
+/// Parse `rewrite.def`.
+///
+/// The rules are grouped in `[unigram rewrite]`, `[left rewrite]` and `[right rewrite]` sections.
+/// Each rule consists of a pattern and the rewritten features, separated by whitespace.
+pub fn parse_rewrite_def(content: &str) -> LinderaResult<RewriteRules> {
+    let mut rules = RewriteRules::default();
+    let mut section: Option<&mut Vec<RewriteRule>> = None;
+    for (index, line) in content.lines().enumerate() {
+        let location = ErrorLocation::new().with_line(index + 1);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line {
+            "[unigram rewrite]" => {
+                section = Some(&mut rules.unigram);
+                continue;
+            }
+            "[left rewrite]" => {
+                section = Some(&mut rules.left);
+                continue;
+            }
+            "[right rewrite]" => {
+                section = Some(&mut rules.right);
+                continue;
+            }
+            _ => {}
+        }
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        if fields.len() != 2 {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("Invalid line: {}", line))
+                .add_location(location));
+        }
+        let rule = RewriteRule {
+            pattern: FeaturePattern::new(fields[0]),
+            rewrite: split_features(fields[1]),
+        };
+        match section.as_mut() {
+            Some(section) => section.push(rule),
+            None => {
+                return Err(LinderaErrorKind::Content
+                    .with_error(anyhow::anyhow!("Rule outside of a section: {}", line))
+                    .add_location(location))
+            }
+        }
+    }
+    Ok(rules)
+}
+
+/// Parse `pos-id.def`.
+///
+/// Each line consists of a pattern and the part-of-speech id, separated by a space.
+pub fn parse_pos_id_def(content: &str) -> LinderaResult<Vec<(FeaturePattern, u16)>> {
+    let mut pos_ids = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let location = ErrorLocation::new().with_line(index + 1);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (pattern, id) = line.rsplit_once(char::is_whitespace).ok_or_else(|| {
+            LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("Invalid line: {}", line))
+                .add_location(location.clone())
+        })?;
+        let id = u16::from_str(id).map_err(|err| {
+            LinderaErrorKind::Parse
+                .with_error(anyhow::anyhow!(err))
+                .add_location(location.with_field(1, id))
+        })?;
+        pos_ids.push((FeaturePattern::new(pattern.trim()), id));
+    }
+    Ok(pos_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::id_definition::{parse_id_def, parse_pos_id_def, parse_rewrite_def, IdDefinitions};
+
+    const REWRITE_DEF: &str = r#"
+# comment
+[unigram rewrite]
+*,*,*,*,*,*,*	$1,$2,$3,$4,$5,$6,$7
+
+[left rewrite]
+(助詞|助動詞),*,*,*,*,*,(の|に)	$1,$2,$3,$4,$5,$6,$7
+*,*,*,*,*,*,*	$1,$2,$3,$4,$5,$6,*
+
+[right rewrite]
+*,*,*,*,*,*,*	$1,$2,$3,$4,$5,$6,*
+"#;
+
+    #[test]
+    fn test_context_ids() {
+        let id_definitions = IdDefinitions {
+            left_ids: parse_id_def(
+                "0 BOS/EOS,*,*,*,*,*,*\n1285 名詞,一般,*,*,*,*,*\n262 助詞,格助詞,一般,*,*,*,の\n",
+            )
+            .unwrap(),
+            right_ids: parse_id_def("0 BOS/EOS,*,*,*,*,*,*\n1285 名詞,一般,*,*,*,*,*\n").unwrap(),
+            rewrite_rules: parse_rewrite_def(REWRITE_DEF).unwrap(),
+            pos_ids: parse_pos_id_def("助詞,格助詞,*,* 13\n名詞,一般,*,* 38\n").unwrap(),
+        };
+
+        let noun = ["名詞", "一般", "*", "*", "*", "*", "机", "ツクエ", "ツクエ"];
+        assert_eq!(id_definitions.context_ids(&noun), Some((1285, 1285)));
+        assert_eq!(id_definitions.pos_id(&noun), Some(38));
+
+        let particle = ["助詞", "格助詞", "一般", "*", "*", "*", "の", "ノ", "ノ"];
+        assert_eq!(id_definitions.left_id(&particle), Some(262));
+        assert_eq!(id_definitions.right_id(&particle), None);
+        assert_eq!(id_definitions.pos_id(&particle), Some(13));
+
+        assert_eq!(id_definitions.context_ids(&["カスタム名詞"]), None);
+    }
+
+    #[test]
+    fn test_context_ids_without_matching_rule() {
+        let id_definitions = IdDefinitions {
+            left_ids: parse_id_def("0 BOS/EOS,*,*,*,*,*,*\n5 記号,一般\n").unwrap(),
+            right_ids: parse_id_def("0 BOS/EOS,*,*,*,*,*,*\n6 記号,一般\n").unwrap(),
+            rewrite_rules: parse_rewrite_def(REWRITE_DEF).unwrap(),
+            pos_ids: Vec::new(),
+        };
+
+        // The features are too short for the rules, so they are looked up as they are.
+        assert_eq!(id_definitions.context_ids(&["記号", "一般"]), Some((5, 6)));
+        assert_eq!(id_definitions.context_ids(&["記号", "括弧開"]), None);
+    }
+
+    #[test]
+    fn test_parse_rewrite_def_without_section() {
+        let err = parse_rewrite_def("# comment\n*,*,*	$1,$2,$3\n").unwrap_err();
+        assert_eq!(err.location().and_then(|location| location.line), Some(2));
+    }
+}
@@ -3,10 +3,15 @@ pub mod connection;
 pub mod dictionary;
 pub mod dictionary_builder;
 pub mod error;
+pub mod features;
 pub mod file_util;
+pub mod id_definition;
 pub mod mode;
+pub mod offsets;
 pub mod prefix_dict;
 pub mod unknown_dictionary;
+pub mod user_dictionary_source;
+pub mod validation;
 pub mod viterbi;
 pub mod word_entry;
 
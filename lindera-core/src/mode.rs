@@ -1,13 +1,16 @@
 use std::str::FromStr;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
     error::{LinderaError, LinderaErrorKind},
     viterbi::Edge,
 };
 
+/// The penalties of long words in the decompose mode.
+/// The fields that are missing in a config take their default values.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
 pub struct Penalty {
     pub kanji_penalty_length_threshold: usize,
     pub kanji_penalty_length_penalty: i32,
@@ -44,7 +47,7 @@ impl Penalty {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub enum Mode {
     #[serde(rename = "normal")]
     Normal,
@@ -67,6 +70,27 @@ impl Mode {
     }
 }
 
+/// The forms a mode can be written in a config: `"normal"`, `"decompose"` for the default
+/// penalties, or `{"decompose": {...}}` with some penalty fields.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ModeConfig {
+    Name(String),
+    Decompose { decompose: Penalty },
+}
+
+impl<'de> Deserialize<'de> for Mode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match ModeConfig::deserialize(deserializer)? {
+            ModeConfig::Name(name) => Mode::from_str(&name).map_err(serde::de::Error::custom),
+            ModeConfig::Decompose { decompose } => Ok(Mode::Decompose(decompose)),
+        }
+    }
+}
+
 impl FromStr for Mode {
     type Err = LinderaError;
     fn from_str(mode: &str) -> Result<Mode, Self::Err> {
@@ -80,3 +104,37 @@ impl FromStr for Mode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::mode::{Mode, Penalty};
+
+    #[test]
+    fn test_deserialize_mode() {
+        assert_eq!(
+            serde_json::from_str::<Mode>(r#""normal""#).unwrap(),
+            Mode::Normal
+        );
+        assert_eq!(
+            serde_json::from_str::<Mode>(r#""decompose""#).unwrap(),
+            Mode::Decompose(Penalty::default())
+        );
+        assert_eq!(
+            serde_json::from_str::<Mode>(r#"{"decompose": {"kanji_penalty_length_threshold": 3}}"#)
+                .unwrap(),
+            Mode::Decompose(Penalty {
+                kanji_penalty_length_threshold: 3,
+                ..Penalty::default()
+            })
+        );
+        assert!(serde_json::from_str::<Mode>(r#""search""#).is_err());
+
+        // The serialized modes are read back.
+        let mode = Mode::Decompose(Penalty {
+            other_penalty_length_penalty: 1000,
+            ..Penalty::default()
+        });
+        let json = serde_json::to_string(&mode).unwrap();
+        assert_eq!(serde_json::from_str::<Mode>(&json).unwrap(), mode);
+    }
+}
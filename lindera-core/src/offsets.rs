@@ -0,0 +1,179 @@
+use std::iter::Peekable;
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::{GraphemeIndices, UnicodeSegmentation};
+
+/// The offsets of a token in characters (Unicode scalar values), UTF-16 code units and
+/// extended grapheme clusters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenOffsets {
+    /// Starting position of the token in characters.
+    pub char_start: usize,
+
+    /// Ending position of the token in characters.
+    pub char_end: usize,
+
+    /// Starting position of the token in UTF-16 code units.
+    pub utf16_start: usize,
+
+    /// Ending position of the token in UTF-16 code units.
+    pub utf16_end: usize,
+
+    /// Starting position of the token in grapheme clusters.
+    /// A token that starts in the middle of a grapheme cluster starts after it.
+    pub grapheme_start: usize,
+
+    /// Ending position of the token in grapheme clusters.
+    /// A token that ends in the middle of a grapheme cluster ends after it.
+    pub grapheme_end: usize,
+}
+
+/// A position in a text, counted in characters, UTF-16 code units and grapheme clusters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextPosition {
+    pub chars: usize,
+    pub utf16: usize,
+    pub graphemes: usize,
+}
+
+/// Converts byte offsets of a text to `TextPosition`s.
+///
+/// The text is counted from the last converted offset, so converting increasing offsets walks
+/// the text once. A smaller offset than the last one counts the text again from the start.
+pub struct OffsetCounter<'a> {
+    text: &'a str,
+    /// The position of the start of `text`.
+    base: TextPosition,
+    grapheme_indices: Peekable<GraphemeIndices<'a>>,
+    byte_offset: usize,
+    position: TextPosition,
+}
+
+impl<'a> OffsetCounter<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self::with_base(text, TextPosition::default())
+    }
+
+    /// Create a counter for a text that starts at `base`, such as a sentence of a longer text.
+    pub fn with_base(text: &'a str, base: TextPosition) -> Self {
+        OffsetCounter {
+            text,
+            base,
+            grapheme_indices: text.grapheme_indices(true).peekable(),
+            byte_offset: 0,
+            position: base,
+        }
+    }
+
+    /// Returns the position of a byte offset of the text.
+    /// The offset is moved back to a character boundary and to the end of the text if needed.
+    pub fn position(&mut self, byte_offset: usize) -> TextPosition {
+        let mut byte_offset = byte_offset.min(self.text.len());
+        while !self.text.is_char_boundary(byte_offset) {
+            byte_offset -= 1;
+        }
+
+        if byte_offset < self.byte_offset {
+            self.grapheme_indices = self.text.grapheme_indices(true).peekable();
+            self.byte_offset = 0;
+            self.position = self.base;
+        }
+
+        for c in self.text[self.byte_offset..byte_offset].chars() {
+            self.position.chars += 1;
+            self.position.utf16 += c.len_utf16();
+        }
+        while let Some((index, _)) = self.grapheme_indices.peek() {
+            if *index >= byte_offset {
+                break;
+            }
+            self.position.graphemes += 1;
+            self.grapheme_indices.next();
+        }
+        self.byte_offset = byte_offset;
+
+        self.position
+    }
+
+    /// Returns the offsets of a token from its byte offsets in the text.
+    pub fn offsets(&mut self, byte_start: usize, byte_end: usize) -> TokenOffsets {
+        let start = self.position(byte_start);
+        let end = self.position(byte_end);
+        TokenOffsets {
+            char_start: start.chars,
+            char_end: end.chars,
+            utf16_start: start.utf16,
+            utf16_end: end.utf16,
+            grapheme_start: start.graphemes,
+            grapheme_end: end.graphemes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::offsets::{OffsetCounter, TextPosition, TokenOffsets};
+
+    #[test]
+    fn test_offset_counter() {
+        // "が" as a base character and a combining mark, and a character outside the BMP
+        let text = "か\u{3099}𠮷野家";
+        let mut counter = OffsetCounter::new(text);
+
+        assert_eq!(
+            counter.offsets(0, 6),
+            TokenOffsets {
+                char_start: 0,
+                char_end: 2,
+                utf16_start: 0,
+                utf16_end: 2,
+                grapheme_start: 0,
+                grapheme_end: 1,
+            }
+        );
+        assert_eq!(
+            counter.offsets(6, 10),
+            TokenOffsets {
+                char_start: 2,
+                char_end: 3,
+                utf16_start: 2,
+                utf16_end: 4,
+                grapheme_start: 1,
+                grapheme_end: 2,
+            }
+        );
+        assert_eq!(
+            counter.position(text.len()),
+            TextPosition {
+                chars: 5,
+                utf16: 6,
+                graphemes: 4,
+            }
+        );
+
+        // Smaller offsets are counted again.
+        assert_eq!(counter.offsets(3, 10).char_start, 1);
+        assert_eq!(counter.offsets(3, 10).grapheme_start, 1);
+
+        // Offsets relative to a base
+        let mut counter = OffsetCounter::with_base(
+            "野家",
+            TextPosition {
+                chars: 3,
+                utf16: 4,
+                graphemes: 2,
+            },
+        );
+        assert_eq!(
+            counter.offsets(3, 6),
+            TokenOffsets {
+                char_start: 4,
+                char_end: 5,
+                utf16_start: 5,
+                utf16_end: 6,
+                grapheme_start: 3,
+                grapheme_end: 4,
+            }
+        );
+    }
+}
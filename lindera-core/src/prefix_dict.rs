@@ -1,6 +1,8 @@
 use std::ops::Deref;
 
+use byteorder::{ByteOrder, LittleEndian};
 use serde::{Deserialize, Serialize};
+use yada::unit::{Unit, UNIT_SIZE};
 use yada::DoubleArray;
 
 use crate::word_entry::WordEntry;
@@ -56,24 +58,109 @@ impl<D: Deref<Target = [u8]>> PrefixDict<D> {
     /// Find `WordEntry`s with surface
     pub fn find_surface(&self, surface: &str) -> Vec<WordEntry> {
         match self.da.exact_match_search(surface) {
-            Some(offset_len) => {
-                let offset = offset_len >> 5u32;
-                let offset_bytes = (offset as usize) * WordEntry::SERIALIZED_LEN;
-                let data: &[u8] = &self.vals_data[offset_bytes..];
-                let len = offset_len & ((1u32 << 5) - 1u32);
-                (0..len as usize)
-                    .map(|i| {
-                        WordEntry::deserialize(
-                            &data[WordEntry::SERIALIZED_LEN * i..],
-                            self.is_system,
-                        )
-                    })
-                    .collect::<Vec<WordEntry>>()
-            }
+            Some(offset_len) => self.word_entries(offset_len),
             None => vec![],
         }
     }
+
+    /// Returns all the surfaces in the dictionary with their `WordEntry`s, in byte order of the
+    /// surfaces.
+    pub fn entries(&self) -> Vec<(String, Vec<WordEntry>)> {
+        let mut entries = Vec::new();
+
+        // Depth-first traversal of the double array, visiting the children in label order
+        let mut stack = vec![(0usize, Vec::new())];
+        while let Some((node_pos, key)) = stack.pop() {
+            let unit = match self.unit(node_pos) {
+                Some(unit) => unit,
+                None => continue,
+            };
+
+            if unit.has_leaf() {
+                if let Some(leaf) = self.unit((unit.offset() ^ node_pos as u32) as usize) {
+                    if leaf.is_leaf() {
+                        entries.push((
+                            String::from_utf8_lossy(&key).to_string(),
+                            self.word_entries(leaf.value()),
+                        ));
+                    }
+                }
+            }
+
+            for label in (1..=u8::MAX as u32).rev() {
+                let child_pos = (unit.offset() ^ node_pos as u32 ^ label) as usize;
+                if let Some(child) = self.unit(child_pos) {
+                    if child.label() == label {
+                        let mut child_key = key.clone();
+                        child_key.push(label as u8);
+                        stack.push((child_pos, child_key));
+                    }
+                }
+            }
+        }
+
+        entries
+    }
+
+    fn unit(&self, pos: usize) -> Option<Unit> {
+        let bytes = self.da.0.get(pos * UNIT_SIZE..(pos + 1) * UNIT_SIZE)?;
+        Some(Unit::from_u32(LittleEndian::read_u32(bytes)))
+    }
+
+    fn word_entries(&self, offset_len: u32) -> Vec<WordEntry> {
+        let offset = offset_len >> 5u32;
+        let offset_bytes = (offset as usize) * WordEntry::SERIALIZED_LEN;
+        let data: &[u8] = &self.vals_data[offset_bytes..];
+        let len = offset_len & ((1u32 << 5) - 1u32);
+        (0..len as usize)
+            .map(|i| WordEntry::deserialize(&data[WordEntry::SERIALIZED_LEN * i..], self.is_system))
+            .collect::<Vec<WordEntry>>()
+    }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use yada::builder::DoubleArrayBuilder;
+    use yada::DoubleArray;
+
+    use crate::prefix_dict::PrefixDict;
+    use crate::word_entry::{WordEntry, WordId};
+
+    #[test]
+    fn test_entries() {
+        let surfaces = ["京", "京都", "東", "東京", "東京都"];
+
+        let mut vals_data = Vec::new();
+        let mut keyset = Vec::new();
+        for (i, surface) in surfaces.iter().enumerate() {
+            WordEntry {
+                word_id: WordId(i as u32, true),
+                word_cost: i as i16 * 100,
+                left_id: i as u16,
+                right_id: i as u16,
+            }
+            .serialize(&mut vals_data)
+            .unwrap();
+            keyset.push((surface.as_bytes(), ((i as u32) << 5) | 1));
+        }
+
+        let prefix_dict = PrefixDict {
+            da: DoubleArray::new(DoubleArrayBuilder::build(&keyset).unwrap()),
+            vals_data,
+            is_system: true,
+        };
+
+        let entries = prefix_dict.entries();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|(surface, _)| surface.as_str())
+                .collect::<Vec<_>>(),
+            surfaces
+        );
+        for (i, (surface, word_entries)) in entries.iter().enumerate() {
+            assert_eq!(word_entries, &prefix_dict.find_surface(surface));
+            assert_eq!(word_entries[0].word_id.0, i as u32);
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use std::{str::FromStr, u32};
+use std::str::FromStr;
 
 use log::warn;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     character_definition::CategoryId,
-    error::LinderaErrorKind,
+    error::{ErrorLocation, LinderaErrorKind},
     word_entry::{WordEntry, WordId},
     LinderaResult,
 };
@@ -14,12 +14,30 @@ use crate::{
 pub struct UnknownDictionary {
     pub category_references: Vec<Vec<u32>>,
     pub costs: Vec<WordEntry>,
+    /// The features of the entries, i.e. the fields of `unk.def` after the cost.
+    pub details: Vec<Vec<String>>,
+}
+
+/// The unknown dictionary as it was serialized before the features of the entries were kept.
+#[derive(Deserialize)]
+struct LegacyUnknownDictionary {
+    category_references: Vec<Vec<u32>>,
+    costs: Vec<WordEntry>,
 }
 
 impl UnknownDictionary {
+    /// Dictionaries built without the features of the unknown word entries are loaded with no
+    /// features.
     pub fn load(unknown_data: &[u8]) -> LinderaResult<UnknownDictionary> {
-        bincode::deserialize(unknown_data)
-            .map_err(|err| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))
+        bincode::deserialize(unknown_data).or_else(|err| {
+            let legacy = bincode::deserialize::<LegacyUnknownDictionary>(unknown_data)
+                .map_err(|_| LinderaErrorKind::Deserialize.with_error(anyhow::anyhow!(err)))?;
+            Ok(UnknownDictionary {
+                category_references: legacy.category_references,
+                costs: legacy.costs,
+                details: Vec::new(),
+            })
+        })
     }
 
     pub fn word_entry(&self, word_id: u32) -> WordEntry {
@@ -29,6 +47,22 @@ impl UnknownDictionary {
     pub fn lookup_word_ids(&self, category_id: CategoryId) -> &[u32] {
         &self.category_references[category_id.0][..]
     }
+
+    /// Returns the features of an entry, or `None` if the dictionary does not have them.
+    pub fn word_details(&self, word_id: u32) -> Option<Vec<String>> {
+        self.details
+            .get(word_id as usize)
+            .filter(|details| !details.is_empty())
+            .cloned()
+    }
+
+    /// Returns the character category of an entry.
+    pub fn category_id(&self, word_id: u32) -> Option<CategoryId> {
+        self.category_references
+            .iter()
+            .position(|word_ids| word_ids.contains(&word_id))
+            .map(CategoryId)
+    }
 }
 
 #[derive(Debug)]
@@ -37,6 +71,7 @@ pub struct UnknownDictionaryEntry {
     pub left_id: u32,
     pub right_id: u32,
     pub word_cost: i32,
+    pub details: Vec<String>,
 }
 
 fn parse_dictionary_entry(
@@ -51,18 +86,28 @@ fn parse_dictionary_entry(
         )));
     }
     let surface = fields[0];
-    let left_id = u32::from_str(fields[1])
-        .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?;
-    let right_id = u32::from_str(fields[2])
-        .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?;
-    let word_cost = i32::from_str(fields[3])
-        .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?;
+    let left_id = u32::from_str(fields[1]).map_err(|err| {
+        LinderaErrorKind::Parse
+            .with_error(anyhow::anyhow!(err))
+            .add_location(ErrorLocation::new().with_field(1, fields[1]))
+    })?;
+    let right_id = u32::from_str(fields[2]).map_err(|err| {
+        LinderaErrorKind::Parse
+            .with_error(anyhow::anyhow!(err))
+            .add_location(ErrorLocation::new().with_field(2, fields[2]))
+    })?;
+    let word_cost = i32::from_str(fields[3]).map_err(|err| {
+        LinderaErrorKind::Parse
+            .with_error(anyhow::anyhow!(err))
+            .add_location(ErrorLocation::new().with_field(3, fields[3]))
+    })?;
 
     Ok(UnknownDictionaryEntry {
         surface: surface.to_string(),
         left_id,
         right_id,
         word_cost,
+        details: fields[4..].iter().map(|field| field.to_string()).collect(),
     })
 }
 
@@ -103,7 +148,7 @@ fn make_costs_array(entries: &[UnknownDictionaryEntry]) -> Vec<WordEntry> {
                 warn!("left id and right id are not same: {:?}", e);
             }
             WordEntry {
-                word_id: WordId(std::u32::MAX, true),
+                word_id: WordId(u32::MAX, true),
                 left_id: e.left_id as u16,
                 right_id: e.right_id as u16,
                 word_cost: e.word_cost as i16,
@@ -118,19 +163,71 @@ pub fn parse_unk(
     expected_fields_len: usize,
 ) -> LinderaResult<UnknownDictionary> {
     let mut unknown_dict_entries = Vec::new();
-    for line in file_content.lines() {
+    for (index, line) in file_content.lines().enumerate() {
         let fields: Vec<&str> = line.split(',').collect::<Vec<&str>>();
-        let entry = parse_dictionary_entry(&fields[..], expected_fields_len)?;
+        let entry = parse_dictionary_entry(&fields[..], expected_fields_len)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_line(index + 1)))?;
         unknown_dict_entries.push(entry);
     }
 
     let category_references = make_category_references(categories, &unknown_dict_entries[..]);
     let costs = make_costs_array(&unknown_dict_entries[..]);
+    let details = unknown_dict_entries
+        .into_iter()
+        .map(|entry| entry.details)
+        .collect();
     Ok(UnknownDictionary {
         category_references,
         costs,
+        details,
     })
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use serde::Serialize;
+
+    use crate::character_definition::CategoryId;
+    use crate::unknown_dictionary::{parse_unk, UnknownDictionary};
+    use crate::word_entry::WordEntry;
+
+    #[test]
+    fn test_parse_unk() {
+        let categories = vec!["DEFAULT".to_string(), "KATAKANA".to_string()];
+        let unknown_dictionary = parse_unk(
+            &categories,
+            "DEFAULT,5,5,4769,記号,一般\nKATAKANA,1285,1285,13398,名詞,固有名詞\nKATAKANA,1293,1293,9964,名詞,一般\n",
+            6,
+        )
+        .unwrap();
+
+        assert_eq!(
+            unknown_dictionary.category_references,
+            vec![vec![0], vec![1, 2]]
+        );
+        assert_eq!(
+            unknown_dictionary.word_details(2),
+            Some(vec!["名詞".to_string(), "一般".to_string()])
+        );
+        assert_eq!(unknown_dictionary.category_id(2), Some(CategoryId(1)));
+        assert_eq!(unknown_dictionary.category_id(3), None);
+    }
+
+    #[test]
+    fn test_load_legacy() {
+        #[derive(Serialize)]
+        struct LegacyUnknownDictionary {
+            category_references: Vec<Vec<u32>>,
+            costs: Vec<WordEntry>,
+        }
+
+        let data = bincode::serialize(&LegacyUnknownDictionary {
+            category_references: vec![vec![0]],
+            costs: vec![WordEntry::default()],
+        })
+        .unwrap();
+        let unknown_dictionary = UnknownDictionary::load(&data).unwrap();
+        assert_eq!(unknown_dictionary.costs.len(), 1);
+        assert_eq!(unknown_dictionary.word_details(0), None);
+    }
+}
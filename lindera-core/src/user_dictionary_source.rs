@@ -0,0 +1,129 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{LinderaError, LinderaErrorKind};
+
+/// The format of a user dictionary source.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserDictionaryFormat {
+    /// Rows of `surface,pos,reading` or `surface,left_id,right_id,cost,features...`.
+    Csv,
+    /// A header row naming the columns, followed by tab separated rows without quoting.
+    Tsv,
+    /// An array of `UserDictionaryEntry` objects.
+    Json,
+    /// One `UserDictionaryEntry` object per line.
+    Jsonl,
+    /// A user dictionary built by `lindera build --build-user-dic`.
+    Bin,
+}
+
+impl UserDictionaryFormat {
+    /// Detect the format from the extension of the file.
+    pub fn from_path(path: &Path) -> Option<UserDictionaryFormat> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| UserDictionaryFormat::from_str(&ext.to_lowercase()).ok())
+    }
+}
+
+impl FromStr for UserDictionaryFormat {
+    type Err = LinderaError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "csv" => Ok(UserDictionaryFormat::Csv),
+            "tsv" => Ok(UserDictionaryFormat::Tsv),
+            "json" => Ok(UserDictionaryFormat::Json),
+            "jsonl" | "ndjson" => Ok(UserDictionaryFormat::Jsonl),
+            "bin" => Ok(UserDictionaryFormat::Bin),
+            _ => Err(LinderaErrorKind::Args
+                .with_error(anyhow::anyhow!("Invalid user dictionary format: {}", input))),
+        }
+    }
+}
+
+/// An entry of a user dictionary source with named fields.
+///
+/// If `features` is empty, the details are made from `pos` and `reading` as for a simple CSV
+/// entry. Otherwise `features` are the details, as the fields after the cost of a detailed CSV
+/// entry.
+/// The cost and the context ids that are not given are assigned as for a simple CSV entry.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UserDictionaryEntry {
+    pub surface: String,
+    #[serde(default)]
+    pub pos: Option<String>,
+    #[serde(default)]
+    pub reading: Option<String>,
+    #[serde(default, alias = "word_cost")]
+    pub cost: Option<i16>,
+    #[serde(default)]
+    pub left_id: Option<u16>,
+    #[serde(default)]
+    pub right_id: Option<u16>,
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl UserDictionaryEntry {
+    pub fn new(surface: &str) -> Self {
+        UserDictionaryEntry {
+            surface: surface.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the value of a named field, as used by the layout of simple entries.
+    pub fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "surface" => Some(self.surface.clone()),
+            "pos" => self.pos.clone(),
+            "reading" => self.reading.clone(),
+            "cost" | "word_cost" => self.cost.map(|cost| cost.to_string()),
+            "left_id" => self.left_id.map(|id| id.to_string()),
+            "right_id" => self.right_id.map(|id| id.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat};
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            UserDictionaryFormat::from_path(Path::new("userdic.JSONL")),
+            Some(UserDictionaryFormat::Jsonl)
+        );
+        assert_eq!(
+            UserDictionaryFormat::from_path(Path::new("userdic.tsv")),
+            Some(UserDictionaryFormat::Tsv)
+        );
+        assert_eq!(UserDictionaryFormat::from_path(Path::new("userdic")), None);
+    }
+
+    #[test]
+    fn test_deserialize_entry() {
+        let entry: UserDictionaryEntry = serde_json::from_str(
+            r#"{"surface": "東京スカイツリー", "pos": "カスタム名詞", "reading": "トウキョウ,スカイツリー", "word_cost": -100}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            entry,
+            UserDictionaryEntry {
+                pos: Some("カスタム名詞".to_string()),
+                reading: Some("トウキョウ,スカイツリー".to_string()),
+                cost: Some(-100),
+                ..UserDictionaryEntry::new("東京スカイツリー")
+            }
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of a problem found in a dictionary source.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ValidationIssueKind {
+    /// The file or a value could not be read.
+    Parse,
+    /// The row has the wrong number of fields.
+    FieldCount,
+    /// The context id is outside the dimensions of `matrix.def`.
+    ContextIdOutOfRange,
+    /// The cost does not fit in i16.
+    CostOutOfRange,
+    /// The category is not defined in `char.def`.
+    UndefinedCategory,
+    /// The row is identical to a preceding row.
+    DuplicateEntry,
+    /// The surface has more entries than a dictionary can hold.
+    TooManyHomographs,
+}
+
+/// A problem found in a dictionary source.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub kind: ValidationIssueKind,
+    /// The source file
+    pub path: PathBuf,
+    /// The line number in the source file, starting from 1
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// The problems found in a dictionary source.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(
+        &mut self,
+        kind: ValidationIssueKind,
+        path: &Path,
+        line: Option<usize>,
+        message: impl Into<String>,
+    ) {
+        self.issues.push(ValidationIssue {
+            kind,
+            path: path.to_path_buf(),
+            line,
+            message: message.into(),
+        });
+    }
+
+    /// Returns true if no problem was found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
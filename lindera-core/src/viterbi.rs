@@ -1,4 +1,4 @@
-use std::u32;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     character_definition::{CategoryId, CharacterDefinitions},
@@ -11,12 +11,18 @@ use crate::{
 
 const EOS_NODE: EdgeId = EdgeId(1u32);
 
-#[derive(Clone, Copy, Debug, Default)]
+/// The kind of an edge of the lattice, that is where its word comes from.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub enum EdgeType {
+    /// A word of the system dictionary.
     #[default]
     KNOWN,
+    /// An unknown word, made from an entry of the unknown dictionary.
     UNKNOWN,
+    /// A word of the user dictionary.
     USER,
+    /// An edge given to the lattice instead of being looked up, such as a word of the training
+    /// corpus.
     INSERTED,
 }
 
@@ -35,6 +41,9 @@ pub struct Edge {
     pub stop_index: u32,
 
     pub kanji_only: bool,
+
+    /// The id of the entry of the unknown dictionary, for the unknown words.
+    pub unknown_word_id: Option<u32>,
 }
 
 impl Edge {
@@ -91,6 +100,30 @@ impl Lattice {
         unknown_dictionary: &UnknownDictionary,
         text: &str,
         search_mode: &Mode,
+    ) {
+        self.set_text_with_inserted_edges(
+            dict,
+            user_dict,
+            char_definitions,
+            unknown_dictionary,
+            text,
+            search_mode,
+            Vec::new(),
+        );
+    }
+
+    /// Same as `set_text`, but also adds the given edges to the lattice before looking up the
+    /// dictionaries, so that words can also start where an inserted edge ends.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_text_with_inserted_edges(
+        &mut self,
+        dict: &PrefixDict,
+        user_dict: &Option<&PrefixDict>,
+        char_definitions: &CharacterDefinitions,
+        unknown_dictionary: &UnknownDictionary,
+        text: &str,
+        search_mode: &Mode,
+        inserted_edges: Vec<Edge>,
     ) {
         let len = text.len();
         self.set_capacity(len);
@@ -102,6 +135,13 @@ impl Lattice {
         self.ends_at[0].push(start_edge_id);
         self.starts_at[len].push(end_edge_id);
 
+        for edge in inserted_edges {
+            self.add_edge_in_lattice(Edge {
+                edge_type: EdgeType::INSERTED,
+                ..edge
+            });
+        }
+
         // index of the last character of unknown word
         let mut unknown_word_end: Option<usize> = None;
 
@@ -121,13 +161,14 @@ impl Lattice {
                 let dict = user_dict.as_ref().unwrap();
                 for (prefix_len, word_entry) in dict.prefix(suffix) {
                     let edge = Edge {
-                        edge_type: EdgeType::KNOWN,
+                        edge_type: EdgeType::USER,
                         word_entry,
                         left_edge: None,
                         start_index: start as u32,
                         stop_index: (start + prefix_len) as u32,
-                        path_cost: i32::max_value(),
+                        path_cost: i32::MAX,
                         kanji_only: is_kanji_only(&suffix[..prefix_len]),
+                        unknown_word_id: None,
                     };
                     self.add_edge_in_lattice(edge);
                     found = true;
@@ -143,8 +184,9 @@ impl Lattice {
                     left_edge: None,
                     start_index: start as u32,
                     stop_index: (start + prefix_len) as u32,
-                    path_cost: i32::max_value(),
+                    path_cost: i32::MAX,
                     kanji_only: is_kanji_only(&suffix[..prefix_len]),
+                    unknown_word_id: None,
                 };
                 self.add_edge_in_lattice(edge);
                 found = true;
@@ -214,8 +256,9 @@ impl Lattice {
                     left_edge: None,
                     start_index: start as u32,
                     stop_index: (start + unknown_word.len()) as u32,
-                    path_cost: i32::max_value(),
+                    path_cost: i32::MAX,
                     kanji_only: is_kanji_only(&unknown_word[..]),
+                    unknown_word_id: Some(word_id),
                 };
                 self.add_edge_in_lattice(edge);
             }
@@ -242,6 +285,16 @@ impl Lattice {
         &self.edges[edge_id.0 as usize]
     }
 
+    /// Returns the ids of the edges starting at the given byte position.
+    pub fn starts_at(&self, index: usize) -> &[EdgeId] {
+        &self.starts_at[index]
+    }
+
+    /// Returns the ids of the edges ending at the given byte position.
+    pub fn ends_at(&self, index: usize) -> &[EdgeId] {
+        &self.ends_at[index]
+    }
+
     #[inline(never)]
     pub fn calculate_path_costs(&mut self, cost_matrix: &ConnectionCostMatrix, mode: &Mode) {
         let text_len = self.starts_at.len();
@@ -271,21 +324,34 @@ impl Lattice {
         }
     }
 
+    /// Returns the cost of the best path, from the start to the end of the text.
+    /// `calculate_path_costs` must have been called.
+    pub fn best_path_cost(&self) -> i32 {
+        self.edge(EOS_NODE).path_cost
+    }
+
     pub fn tokens_offset(&self) -> Vec<(usize, WordId)> {
-        let mut offsets = Vec::new();
+        self.token_edges()
+            .into_iter()
+            .map(|edge| (edge.start_index as usize, edge.word_entry.word_id))
+            .collect()
+    }
+
+    /// Returns the edges of the best path, from the start of the text.
+    pub fn token_edges(&self) -> Vec<&Edge> {
+        let mut edges = Vec::new();
         let mut edge_id = EOS_NODE;
-        let _edge = self.edge(EOS_NODE);
         loop {
             let edge = self.edge(edge_id);
             if let Some(left_edge_id) = edge.left_edge {
-                offsets.push((edge.start_index as usize, edge.word_entry.word_id));
+                edges.push(edge);
                 edge_id = left_edge_id;
             } else {
                 break;
             }
         }
-        offsets.reverse();
-        offsets.pop();
-        offsets
+        edges.reverse();
+        edges.pop();
+        edges
     }
 }
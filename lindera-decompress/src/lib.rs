@@ -21,6 +21,10 @@ impl CompressedData {
     pub fn new(algorithm: Algorithm, data: Vec<u8>) -> Self {
         CompressedData { algorithm, data }
     }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
 }
 
 pub fn decompress(data: CompressedData) -> anyhow::Result<Vec<u8>> {
@@ -6,7 +6,7 @@ use std::path::Path;
 
 use derive_builder::Builder;
 use lindera_core::character_definition::{CharacterDefinitions, CharacterDefinitionsBuilder};
-use lindera_core::error::LinderaErrorKind;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
 use log::debug;
@@ -34,7 +34,9 @@ impl CharDefBuilder {
         let char_def = read_file_with_encoding(&char_def_path, &self.encoding)?;
 
         let mut char_definitions_builder = CharacterDefinitionsBuilder::default();
-        char_definitions_builder.parse(&char_def)?;
+        char_definitions_builder
+            .parse(&char_def)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(&char_def_path)))?;
         let char_definitions = char_definitions_builder.build();
 
         let mut chardef_buffer = Vec::new();
@@ -7,7 +7,7 @@ use std::str::FromStr;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use derive_builder::Builder;
-use lindera_core::error::LinderaErrorKind;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
 use log::debug;
@@ -31,17 +31,41 @@ impl CostMatrixBuilder {
         let matrix_data = read_file_with_encoding(&matrix_data_path, &self.encoding)?;
 
         let mut lines = Vec::new();
-        for line in matrix_data.lines() {
+        for (index, line) in matrix_data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let location = ErrorLocation::new()
+                .with_path(&matrix_data_path)
+                .with_line(index + 1);
             let fields: Vec<i32> = line
                 .split_whitespace()
-                .map(i32::from_str)
-                .collect::<Result<_, _>>()
-                .map_err(|err| LinderaErrorKind::Parse.with_error(anyhow::anyhow!(err)))?;
-            lines.push(fields);
+                .enumerate()
+                .map(|(field, value)| {
+                    i32::from_str(value).map_err(|err| {
+                        LinderaErrorKind::Parse
+                            .with_error(anyhow::anyhow!(err))
+                            .add_location(location.clone().with_field(field, value))
+                    })
+                })
+                .collect::<LinderaResult<_>>()?;
+            let expected_fields_num = if lines.is_empty() { 2 } else { 3 };
+            if fields.len() != expected_fields_num {
+                return Err(LinderaErrorKind::Content
+                    .with_error(anyhow::anyhow!(
+                        "Invalid number of fields. Expect {}, got {}",
+                        expected_fields_num,
+                        fields.len()
+                    ))
+                    .add_location(location));
+            }
+            lines.push((location, fields));
         }
         let mut lines_it = lines.into_iter();
-        let header = lines_it.next().ok_or_else(|| {
-            LinderaErrorKind::Content.with_error(anyhow::anyhow!("unknown error"))
+        let (_, header) = lines_it.next().ok_or_else(|| {
+            LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("unknown error"))
+                .add_location(ErrorLocation::new().with_path(&matrix_data_path))
         })?;
         let forward_size = header[0] as u32;
         let backward_size = header[1] as u32;
@@ -49,9 +73,18 @@ impl CostMatrixBuilder {
         let mut costs = vec![i16::MAX; len];
         costs[0] = forward_size as i16;
         costs[1] = backward_size as i16;
-        for fields in lines_it {
+        for (location, fields) in lines_it {
             let forward_id = fields[0] as u32;
             let backward_id = fields[1] as u32;
+            if forward_id >= forward_size || backward_id >= backward_size {
+                return Err(LinderaErrorKind::Content
+                    .with_error(anyhow::anyhow!(
+                        "context id is out of range: {} {}",
+                        forward_id,
+                        backward_id
+                    ))
+                    .add_location(location));
+            }
             let cost = fields[2] as u16;
             costs[2 + (backward_id + forward_id * backward_size) as usize] = cost as i16;
         }
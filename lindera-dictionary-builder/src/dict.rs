@@ -1,27 +1,31 @@
 use std::borrow::Cow;
-use std::collections::BTreeMap;
-use std::fs::File;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
 use std::io::Write;
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use byteorder::{LittleEndian, WriteBytesExt};
 use csv::StringRecord;
 use derive_builder::Builder;
 use encoding_rs::{Encoding, UTF_8};
 use encoding_rs_io::DecodeReaderBytesBuilder;
 use glob::glob;
 use log::{debug, warn};
+use rayon::prelude::*;
 use yada::builder::DoubleArrayBuilder;
 
-use lindera_core::error::LinderaErrorKind;
+use lindera_core::error::{ErrorLocation, LinderaError, LinderaErrorKind};
 use lindera_core::word_entry::{WordEntry, WordId};
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
 
-use crate::utils::compress_write;
+use crate::utils::{compress_write, compress_write_file, csv_error_location};
+
+/// The number of rows encoded in parallel at once.
+const ENCODE_BATCH_SIZE: usize = 10000;
 
 #[derive(Builder, Debug)]
 #[builder(name = "DictBuilderOptions")]
@@ -38,10 +42,54 @@ pub struct DictBuilder {
     normalize_details: bool,
     #[builder(default = "false")]
     skip_invalid_cost_or_id: bool,
+    /* The number of threads to read the CSV files and encode the rows with. If set to 0, the global rayon thread pool is used. */
+    #[builder(default = "0")]
+    threads: usize,
+    /* If set, the rows are sorted in chunks of this size that are spilled to temporary files and merged,
+    so that all the rows are not held in memory at once. Only the distinct surfaces are then held in memory,
+    to build the double array.
+    Defaults to the value of the `LINDERA_MAX_ROWS_IN_MEMORY` environment variable. */
+    #[builder(setter(strip_option), default = "max_rows_in_memory_from_env()")]
+    max_rows_in_memory: Option<usize>,
+}
+
+fn max_rows_in_memory_from_env() -> Option<usize> {
+    std::env::var("LINDERA_MAX_ROWS_IN_MEMORY")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+}
+
+/// A lexicon row with the location it was read from.
+struct Row<'a> {
+    path: Option<&'a Path>,
+    line: Option<usize>,
+    record: StringRecord,
+}
+
+impl<'a> Row<'a> {
+    fn new(path: Option<&'a Path>, record: StringRecord) -> Self {
+        let line = record.position().map(|position| position.line() as usize);
+        Row { path, line, record }
+    }
+
+    fn location(&self) -> ErrorLocation {
+        let mut location = ErrorLocation::new();
+        if let Some(path) = self.path {
+            location = location.with_path(path);
+        }
+        if let Some(line) = self.line {
+            location = location.with_line(line);
+        }
+        location
+    }
 }
 
 impl DictBuilder {
     pub fn build(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        self.install(|| self.build_dir(input_dir, output_dir))
+    }
+
+    fn build_dir(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
         let pattern = if let Some(path) = input_dir.to_str() {
             format!("{}/*.csv", path)
         } else {
@@ -67,166 +115,290 @@ impl DictBuilder {
             }
         }
 
+        // The order of the files does not depend on the file system
+        filenames.sort();
+
         let encoding = Encoding::for_label_no_replacement(self.encoding.as_bytes());
         let encoding = encoding.ok_or_else(|| {
             LinderaErrorKind::Decode.with_error(anyhow!("Invalid encoding: {}", self.encoding))
         })?;
 
-        let mut rows: Vec<StringRecord> = vec![];
-        for filename in filenames {
-            debug!("reading {:?}", filename);
+        if let Some(max_rows_in_memory) = self.max_rows_in_memory {
+            return self.build_with_external_sort(
+                &filenames,
+                encoding,
+                max_rows_in_memory,
+                output_dir,
+            );
+        }
 
-            let file = File::open(filename)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-            let reader: Box<dyn Read> = if encoding == UTF_8 {
-                Box::new(file)
-            } else {
-                Box::new(
-                    DecodeReaderBytesBuilder::new()
-                        .encoding(Some(encoding))
-                        .build(file),
-                )
-            };
-            let mut rdr = csv::ReaderBuilder::new()
-                .has_headers(false)
-                .flexible(self.flexible_csv)
-                .from_reader(reader);
+        let mut rows: Vec<Row> = vec![];
+        for (filename, records) in filenames.iter().zip(self.read_files(&filenames, encoding)?) {
+            rows.extend(
+                records
+                    .into_iter()
+                    .map(|record| Row::new(Some(filename.as_path()), record)),
+            );
+        }
 
-            for result in rdr.records() {
-                let record =
-                    result.map_err(|err| LinderaErrorKind::Content.with_error(anyhow!(err)))?;
-                rows.push(record);
-            }
+        self.build_rows(rows, output_dir)
+    }
+
+    /// Build the dictionary from lexicon rows that are already read.
+    /// Each row consists of the surface, the left context id, the right context id, the word cost
+    /// and the details.
+    pub fn build_from_records(
+        &self,
+        rows: Vec<StringRecord>,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        self.install(|| {
+            self.build_rows(
+                rows.into_iter().map(|row| Row::new(None, row)).collect(),
+                output_dir,
+            )
+        })
+    }
+
+    /// Run `op` in a thread pool of `threads` threads, or in the global one if `threads` is 0.
+    fn install<T: Send>(&self, op: impl FnOnce() -> LinderaResult<T> + Send) -> LinderaResult<T> {
+        if self.threads == 0 {
+            return op();
         }
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(self.threads)
+            .build()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
+            .install(op)
+    }
+
+    fn build_rows(&self, mut rows: Vec<Row>, output_dir: &Path) -> LinderaResult<()> {
+        self.sort_rows(&mut rows, |row| &row.record);
+
+        self.write_sorted_rows(rows.into_iter().map(Ok), output_dir)
+    }
+
+    /// Read the CSV files in parallel, keeping the order of the files.
+    fn read_files(
+        &self,
+        filenames: &[PathBuf],
+        encoding: &'static Encoding,
+    ) -> LinderaResult<Vec<Vec<StringRecord>>> {
+        filenames
+            .par_iter()
+            .map(|filename| {
+                self.read_file(filename, encoding)?
+                    .into_records()
+                    .map(|result| result.map_err(|err| csv_error(filename, err)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn read_file(
+        &self,
+        filename: &Path,
+        encoding: &'static Encoding,
+    ) -> LinderaResult<csv::Reader<Box<dyn Read>>> {
+        debug!("reading {:?}", filename);
 
+        let file = File::open(filename)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        let reader: Box<dyn Read> = if encoding == UTF_8 {
+            Box::new(file)
+        } else {
+            Box::new(
+                DecodeReaderBytesBuilder::new()
+                    .encoding(Some(encoding))
+                    .build(file),
+            )
+        };
+        Ok(csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(self.flexible_csv)
+            .from_reader(reader))
+    }
+
+    /// Sort the rows by surface, and then by all the fields, so that the order of the rows (and
+    /// so the word ids) does not depend on the order of the input.
+    fn sort_rows<T>(&self, rows: &mut Vec<T>, record: impl Fn(&T) -> &StringRecord) {
         if self.normalize_details {
-            rows.sort_by_key(|row| normalize(&row[0]));
+            let mut keyed_rows: Vec<(String, T)> = rows
+                .drain(..)
+                .map(|row| (normalize(&record(&row)[0]), row))
+                .collect();
+            keyed_rows.sort_by(|(a_key, a), (b_key, b)| {
+                a_key
+                    .cmp(b_key)
+                    .then_with(|| record(a).iter().cmp(record(b).iter()))
+            });
+            rows.extend(keyed_rows.into_iter().map(|(_, row)| row));
         } else {
-            rows.sort_by(|a, b| a[0].cmp(&b[0]))
+            // The surface is the first field
+            rows.sort_by(|a, b| record(a).iter().cmp(record(b).iter()));
         }
+    }
 
-        let wtr_da_path = output_dir.join(Path::new("dict.da"));
-        let mut wtr_da = io::BufWriter::new(
-            File::create(wtr_da_path)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
-        );
+    fn sort_key(&self, record: &StringRecord) -> String {
+        if self.normalize_details {
+            normalize(&record[0])
+        } else {
+            record[0].to_string()
+        }
+    }
 
-        let wtr_vals_path = output_dir.join(Path::new("dict.vals"));
-        let mut wtr_vals = io::BufWriter::new(
-            File::create(wtr_vals_path)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+    /// Build the dictionary, sorting the rows in chunks of `max_rows_in_memory` rows that are
+    /// spilled to temporary files and merged.
+    fn build_with_external_sort(
+        &self,
+        filenames: &[PathBuf],
+        encoding: &'static Encoding,
+        max_rows_in_memory: usize,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let tmp_dir = output_dir.join(".lindera-sort");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        fs::create_dir_all(&tmp_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        let result = self.sort_and_write_runs(
+            filenames,
+            encoding,
+            max_rows_in_memory.max(1),
+            &tmp_dir,
+            output_dir,
         );
 
-        let mut word_entry_map: BTreeMap<String, Vec<WordEntry>> = BTreeMap::new();
+        let _ = fs::remove_dir_all(&tmp_dir);
 
-        for (row_id, row) in rows.iter().enumerate() {
-            let word_cost = match i16::from_str(row[3].trim()) {
-                Ok(wc) => wc,
-                Err(_err) => {
-                    if self.skip_invalid_cost_or_id {
-                        warn!("failed to parse word_cost: {:?}", row);
-                        continue;
-                    } else {
-                        return Err(LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse word_cost")));
-                    }
-                }
-            };
-            let left_id = match u16::from_str(row[1].trim()) {
-                Ok(lid) => lid,
-                Err(_err) => {
-                    if self.skip_invalid_cost_or_id {
-                        warn!("failed to parse left_id: {:?}", row);
-                        continue;
-                    } else {
-                        return Err(LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse left_id")));
-                    }
-                }
-            };
-            let right_id = match u16::from_str(row[2].trim()) {
-                Ok(rid) => rid,
-                Err(_err) => {
-                    if self.skip_invalid_cost_or_id {
-                        warn!("failed to parse right_id: {:?}", row);
-                        continue;
-                    } else {
-                        return Err(LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse right_id")));
-                    }
+        result
+    }
+
+    fn sort_and_write_runs(
+        &self,
+        filenames: &[PathBuf],
+        encoding: &'static Encoding,
+        max_rows_in_memory: usize,
+        tmp_dir: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let mut runs: Vec<PathBuf> = Vec::new();
+        let mut chunk: Vec<(usize, Row)> = Vec::new();
+        for (file_index, filename) in filenames.iter().enumerate() {
+            for result in self.read_file(filename, encoding)?.into_records() {
+                let record = result.map_err(|err| csv_error(filename, err))?;
+                chunk.push((file_index, Row::new(Some(filename.as_path()), record)));
+                if chunk.len() >= max_rows_in_memory {
+                    runs.push(self.write_run(&mut chunk, tmp_dir, runs.len())?);
                 }
-            };
-            let key = if self.normalize_details {
-                normalize(&row[0])
-            } else {
-                row[0].to_string()
-            };
-            word_entry_map.entry(key).or_default().push(WordEntry {
-                word_id: WordId(row_id as u32, true),
-                word_cost,
-                left_id,
-                right_id,
-            });
+            }
         }
 
-        let wtr_words_path = output_dir.join(Path::new("dict.words"));
-        let mut wtr_words = io::BufWriter::new(
-            File::create(wtr_words_path)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
-        );
+        // Everything fits in memory, no need to merge
+        if runs.is_empty() {
+            return self.build_rows(chunk.into_iter().map(|(_, row)| row).collect(), output_dir);
+        }
+        if !chunk.is_empty() {
+            runs.push(self.write_run(&mut chunk, tmp_dir, runs.len())?);
+        }
 
-        let wtr_words_idx_path = output_dir.join(Path::new("dict.wordsidx"));
-        let mut wtr_words_idx = io::BufWriter::new(
-            File::create(wtr_words_idx_path)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
-        );
+        let merged = MergedRuns::new(self, &runs, filenames)?;
+        self.write_sorted_rows(merged, output_dir)
+    }
 
-        let mut words_buffer = Vec::new();
-        let mut words_idx_buffer = Vec::new();
-        for row in rows.iter() {
-            let offset = words_buffer.len();
-            words_idx_buffer
-                .write_u32::<LittleEndian>(offset as u32)
-                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    /// Sort the chunk and write it to a temporary file.
+    /// Each row of the file is prefixed with the index of the source file and the line number.
+    fn write_run(
+        &self,
+        chunk: &mut Vec<(usize, Row)>,
+        tmp_dir: &Path,
+        run_index: usize,
+    ) -> LinderaResult<PathBuf> {
+        self.sort_rows(chunk, |(_, row)| &row.record);
 
-            let joined_details = if self.normalize_details {
-                row.iter()
-                    .skip(4)
-                    .map(|item| normalize(item))
-                    .collect::<Vec<String>>()
-                    .join("\0")
-            } else {
-                row.iter().skip(4).collect::<Vec<&str>>().join("\0")
-            };
-            let joined_details_len = u32::try_from(joined_details.as_bytes().len())
-                .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
-            words_buffer
-                .write_u32::<LittleEndian>(joined_details_len)
-                .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
-            words_buffer
-                .write_all(joined_details.as_bytes())
-                .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+        let run_path = tmp_dir.join(format!("run-{}.csv", run_index));
+        debug!("writing {:?}", run_path);
+        let mut wtr = csv::WriterBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_path(&run_path)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        for (file_index, row) in chunk.drain(..) {
+            let file_index = file_index.to_string();
+            let line = row.line.map(|line| line.to_string()).unwrap_or_default();
+            let mut fields = vec![file_index.as_str(), line.as_str()];
+            fields.extend(row.record.iter());
+            wtr.write_record(&fields)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
         }
+        wtr.flush()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
 
-        compress_write(&words_buffer, self.compress_algorithm, &mut wtr_words)?;
-        compress_write(
-            &words_idx_buffer,
-            self.compress_algorithm,
-            &mut wtr_words_idx,
-        )?;
+        Ok(run_path)
+    }
 
-        wtr_words
-            .flush()
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-        wtr_words_idx
-            .flush()
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+    /// Write the dictionary from the rows sorted by surface.
+    /// The rows are encoded in parallel in batches, and the encoded rows are streamed to the
+    /// output files, so that only the distinct surfaces are held in memory.
+    fn write_sorted_rows<'a>(
+        &self,
+        rows: impl Iterator<Item = LinderaResult<Row<'a>>>,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let mut vals = SpilledOutput::create(output_dir, "dict.vals")?;
+        let mut words = SpilledOutput::create(output_dir, "dict.words")?;
+        let mut words_idx = SpilledOutput::create(output_dir, "dict.wordsidx")?;
+
+        // The surfaces and the numbers of their entries, in the order of the surfaces
+        let mut keys: Vec<(String, u32)> = Vec::new();
+        let mut words_len = 0usize;
+        let mut row_id = 0usize;
+        let mut rows = rows.fuse();
+        let mut batch: Vec<Row> = Vec::with_capacity(ENCODE_BATCH_SIZE);
+        loop {
+            batch.clear();
+            for row in rows.by_ref().take(ENCODE_BATCH_SIZE) {
+                batch.push(row?);
+            }
+            if batch.is_empty() {
+                break;
+            }
+
+            let encoded_rows = batch
+                .par_iter()
+                .enumerate()
+                .map(|(index, row)| self.encode_row(row_id + index, row))
+                .collect::<Vec<_>>();
+            row_id += batch.len();
+
+            for encoded_row in encoded_rows {
+                let encoded_row = encoded_row?;
+                if let Some((key, word_entry)) = encoded_row.entry {
+                    match keys.last_mut() {
+                        Some((last_key, len)) if *last_key == key => *len += 1,
+                        _ => keys.push((key, 1)),
+                    }
+                    vals.write_all(&word_entry)?;
+                }
+
+                words_idx.write_all(&(words_len as u32).to_le_bytes())?;
+
+                let joined_details = encoded_row.details.as_bytes();
+                let joined_details_len = u32::try_from(joined_details.len())
+                    .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+                words.write_all(&joined_details_len.to_le_bytes())?;
+                words.write_all(joined_details)?;
+                words_len += 4 + joined_details.len();
+            }
+        }
+
+        words.finish(self.compress_algorithm)?;
+        words_idx.finish(self.compress_algorithm)?;
 
         let mut id = 0u32;
 
         let mut keyset: Vec<(&[u8], u32)> = vec![];
-        for (key, word_entries) in &word_entry_map {
-            let len = word_entries.len() as u32;
+        for (key, len) in &keys {
             let val = (id << 5) | len; // 27bit for word ID, 5bit for different parts of speech on the same surface.
             keyset.push((key.as_bytes(), val));
             id += len;
@@ -236,27 +408,317 @@ impl DictBuilder {
             LinderaErrorKind::Io.with_error(anyhow::anyhow!("DoubleArray build error."))
         })?;
 
+        let wtr_da_path = output_dir.join(Path::new("dict.da"));
+        let mut wtr_da = io::BufWriter::new(
+            File::create(wtr_da_path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+        );
         compress_write(&da_bytes, self.compress_algorithm, &mut wtr_da)?;
+        wtr_da
+            .flush()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        vals.finish(self.compress_algorithm)?;
+
+        Ok(())
+    }
 
-        let mut vals_buffer = Vec::new();
-        for word_entries in word_entry_map.values() {
-            for word_entry in word_entries {
+    /// Encode the word entry and the details of the row.
+    fn encode_row(&self, row_id: usize, row: &Row) -> LinderaResult<EncodedRow> {
+        let record = &row.record;
+        if record.len() < 4 {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!(
+                    "Invalid number of fields. Expect at least 4, got {}",
+                    record.len()
+                ))
+                .add_location(row.location()));
+        }
+
+        let entry = match self.word_entry(row_id, row)? {
+            Some(word_entry) => {
+                let mut word_entry_buffer = Vec::new();
                 word_entry
-                    .serialize(&mut vals_buffer)
+                    .serialize(&mut word_entry_buffer)
                     .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+                Some((self.sort_key(record), word_entry_buffer))
             }
-        }
+            None => None,
+        };
+
+        let details = if self.normalize_details {
+            record
+                .iter()
+                .skip(4)
+                .map(normalize)
+                .collect::<Vec<String>>()
+                .join("\0")
+        } else {
+            record.iter().skip(4).collect::<Vec<&str>>().join("\0")
+        };
 
-        compress_write(&vals_buffer, self.compress_algorithm, &mut wtr_vals)?;
+        Ok(EncodedRow { entry, details })
+    }
 
-        wtr_vals
-            .flush()
+    /// Returns the word entry of the row, or `None` if the row is skipped.
+    fn word_entry(&self, row_id: usize, row: &Row) -> LinderaResult<Option<WordEntry>> {
+        let record = &row.record;
+        let invalid_field = |field: usize, name: &str| {
+            LinderaErrorKind::Parse
+                .with_error(anyhow::anyhow!("failed to parse {}", name))
+                .add_location(row.location().with_field(field, &record[field]))
+        };
+        let word_cost = match i16::from_str(record[3].trim()) {
+            Ok(wc) => wc,
+            Err(_err) => {
+                if self.skip_invalid_cost_or_id {
+                    warn!("failed to parse word_cost: {:?}", record);
+                    return Ok(None);
+                } else {
+                    return Err(invalid_field(3, "word_cost"));
+                }
+            }
+        };
+        let left_id = match u16::from_str(record[1].trim()) {
+            Ok(lid) => lid,
+            Err(_err) => {
+                if self.skip_invalid_cost_or_id {
+                    warn!("failed to parse left_id: {:?}", record);
+                    return Ok(None);
+                } else {
+                    return Err(invalid_field(1, "left_id"));
+                }
+            }
+        };
+        let right_id = match u16::from_str(record[2].trim()) {
+            Ok(rid) => rid,
+            Err(_err) => {
+                if self.skip_invalid_cost_or_id {
+                    warn!("failed to parse right_id: {:?}", record);
+                    return Ok(None);
+                } else {
+                    return Err(invalid_field(2, "right_id"));
+                }
+            }
+        };
+        Ok(Some(WordEntry {
+            word_id: WordId(row_id as u32, true),
+            word_cost,
+            left_id,
+            right_id,
+        }))
+    }
+}
+
+/// A lexicon row encoded for the dictionary files.
+struct EncodedRow {
+    /// The sort key of the surface and the serialized word entry, or `None` if the row is skipped
+    entry: Option<(String, Vec<u8>)>,
+    /// The details joined with NUL
+    details: String,
+}
+
+/// An output file of which the uncompressed content is written to a temporary file first, so that
+/// it is not held in memory. The temporary file is removed when it is dropped.
+struct SpilledOutput {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    writer: Option<io::BufWriter<File>>,
+}
+
+impl SpilledOutput {
+    fn create(output_dir: &Path, name: &str) -> LinderaResult<Self> {
+        let tmp_path = output_dir.join(format!(".{}.tmp", name));
+        let file = File::create(&tmp_path)
             .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        Ok(SpilledOutput {
+            path: output_dir.join(name),
+            tmp_path,
+            writer: Some(io::BufWriter::new(file)),
+        })
+    }
 
+    fn write_all(&mut self, buf: &[u8]) -> LinderaResult<()> {
+        let writer = self.writer.as_mut().ok_or_else(|| {
+            LinderaErrorKind::Io.with_error(anyhow::anyhow!("{:?} is already written", self.path))
+        })?;
+        writer
+            .write_all(buf)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
+    }
+
+    /// Write the compressed content to the output file.
+    fn finish(&mut self, algorithm: Algorithm) -> LinderaResult<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer
+                .flush()
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        }
+
+        let mut wtr = io::BufWriter::new(
+            File::create(&self.path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+        );
+        compress_write_file(&self.tmp_path, algorithm, &mut wtr)?;
+        wtr.flush()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
+    }
+}
+
+impl Drop for SpilledOutput {
+    fn drop(&mut self) {
+        // Close the temporary file before removing it
+        self.writer.take();
+        let _ = fs::remove_file(&self.tmp_path);
+    }
+}
+
+/// Merges the sorted runs written by [`DictBuilder::write_run`].
+/// Rows are returned in the same order as [`DictBuilder::sort_rows`].
+struct MergedRuns<'a> {
+    builder: &'a DictBuilder,
+    filenames: &'a [PathBuf],
+    readers: Vec<csv::StringRecordsIntoIter<File>>,
+    heads: Vec<Option<Row<'a>>>,
+    heap: BinaryHeap<Reverse<(String, Vec<String>, usize)>>,
+}
+
+impl<'a> MergedRuns<'a> {
+    fn new(
+        builder: &'a DictBuilder,
+        runs: &[PathBuf],
+        filenames: &'a [PathBuf],
+    ) -> LinderaResult<Self> {
+        let mut merged = MergedRuns {
+            builder,
+            filenames,
+            readers: Vec::new(),
+            heads: Vec::new(),
+            heap: BinaryHeap::new(),
+        };
+        for (run_index, run) in runs.iter().enumerate() {
+            let rdr = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_path(run)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            merged.readers.push(rdr.into_records());
+            merged.heads.push(None);
+            merged.advance(run_index)?;
+        }
+        Ok(merged)
+    }
+
+    /// Read the next row of the run.
+    fn advance(&mut self, run_index: usize) -> LinderaResult<()> {
+        let Some(result) = self.readers[run_index].next() else {
+            return Ok(());
+        };
+        let record = result.map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        let path = record
+            .get(0)
+            .and_then(|file_index| file_index.parse::<usize>().ok())
+            .and_then(|file_index| self.filenames.get(file_index))
+            .map(|path| path.as_path());
+        let line = record.get(1).and_then(|line| line.parse::<usize>().ok());
+        let row = Row {
+            path,
+            line,
+            record: record.iter().skip(2).collect(),
+        };
+        self.heap.push(Reverse((
+            self.builder.sort_key(&row.record),
+            row.record.iter().map(str::to_string).collect(),
+            run_index,
+        )));
+        self.heads[run_index] = Some(row);
         Ok(())
     }
 }
 
+impl<'a> Iterator for MergedRuns<'a> {
+    type Item = LinderaResult<Row<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((_, _, run_index)) = self.heap.pop()?;
+        let row = self.heads[run_index].take()?;
+        match self.advance(run_index) {
+            Ok(()) => Some(Ok(row)),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+fn csv_error(path: &Path, err: csv::Error) -> LinderaError {
+    let location = csv_error_location(path, &err);
+    LinderaErrorKind::Content
+        .with_error(anyhow!(err))
+        .add_location(location)
+}
+
 fn normalize(text: &str) -> String {
     text.to_string().replace('―', "—").replace('～', "〜")
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::dict::DictBuilderOptions;
+
+    fn read_output(output_dir: &Path) -> Vec<Vec<u8>> {
+        ["dict.da", "dict.vals", "dict.words", "dict.wordsidx"]
+            .iter()
+            .map(|name| fs::read(output_dir.join(name)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_with_external_sort() {
+        let tmp_dir = std::env::temp_dir().join("lindera-dictionary-builder-dict");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let input_dir = tmp_dir.join("input");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(
+            input_dir.join("a.csv"),
+            "東京,1,1,100,名詞,固有名詞\n京都,2,2,200,名詞,固有名詞\n東,3,3,300,名詞,一般\n",
+        )
+        .unwrap();
+        fs::write(
+            input_dir.join("b.csv"),
+            "東京,4,4,400,名詞,一般\n大阪,5,5,500,名詞,固有名詞\n京,6,6,600,名詞,一般\n",
+        )
+        .unwrap();
+
+        let in_memory_dir = tmp_dir.join("in-memory");
+        fs::create_dir_all(&in_memory_dir).unwrap();
+        DictBuilderOptions::default()
+            .threads(2)
+            .builder()
+            .unwrap()
+            .build(&input_dir, &in_memory_dir)
+            .unwrap();
+
+        let external_sort_dir = tmp_dir.join("external-sort");
+        fs::create_dir_all(&external_sort_dir).unwrap();
+        DictBuilderOptions::default()
+            .max_rows_in_memory(2)
+            .builder()
+            .unwrap()
+            .build(&input_dir, &external_sort_dir)
+            .unwrap();
+
+        assert_eq!(read_output(&in_memory_dir), read_output(&external_sort_dir));
+        // The sorted runs and the uncompressed outputs are removed
+        for output_dir in [&in_memory_dir, &external_sort_dir] {
+            assert!(fs::read_dir(output_dir).unwrap().all(|entry| !entry
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .starts_with('.')));
+        }
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
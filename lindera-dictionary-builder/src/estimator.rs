@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use lindera_core::character_definition::CharacterDefinitions;
+use lindera_core::connection::ConnectionCostMatrix;
+use lindera_core::dictionary::Dictionary;
+use lindera_core::mode::Mode;
+use lindera_core::prefix_dict::PrefixDict;
+use lindera_core::unknown_dictionary::UnknownDictionary;
+use lindera_core::viterbi::Lattice;
+use lindera_core::word_entry::WordEntry;
+
+/// Context ids and word cost estimated for a user dictionary entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EstimatedCost {
+    pub left_id: u16,
+    pub right_id: u16,
+    pub word_cost: i16,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct ContextStats {
+    count: usize,
+    cost_sum: i64,
+}
+
+/// The parts of the system dictionary needed to find the best segmentation of a text.
+#[derive(Clone)]
+struct Segmenter {
+    dict: PrefixDict,
+    cost_matrix: ConnectionCostMatrix,
+    char_definitions: CharacterDefinitions,
+    unknown_dictionary: UnknownDictionary,
+}
+
+impl Segmenter {
+    /// The cost of the best path over `text` with the words of the system dictionary.
+    fn best_path_cost(&self, text: &str) -> i32 {
+        let mut lattice = Lattice::default();
+        lattice.set_text(
+            &self.dict,
+            &None,
+            &self.char_definitions,
+            &self.unknown_dictionary,
+            text,
+            &Mode::Normal,
+        );
+        lattice.calculate_path_costs(&self.cost_matrix, &Mode::Normal);
+        lattice.best_path_cost()
+    }
+
+    /// The cost of the path made of the single word `estimated`, between BOS and EOS.
+    fn word_path_cost(&self, estimated: &EstimatedCost) -> i32 {
+        self.cost_matrix.cost(0, estimated.left_id as u32)
+            + estimated.word_cost as i32
+            + self.cost_matrix.cost(estimated.right_id as u32, 0)
+    }
+}
+
+/// Estimates the context ids and the word cost of simple user dictionary entries,
+/// similar to `mecab-dict-index -a`.
+///
+/// This is a frequency heuristic, not a trained model. The estimator collects, for every
+/// part-of-speech of the system dictionary, how often each pair of context ids is used and the
+/// costs of those entries. A simple user entry is then given the most frequently used context ids
+/// of the system entries whose part-of-speech matches its own, and the average cost of those
+/// entries.
+///
+/// The average cost does not tell whether the word is chosen over the words it would otherwise be
+/// split into. So if the estimator was created from a system dictionary, `estimate_word` also
+/// tokenizes the surface with the system dictionary and lowers the cost until the entry is cheaper
+/// than the best split of its surface, between BOS and EOS.
+#[derive(Clone)]
+pub struct CostEstimator {
+    pos_fields_num: usize,
+    stats: BTreeMap<Vec<String>, BTreeMap<(u16, u16), ContextStats>>,
+    segmenter: Option<Segmenter>,
+}
+
+impl fmt::Debug for CostEstimator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CostEstimator")
+            .field("pos_fields_num", &self.pos_fields_num)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CostEstimator {
+    /// Create an empty estimator.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos_fields_num`: The number of leading detail fields that hold the part-of-speech.
+    ///
+    pub fn new(pos_fields_num: usize) -> Self {
+        CostEstimator {
+            pos_fields_num,
+            stats: BTreeMap::new(),
+            segmenter: None,
+        }
+    }
+
+    /// Create an estimator from all the entries of a system dictionary.
+    /// The costs estimated by `estimate_word` are also checked against the segmentation of the
+    /// surfaces with this dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary`: The system dictionary.
+    /// * `pos_fields_num`: The number of leading detail fields that hold the part-of-speech.
+    ///
+    pub fn from_dictionary(dictionary: &Dictionary, pos_fields_num: usize) -> Self {
+        let mut estimator = CostEstimator::new(pos_fields_num);
+        for data in dictionary
+            .dict
+            .vals_data
+            .chunks_exact(WordEntry::SERIALIZED_LEN)
+        {
+            let word_entry = WordEntry::deserialize(data, true);
+            if let Some(details) = dictionary.word_details(word_entry.word_id.0 as usize) {
+                estimator.add(&details, &word_entry);
+            }
+        }
+        estimator.segmenter = Some(Segmenter {
+            dict: dictionary.dict.clone(),
+            cost_matrix: dictionary.cost_matrix.clone(),
+            char_definitions: dictionary.char_definitions.clone(),
+            unknown_dictionary: dictionary.unknown_dictionary.clone(),
+        });
+        estimator
+    }
+
+    /// Register a system dictionary entry.
+    pub fn add(&mut self, details: &[String], word_entry: &WordEntry) {
+        let pos = details
+            .iter()
+            .take(self.pos_fields_num)
+            .cloned()
+            .collect::<Vec<String>>();
+        let stats = self
+            .stats
+            .entry(pos)
+            .or_default()
+            .entry((word_entry.left_id, word_entry.right_id))
+            .or_default();
+        stats.count += 1;
+        stats.cost_sum += word_entry.word_cost as i64;
+    }
+
+    /// Estimate the context ids and the word cost from the details of a user dictionary entry.
+    ///
+    /// A `*` in the part-of-speech fields of `details` matches any value.
+    /// Returns `None` if no system entry has a matching part-of-speech.
+    pub fn estimate<S: AsRef<str>>(&self, details: &[S]) -> Option<EstimatedCost> {
+        let pos = &details[..details.len().min(self.pos_fields_num)];
+
+        let mut candidates: BTreeMap<(u16, u16), ContextStats> = BTreeMap::new();
+        for (key, context_stats) in self.stats.iter() {
+            let matched = pos.iter().enumerate().all(|(i, field)| {
+                let field = field.as_ref();
+                field == "*" || key.get(i).map(|k| k.as_str()) == Some(field)
+            });
+            if !matched {
+                continue;
+            }
+            for (context_ids, stats) in context_stats.iter() {
+                let candidate = candidates.entry(*context_ids).or_default();
+                candidate.count += stats.count;
+                candidate.cost_sum += stats.cost_sum;
+            }
+        }
+
+        // Prefer the most frequent context ids, then the smallest ones to keep the result stable.
+        let ((left_id, right_id), stats) = candidates
+            .into_iter()
+            .max_by(|a, b| a.1.count.cmp(&b.1.count).then(b.0.cmp(&a.0)))?;
+        let word_cost =
+            (stats.cost_sum / stats.count as i64).clamp(i16::MIN as i64, i16::MAX as i64) as i16;
+
+        Some(EstimatedCost {
+            left_id,
+            right_id,
+            word_cost,
+        })
+    }
+
+    /// Estimate the context ids and the word cost of a user dictionary entry, making sure that
+    /// the entry is chosen over the best split of its surface by the system dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// * `surface`: The surface of the entry.
+    /// * `details`: The details of the entry, of which the part-of-speech is matched as in
+    ///   `estimate`.
+    /// * `context_ids`: The context ids of the entry if they are already known, such as from the
+    ///   id definitions. Otherwise the estimated ones are used.
+    ///
+    /// returns: Option<EstimatedCost>
+    ///
+    pub fn estimate_word<S: AsRef<str>>(
+        &self,
+        surface: &str,
+        details: &[S],
+        context_ids: Option<(u16, u16)>,
+    ) -> Option<EstimatedCost> {
+        let mut estimated = self.estimate(details)?;
+        if let Some((left_id, right_id)) = context_ids {
+            estimated.left_id = left_id;
+            estimated.right_id = right_id;
+        }
+
+        if let Some(segmenter) = &self.segmenter {
+            if !surface.is_empty() {
+                let best_path_cost = segmenter.best_path_cost(surface);
+                let word_path_cost = segmenter.word_path_cost(&estimated);
+                if word_path_cost >= best_path_cost {
+                    let word_cost =
+                        estimated.word_cost as i64 - (word_path_cost - best_path_cost) as i64 - 1;
+                    estimated.word_cost = word_cost.clamp(i16::MIN as i64, i16::MAX as i64) as i16;
+                }
+            }
+        }
+
+        Some(estimated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lindera_core::word_entry::{WordEntry, WordId};
+
+    use crate::estimator::{CostEstimator, EstimatedCost};
+
+    fn details(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn word_entry(left_id: u16, right_id: u16, word_cost: i16) -> WordEntry {
+        WordEntry {
+            word_id: WordId(0, true),
+            word_cost,
+            left_id,
+            right_id,
+        }
+    }
+
+    #[test]
+    fn test_estimate() {
+        let mut estimator = CostEstimator::new(4);
+        estimator.add(
+            &details(&["名詞", "固有名詞", "一般", "*", "*", "*", "東京"]),
+            &word_entry(1288, 1288, 3000),
+        );
+        estimator.add(
+            &details(&["名詞", "固有名詞", "一般", "*", "*", "*", "大阪"]),
+            &word_entry(1288, 1288, 5000),
+        );
+        estimator.add(
+            &details(&["名詞", "一般", "*", "*", "*", "*", "机"]),
+            &word_entry(1285, 1285, 7000),
+        );
+        estimator.add(
+            &details(&["助詞", "格助詞", "一般", "*", "*", "*", "が"]),
+            &word_entry(13, 13, 3000),
+        );
+
+        assert_eq!(
+            estimator.estimate(&details(&["名詞", "固有名詞", "*", "*", "*", "*", "*"])),
+            Some(EstimatedCost {
+                left_id: 1288,
+                right_id: 1288,
+                word_cost: 4000,
+            })
+        );
+        assert_eq!(
+            estimator.estimate(&details(&["助詞", "*", "*", "*", "*", "*", "*"])),
+            Some(EstimatedCost {
+                left_id: 13,
+                right_id: 13,
+                word_cost: 3000,
+            })
+        );
+        assert_eq!(
+            estimator.estimate(&details(&["カスタム名詞", "*", "*", "*", "*", "*", "*"])),
+            None
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use derive_builder::Builder;
+use lindera_core::character_definition::CharacterDefinitions;
+use lindera_core::connection::ConnectionCostMatrix;
+use lindera_core::dictionary::Dictionary;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+use lindera_core::unknown_dictionary::UnknownDictionary;
+use lindera_core::LinderaResult;
+use log::debug;
+
+use crate::utils::write_file_with_encoding;
+
+/// The category of the characters that are not in any range of `char.def`.
+const DEFAULT_CATEGORY_NAME: &str = "DEFAULT";
+/// `char.def` can only describe the characters of the Basic Multilingual Plane.
+const MAX_CHAR_DEF_CODE_POINT: u32 = 0xFFFF;
+
+type LexiconRowProcessor = Box<dyn Fn(Vec<String>) -> LinderaResult<Vec<String>>>;
+
+/// Exports a built dictionary back to the MeCab sources it can be rebuilt from:
+/// the lexicon CSV, `matrix.def`, `char.def` and `unk.def`.
+///
+/// The features of the unknown word entries are exported as `*` for the dictionaries that were
+/// built before they were kept. The id definitions (`left-id.def`, `right-id.def` and
+/// `rewrite.def`) are not exported.
+#[derive(Builder)]
+#[builder(pattern = "owned")]
+#[builder(name = "DictionaryExporterOptions")]
+#[builder(build_fn(name = "builder"))]
+pub struct DictionaryExporter {
+    #[builder(default = "\"UTF-8\".into()", setter(into))]
+    encoding: Cow<'static, str>,
+    /// The name of the lexicon file written to the output directory.
+    #[builder(default = "\"lex.csv\".into()", setter(into))]
+    lexicon_file_name: Cow<'static, str>,
+    /// The number of fields of an `unk.def` row, including the category, the context ids and
+    /// the cost.
+    #[builder(default = "11")]
+    unk_fields_num: usize,
+    /* Converts the rows of the lexicon (surface, context ids, cost and details) to the layout of the source. */
+    #[builder(setter(strip_option), default = "None")]
+    lexicon_row_handler: Option<LexiconRowProcessor>,
+}
+
+impl DictionaryExporter {
+    pub fn export(&self, dictionary: &Dictionary, output_dir: &Path) -> LinderaResult<()> {
+        fs::create_dir_all(output_dir).map_err(|err| {
+            LinderaErrorKind::Io
+                .with_error(anyhow::anyhow!(err))
+                .add_location(ErrorLocation::new().with_path(output_dir))
+        })?;
+
+        self.export_lexicon(
+            dictionary,
+            &output_dir.join(self.lexicon_file_name.as_ref()),
+        )?;
+        self.export_cost_matrix(&dictionary.cost_matrix, &output_dir.join("matrix.def"))?;
+        self.export_chardef(&dictionary.char_definitions, &output_dir.join("char.def"))?;
+        self.export_unk(
+            &dictionary.unknown_dictionary,
+            &dictionary.char_definitions,
+            &output_dir.join("unk.def"),
+        )?;
+
+        Ok(())
+    }
+
+    /// The rows are written in the order of the word ids.
+    fn export_lexicon(&self, dictionary: &Dictionary, path: &Path) -> LinderaResult<()> {
+        debug!("writing {:?}", path);
+
+        let mut words = dictionary
+            .dict
+            .entries()
+            .into_iter()
+            .flat_map(|(surface, word_entries)| {
+                word_entries
+                    .into_iter()
+                    .map(move |word_entry| (surface.clone(), word_entry))
+            })
+            .collect::<Vec<_>>();
+        words.sort_by_key(|(_, word_entry)| word_entry.word_id.0);
+
+        let mut wtr = csv::WriterBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_writer(Vec::new());
+        for (surface, word_entry) in words {
+            let details = dictionary
+                .word_details(word_entry.word_id.0 as usize)
+                .ok_or_else(|| {
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                        "no details for word id {}",
+                        word_entry.word_id.0
+                    ))
+                })?;
+            let mut row = vec![
+                surface,
+                word_entry.left_id.to_string(),
+                word_entry.right_id.to_string(),
+                word_entry.word_cost.to_string(),
+            ];
+            row.extend(details);
+            if let Some(handler) = &self.lexicon_row_handler {
+                row = handler(row)?;
+            }
+            wtr.write_record(&row)
+                .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+        }
+        let buffer = wtr
+            .into_inner()
+            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+        let content = String::from_utf8(buffer)
+            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+
+        write_file_with_encoding(path, &content, &self.encoding)
+    }
+
+    fn export_cost_matrix(&self, matrix: &ConnectionCostMatrix, path: &Path) -> LinderaResult<()> {
+        debug!("writing {:?}", path);
+
+        let backward_size = matrix.backward_size;
+        let forward_size = (matrix.costs_data.len() as u32 / 2)
+            .checked_div(backward_size)
+            .unwrap_or_default();
+
+        let mut content = String::new();
+        writeln!(content, "{} {}", forward_size, backward_size).unwrap();
+        for forward_id in 0..forward_size {
+            for backward_id in 0..backward_size {
+                writeln!(
+                    content,
+                    "{} {} {}",
+                    forward_id,
+                    backward_id,
+                    matrix.cost(forward_id, backward_id)
+                )
+                .unwrap();
+            }
+        }
+
+        write_file_with_encoding(path, &content, &self.encoding)
+    }
+
+    /// The ranges of the characters that are only in the default category are not written.
+    fn export_chardef(
+        &self,
+        char_definitions: &CharacterDefinitions,
+        path: &Path,
+    ) -> LinderaResult<()> {
+        debug!("writing {:?}", path);
+
+        let mut content = String::new();
+        for (name, data) in char_definitions
+            .category_names
+            .iter()
+            .zip(char_definitions.category_definitions.iter())
+        {
+            writeln!(
+                content,
+                "{} {} {} {}",
+                name, data.invoke as u8, data.group as u8, data.length
+            )
+            .unwrap();
+        }
+
+        let category_names = |code_point: u32| -> Vec<&str> {
+            char_definitions
+                .mapping
+                .eval(code_point)
+                .iter()
+                .map(|category_id| char_definitions.category_name(*category_id))
+                .collect()
+        };
+        let mut write_range = |start: u32, end: u32, names: &[&str]| {
+            if names.is_empty() || names == [DEFAULT_CATEGORY_NAME] {
+                return;
+            }
+            if start == end {
+                writeln!(content, "0x{:04X} {}", start, names.join(" ")).unwrap();
+            } else {
+                writeln!(
+                    content,
+                    "0x{:04X}..0x{:04X} {}",
+                    start,
+                    end,
+                    names.join(" ")
+                )
+                .unwrap();
+            }
+        };
+
+        // Surrogates are not characters, so they split the ranges.
+        let mut range: Option<(u32, u32, Vec<&str>)> = None;
+        for code_point in 0..=MAX_CHAR_DEF_CODE_POINT {
+            if char::from_u32(code_point).is_none() {
+                if let Some((start, end, names)) = range.take() {
+                    write_range(start, end, &names);
+                }
+                continue;
+            }
+            let names = category_names(code_point);
+            match &mut range {
+                Some((_, end, range_names)) if *range_names == names => *end = code_point,
+                _ => {
+                    if let Some((start, end, names)) = range.take() {
+                        write_range(start, end, &names);
+                    }
+                    range = Some((code_point, code_point, names));
+                }
+            }
+        }
+        if let Some((start, end, names)) = range {
+            write_range(start, end, &names);
+        }
+
+        write_file_with_encoding(path, &content, &self.encoding)
+    }
+
+    /// The rows are written in the order of the word ids.
+    fn export_unk(
+        &self,
+        unknown_dictionary: &UnknownDictionary,
+        char_definitions: &CharacterDefinitions,
+        path: &Path,
+    ) -> LinderaResult<()> {
+        debug!("writing {:?}", path);
+
+        let unknown_features = vec!["*".to_string(); self.unk_fields_num.saturating_sub(4)];
+        let mut content = String::new();
+        for (word_id, word_entry) in unknown_dictionary.costs.iter().enumerate() {
+            let category = unknown_dictionary
+                .category_references
+                .iter()
+                .position(|word_ids| word_ids.contains(&(word_id as u32)))
+                .map(|category_id| char_definitions.category_names[category_id].as_str())
+                .ok_or_else(|| {
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                        "no category for unknown word id {}",
+                        word_id
+                    ))
+                })?;
+            let mut row = vec![
+                category.to_string(),
+                word_entry.left_id.to_string(),
+                word_entry.right_id.to_string(),
+                word_entry.word_cost.to_string(),
+            ];
+            row.extend(
+                unknown_dictionary
+                    .word_details(word_id as u32)
+                    .unwrap_or_else(|| unknown_features.clone()),
+            );
+            writeln!(content, "{}", row.join(",")).unwrap();
+        }
+
+        write_file_with_encoding(path, &content, &self.encoding)
+    }
+}
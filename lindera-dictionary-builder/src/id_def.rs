@@ -0,0 +1,89 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use derive_builder::Builder;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+use lindera_core::id_definition::{
+    parse_id_def, parse_pos_id_def, parse_rewrite_def, IdDefinitions,
+};
+use lindera_core::LinderaResult;
+use lindera_decompress::Algorithm;
+use log::debug;
+
+use crate::utils::{compress_write, read_file_with_encoding};
+
+#[derive(Builder, Debug)]
+#[builder(name = "IdDefBuilderOptions")]
+#[builder(build_fn(name = "builder"))]
+pub struct IdDefBuilder {
+    #[builder(default = "\"UTF-8\".into()", setter(into))]
+    encoding: Cow<'static, str>,
+    #[builder(default = "Algorithm::Deflate")]
+    compress_algorithm: Algorithm,
+}
+
+impl IdDefBuilder {
+    /// Build the id definitions from `left-id.def`, `right-id.def`, `rewrite.def` and `pos-id.def`.
+    /// Files that do not exist in `input_dir` are treated as empty.
+    pub fn build(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<IdDefinitions> {
+        let id_definitions = IdDefinitions {
+            left_ids: match self.read_def(input_dir, "left-id.def")? {
+                Some(content) => parse_id_def(&content).map_err(|err| {
+                    err.add_location(ErrorLocation::new().with_path(&input_dir.join("left-id.def")))
+                })?,
+                None => Default::default(),
+            },
+            right_ids: match self.read_def(input_dir, "right-id.def")? {
+                Some(content) => parse_id_def(&content).map_err(|err| {
+                    err.add_location(
+                        ErrorLocation::new().with_path(&input_dir.join("right-id.def")),
+                    )
+                })?,
+                None => Default::default(),
+            },
+            rewrite_rules: match self.read_def(input_dir, "rewrite.def")? {
+                Some(content) => parse_rewrite_def(&content).map_err(|err| {
+                    err.add_location(ErrorLocation::new().with_path(&input_dir.join("rewrite.def")))
+                })?,
+                None => Default::default(),
+            },
+            pos_ids: match self.read_def(input_dir, "pos-id.def")? {
+                Some(content) => parse_pos_id_def(&content).map_err(|err| {
+                    err.add_location(ErrorLocation::new().with_path(&input_dir.join("pos-id.def")))
+                })?,
+                None => Default::default(),
+            },
+        };
+
+        let mut id_def_buffer = Vec::new();
+        bincode::serialize_into(&mut id_def_buffer, &id_definitions)
+            .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+
+        let wtr_id_def_path = output_dir.join(Path::new("id_def.bin"));
+        let mut wtr_id_def = io::BufWriter::new(
+            File::create(wtr_id_def_path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?,
+        );
+
+        compress_write(&id_def_buffer, self.compress_algorithm, &mut wtr_id_def)?;
+
+        wtr_id_def
+            .flush()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        Ok(id_definitions)
+    }
+
+    fn read_def(&self, input_dir: &Path, filename: &str) -> LinderaResult<Option<String>> {
+        let path = input_dir.join(filename);
+        if !path.exists() {
+            debug!("{:?} does not exist", path);
+            return Ok(None);
+        }
+        debug!("reading {:?}", path);
+        read_file_with_encoding(&path, &self.encoding).map(Some)
+    }
+}
@@ -7,12 +7,23 @@
 pub mod chardef;
 pub mod cost_matrix;
 pub mod dict;
+pub mod estimator;
+pub mod exporter;
+pub mod id_def;
+pub mod schema;
 pub mod unk;
 pub mod user_dict;
+pub mod user_dict_source;
 pub mod utils;
+pub mod validator;
 
 pub use chardef::CharDefBuilderOptions;
 pub use cost_matrix::CostMatrixBuilderOptions;
 pub use dict::DictBuilderOptions;
+pub use estimator::CostEstimator;
+pub use exporter::DictionaryExporterOptions;
+pub use id_def::IdDefBuilderOptions;
+pub use schema::{DictionarySchema, SchemaDictionaryBuilder};
 pub use unk::UnkBuilderOptions;
 pub use user_dict::{build_user_dictionary, UserDictBuilderOptions};
+pub use validator::DictionaryValidatorOptions;
@@ -0,0 +1,598 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use lindera_core::character_definition::CharacterDefinitions;
+use lindera_core::dictionary::{Dictionary, UserDictionary};
+use lindera_core::dictionary_builder::DictionaryBuilder;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+use lindera_core::id_definition::IdDefinitions;
+use lindera_core::user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat};
+use lindera_core::validation::ValidationReport;
+use lindera_core::LinderaResult;
+use lindera_decompress::Algorithm;
+use serde::{Deserialize, Serialize};
+
+use crate::exporter::DictionaryExporterOptions;
+use crate::user_dict::build_user_dictionary;
+use crate::validator::DictionaryValidatorOptions;
+use crate::{
+    CharDefBuilderOptions, CostEstimator, CostMatrixBuilderOptions, DictBuilderOptions,
+    IdDefBuilderOptions, UnkBuilderOptions, UserDictBuilderOptions,
+};
+
+fn default_encoding() -> String {
+    "UTF-8".to_string()
+}
+
+fn default_compress_algorithm() -> Algorithm {
+    Algorithm::Deflate
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_simple_fields() -> Vec<String> {
+    vec![
+        "surface".to_string(),
+        "pos".to_string(),
+        "reading".to_string(),
+    ]
+}
+
+fn default_simple_word_cost() -> i16 {
+    -10000
+}
+
+/// The layout of the user dictionary of a [`DictionarySchema`].
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UserDictionarySchema {
+    /// The names of the fields of simple entries.
+    #[serde(default = "default_simple_fields")]
+    pub simple_fields: Vec<String>,
+    /// The details of simple entries. `$name` is replaced by the field of the simple entry with the
+    /// name, other values are used as they are.
+    /// If empty, the fields of the simple entry except the surface are used.
+    #[serde(default)]
+    pub simple_details: Vec<String>,
+    #[serde(default = "default_simple_word_cost")]
+    pub simple_word_cost: i16,
+    #[serde(default)]
+    pub simple_context_id: u16,
+    /// The number of leading detail fields that identify the part-of-speech, used to estimate the
+    /// costs of simple entries from the system dictionary.
+    #[serde(default)]
+    pub pos_fields_num: usize,
+    #[serde(default = "default_true")]
+    pub flexible_csv: bool,
+}
+
+impl Default for UserDictionarySchema {
+    fn default() -> Self {
+        UserDictionarySchema {
+            simple_fields: default_simple_fields(),
+            simple_details: Vec::new(),
+            simple_word_cost: default_simple_word_cost(),
+            simple_context_id: 0,
+            pos_fields_num: 0,
+            flexible_csv: true,
+        }
+    }
+}
+
+/// A declarative description of a MeCab-format dictionary source.
+///
+/// It covers what differs between the dictionary specific builders, so that a dictionary can be
+/// built without a dedicated builder.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DictionarySchema {
+    #[serde(default)]
+    pub name: String,
+    /// The encoding of the source files.
+    #[serde(default = "default_encoding")]
+    pub encoding: String,
+    #[serde(default = "default_compress_algorithm")]
+    pub compress_algorithm: Algorithm,
+    /// The names of the detail fields, i.e. the fields after the surface, the context ids and
+    /// the cost.
+    pub fields: Vec<String>,
+    /// The number of fields in `unk.def`. Defaults to the number of fields of the lexicon.
+    #[serde(default)]
+    pub unk_fields_num: Option<usize>,
+    #[serde(default)]
+    pub flexible_csv: bool,
+    /// Whether to normalize the dashes and the wave dashes of the surfaces and the details, as
+    /// the IPADIC builder does.
+    #[serde(default)]
+    pub normalize_details: bool,
+    #[serde(default)]
+    pub skip_invalid_cost_or_id: bool,
+    /// If set, the lexicon is sorted in chunks of this number of rows spilled to temporary files,
+    /// to bound the memory used to build large dictionaries.
+    #[serde(default)]
+    pub max_rows_in_memory: Option<usize>,
+    #[serde(default)]
+    pub user_dictionary: UserDictionarySchema,
+}
+
+impl DictionarySchema {
+    pub fn from_slice(data: &[u8]) -> LinderaResult<DictionarySchema> {
+        let schema: DictionarySchema = serde_json::from_slice(data).map_err(|err| {
+            let location = ErrorLocation::new()
+                .with_line(err.line())
+                .with_column(err.column());
+            LinderaErrorKind::Deserialize
+                .with_error(anyhow::anyhow!(err))
+                .add_location(location)
+        })?;
+        schema.validate()?;
+        Ok(schema)
+    }
+
+    pub fn from_file(path: &Path) -> LinderaResult<DictionarySchema> {
+        let data =
+            fs::read(path).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        Self::from_slice(&data)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(path)))
+    }
+
+    fn validate(&self) -> LinderaResult<()> {
+        if self.fields.is_empty() {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("Schema must have at least one field")));
+        }
+        let user_dictionary = &self.user_dictionary;
+        if user_dictionary.simple_fields.first().map(|s| s.as_str()) != Some("surface") {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("The first simple field must be surface")));
+        }
+        if !user_dictionary.simple_details.is_empty()
+            && user_dictionary.simple_details.len() != self.fields.len()
+        {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "Simple details must have {} fields, got {}",
+                self.fields.len(),
+                user_dictionary.simple_details.len()
+            )));
+        }
+        for detail in &user_dictionary.simple_details {
+            if let Some(name) = detail.strip_prefix('$') {
+                if !user_dictionary
+                    .simple_fields
+                    .iter()
+                    .any(|field| field == name)
+                {
+                    return Err(LinderaErrorKind::Content
+                        .with_error(anyhow::anyhow!("Unknown simple field: {}", name)));
+                }
+            }
+        }
+        if user_dictionary.pos_fields_num > self.fields.len() {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "pos_fields_num must not exceed the number of fields"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the number of fields of a lexicon entry.
+    pub fn detailed_fields_num(&self) -> usize {
+        4 + self.fields.len()
+    }
+
+    /// Returns the index of the detail field with the name.
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field == name)
+    }
+}
+
+/// A dictionary builder driven by a [`DictionarySchema`].
+pub struct SchemaDictionaryBuilder {
+    schema: DictionarySchema,
+}
+
+impl SchemaDictionaryBuilder {
+    pub fn new(schema: DictionarySchema) -> Self {
+        SchemaDictionaryBuilder { schema }
+    }
+
+    pub fn schema(&self) -> &DictionarySchema {
+        &self.schema
+    }
+
+    fn user_dict_builder_options(&self) -> UserDictBuilderOptions {
+        let user_dictionary = &self.schema.user_dictionary;
+        let options = UserDictBuilderOptions::default()
+            .simple_userdic_fields_num(user_dictionary.simple_fields.len())
+            .simple_userdic_field_names(user_dictionary.simple_fields.clone())
+            .detailed_userdic_fields_num(self.schema.detailed_fields_num())
+            .simple_word_cost(user_dictionary.simple_word_cost)
+            .simple_context_id(user_dictionary.simple_context_id)
+            .flexible_csv(user_dictionary.flexible_csv);
+        if user_dictionary.simple_details.is_empty() {
+            return options;
+        }
+
+        // Resolve the field references once, so that the handler only copies the values.
+        let details = user_dictionary
+            .simple_details
+            .iter()
+            .map(|detail| match detail.strip_prefix('$') {
+                Some(name) => Ok(user_dictionary
+                    .simple_fields
+                    .iter()
+                    .position(|field| field == name)),
+                None => Err(detail.clone()),
+            })
+            .collect::<Vec<Result<Option<usize>, String>>>();
+        options.simple_userdic_details_handler(Box::new(move |row| {
+            Ok(details
+                .iter()
+                .map(|detail| match detail {
+                    Ok(Some(index)) => row.get(*index).unwrap_or_default().to_string(),
+                    Ok(None) => String::new(),
+                    Err(literal) => literal.clone(),
+                })
+                .collect())
+        }))
+    }
+}
+
+impl DictionaryBuilder for SchemaDictionaryBuilder {
+    fn build_dictionary(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        fs::create_dir_all(output_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        let chardef = self.build_chardef(input_dir, output_dir)?;
+        self.build_unk(input_dir, &chardef, output_dir)?;
+        self.build_dict(input_dir, output_dir)?;
+        self.build_cost_matrix(input_dir, output_dir)?;
+        self.build_id_def(input_dir, output_dir)?;
+
+        Ok(())
+    }
+
+    fn build_user_dictionary(&self, input_file: &Path, output_file: &Path) -> LinderaResult<()> {
+        let user_dict = self.build_user_dict(input_file)?;
+        build_user_dictionary(user_dict, output_file)
+    }
+
+    fn build_user_dictionary_with_estimation(
+        &self,
+        input_file: &Path,
+        output_file: &Path,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<()> {
+        let format =
+            UserDictionaryFormat::from_path(input_file).unwrap_or(UserDictionaryFormat::Csv);
+        let user_dict = self.build_user_dict_with_estimation(input_file, format, dictionary)?;
+        build_user_dictionary(user_dict, output_file)
+    }
+
+    fn build_chardef(
+        &self,
+        input_dir: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<CharacterDefinitions> {
+        CharDefBuilderOptions::default()
+            .encoding(self.schema.encoding.clone())
+            .compress_algorithm(self.schema.compress_algorithm)
+            .builder()
+            .unwrap()
+            .build(input_dir, output_dir)
+    }
+
+    fn build_unk(
+        &self,
+        input_dir: &Path,
+        chardef: &CharacterDefinitions,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        UnkBuilderOptions::default()
+            .encoding(self.schema.encoding.clone())
+            .compress_algorithm(self.schema.compress_algorithm)
+            .unk_fields_num(
+                self.schema
+                    .unk_fields_num
+                    .unwrap_or_else(|| self.schema.detailed_fields_num()),
+            )
+            .builder()
+            .unwrap()
+            .build(input_dir, chardef, output_dir)
+    }
+
+    fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        let mut options = DictBuilderOptions::default();
+        options
+            .flexible_csv(self.schema.flexible_csv)
+            .encoding(self.schema.encoding.clone())
+            .compress_algorithm(self.schema.compress_algorithm)
+            .normalize_details(self.schema.normalize_details)
+            .skip_invalid_cost_or_id(self.schema.skip_invalid_cost_or_id);
+        if let Some(max_rows_in_memory) = self.schema.max_rows_in_memory {
+            options.max_rows_in_memory(max_rows_in_memory);
+        }
+        options.builder().unwrap().build(input_dir, output_dir)
+    }
+
+    fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        CostMatrixBuilderOptions::default()
+            .encoding(self.schema.encoding.clone())
+            .compress_algorithm(self.schema.compress_algorithm)
+            .builder()
+            .unwrap()
+            .build(input_dir, output_dir)
+    }
+
+    fn build_id_def(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<IdDefinitions> {
+        IdDefBuilderOptions::default()
+            .encoding(self.schema.encoding.clone())
+            .compress_algorithm(self.schema.compress_algorithm)
+            .builder()
+            .unwrap()
+            .build(input_dir, output_dir)
+    }
+
+    fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build(input_file)
+    }
+
+    fn build_user_dict_with_format(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build_with_format(input_file, format)
+    }
+
+    fn build_user_dict_from_entries(
+        &self,
+        entries: Vec<UserDictionaryEntry>,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build_from_entries(entries)
+    }
+
+    fn build_user_dict_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build_from_reader(reader, format)
+    }
+
+    fn build_user_dict_with_estimation(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .simple_userdic_estimator(CostEstimator::from_dictionary(
+                dictionary,
+                self.schema.user_dictionary.pos_fields_num,
+            ))
+            .id_definitions(dictionary.id_definitions.clone())
+            .builder()
+            .unwrap()
+            .build_with_format(input_file, format)
+    }
+
+    fn build_user_dict_without_details(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .details(false)
+            .builder()
+            .unwrap()
+            .build_with_format(input_file, format)
+    }
+
+    fn export_dictionary(&self, dictionary: &Dictionary, output_dir: &Path) -> LinderaResult<()> {
+        DictionaryExporterOptions::default()
+            .encoding(self.schema.encoding.clone())
+            .unk_fields_num(
+                self.schema
+                    .unk_fields_num
+                    .unwrap_or_else(|| self.schema.detailed_fields_num()),
+            )
+            .builder()
+            .unwrap()
+            .export(dictionary, output_dir)
+    }
+
+    fn validate_dictionary(&self, input_dir: &Path) -> LinderaResult<ValidationReport> {
+        DictionaryValidatorOptions::default()
+            .encoding(self.schema.encoding.clone())
+            .fields_num(self.schema.detailed_fields_num())
+            .flexible_csv(self.schema.flexible_csv)
+            .unk_fields_num(
+                self.schema
+                    .unk_fields_num
+                    .unwrap_or_else(|| self.schema.detailed_fields_num()),
+            )
+            .normalize_details(self.schema.normalize_details)
+            .builder()
+            .unwrap()
+            .validate(input_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use lindera_core::dictionary_builder::DictionaryBuilder;
+
+    use crate::schema::{DictionarySchema, SchemaDictionaryBuilder};
+
+    /// Read the names and the contents of the files in `dir`, sorted by name.
+    fn read_files(dir: &Path) -> Vec<(String, Vec<u8>)> {
+        let mut files = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy().to_string();
+                (name, fs::read(&path).unwrap())
+            })
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_build_user_dict() {
+        let resources_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../resources");
+        let schema =
+            DictionarySchema::from_file(&resources_dir.join("ipadic_schema.json")).unwrap();
+        assert_eq!(schema.detailed_fields_num(), 13);
+        assert_eq!(schema.field_index("reading"), Some(7));
+
+        let builder = SchemaDictionaryBuilder::new(schema);
+        let user_dict = builder
+            .build_user_dict(&resources_dir.join("ipadic_mixed_userdic.csv"))
+            .unwrap();
+
+        let entries = user_dict.dict.find_surface("東武スカイツリーライン");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, -10000);
+        assert_eq!(
+            user_dict
+                .word_details(entries[0].word_id.0 as usize)
+                .unwrap(),
+            vec![
+                "カスタム名詞",
+                "*",
+                "*",
+                "*",
+                "*",
+                "*",
+                "東武スカイツリーライン",
+                "トウブスカイツリーライン",
+                "*"
+            ]
+        );
+
+        let entries = user_dict.dict.find_surface("東京スカイツリー");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, -1000);
+        assert_eq!(entries[0].left_id, 1288);
+    }
+
+    #[test]
+    fn test_build_is_reproducible() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let tmp_dir = tmp_dir.path();
+
+        let rows = [
+            "東京,1,1,100,名詞",
+            "京都,1,1,200,名詞",
+            "東京,2,2,400,動詞",
+            "東,2,2,300,動詞",
+            "東京,1,1,100,動詞",
+            "大阪,1,1,500,名詞",
+        ];
+        let reversed = rows.iter().rev().copied().collect::<Vec<&str>>();
+
+        // The same rows, in a different order and split across files differently
+        let sources = [
+            ("first", vec![("a.csv", &rows[..3]), ("b.csv", &rows[3..])]),
+            (
+                "second",
+                vec![("x.csv", &reversed[..2]), ("y.csv", &reversed[2..])],
+            ),
+        ];
+        for (name, lexicon_files) in &sources {
+            let input_dir = tmp_dir.join(name);
+            fs::create_dir_all(&input_dir).unwrap();
+            fs::write(
+                input_dir.join("char.def"),
+                "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+            )
+            .unwrap();
+            fs::write(
+                input_dir.join("unk.def"),
+                "DEFAULT,0,0,1000,記号\nKANJI,1,1,2000,名詞\n",
+            )
+            .unwrap();
+            fs::write(
+                input_dir.join("matrix.def"),
+                "3 3\n0 0 0\n0 1 10\n0 2 20\n1 0 30\n1 1 40\n1 2 50\n2 0 60\n2 1 70\n2 2 80\n",
+            )
+            .unwrap();
+            let id_def = "0 BOS/EOS\n1 名詞\n2 動詞\n";
+            fs::write(input_dir.join("left-id.def"), id_def).unwrap();
+            fs::write(input_dir.join("right-id.def"), id_def).unwrap();
+            for (file_name, rows) in lexicon_files {
+                fs::write(input_dir.join(file_name), rows.join("\n")).unwrap();
+            }
+        }
+
+        for schema in [
+            r#"{"fields": ["pos"]}"#,
+            r#"{"fields": ["pos"], "normalize_details": true}"#,
+        ] {
+            let builder = SchemaDictionaryBuilder::new(
+                DictionarySchema::from_slice(schema.as_bytes()).unwrap(),
+            );
+            let mut outputs = Vec::new();
+            for (index, (name, _)) in [&sources[0], &sources[0], &sources[1]].iter().enumerate() {
+                let output_dir = tmp_dir.join(format!("output-{}", index));
+                let _ = fs::remove_dir_all(&output_dir);
+                builder
+                    .build_dictionary(&tmp_dir.join(name), &output_dir)
+                    .unwrap();
+                outputs.push(read_files(&output_dir));
+            }
+
+            assert_eq!(
+                outputs[0]
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<&str>>(),
+                vec![
+                    "char_def.bin",
+                    "dict.da",
+                    "dict.vals",
+                    "dict.words",
+                    "dict.wordsidx",
+                    "id_def.bin",
+                    "matrix.mtx",
+                    "unk.bin"
+                ]
+            );
+            assert_eq!(outputs[0], outputs[1]);
+            assert_eq!(outputs[0], outputs[2]);
+        }
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        // The number of simple details does not match the number of fields.
+        assert!(DictionarySchema::from_slice(
+            br#"{"fields": ["pos", "reading"], "user_dictionary": {"simple_details": ["$pos"]}}"#
+        )
+        .is_err());
+        // Unknown simple field.
+        assert!(DictionarySchema::from_slice(
+            br#"{"fields": ["pos"], "user_dictionary": {"simple_details": ["$base"]}}"#
+        )
+        .is_err());
+        assert!(DictionarySchema::from_slice(br#"{"fields": ["pos"]}"#).is_ok());
+    }
+}
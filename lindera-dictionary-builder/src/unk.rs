@@ -6,7 +6,7 @@ use std::path::Path;
 
 use derive_builder::Builder;
 use lindera_core::character_definition::CharacterDefinitions;
-use lindera_core::error::LinderaErrorKind;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
 use lindera_core::unknown_dictionary::parse_unk;
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
@@ -36,7 +36,8 @@ impl UnkBuilder {
         let unk_data_path = input_dir.join("unk.def");
         debug!("reading {:?}", unk_data_path);
         let unk_data = read_file_with_encoding(&unk_data_path, &self.encoding)?;
-        let unknown_dictionary = parse_unk(chardef.categories(), &unk_data, self.unk_fields_num)?;
+        let unknown_dictionary = parse_unk(chardef.categories(), &unk_data, self.unk_fields_num)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(&unk_data_path)))?;
 
         let mut unk_buffer = Vec::new();
         bincode::serialize_into(&mut unk_buffer, &unknown_dictionary)
@@ -2,21 +2,29 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
 use std::io;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::Path;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use csv::StringRecord;
 use derive_builder::Builder;
 use lindera_core::dictionary::UserDictionary;
-use lindera_core::error::LinderaErrorKind;
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+use lindera_core::id_definition::IdDefinitions;
 use lindera_core::prefix_dict::PrefixDict;
+use lindera_core::user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat};
 use lindera_core::word_entry::{WordEntry, WordId};
 use lindera_core::LinderaResult;
-use log::debug;
+use log::{debug, warn};
 use yada::builder::DoubleArrayBuilder;
 use yada::DoubleArray;
 
+use crate::estimator::CostEstimator;
+use crate::user_dict_source::read_entries;
+use crate::utils::record_location;
+
+type StringRecordProcessor = Box<dyn Fn(&StringRecord) -> LinderaResult<Vec<String>>>;
+
 #[derive(Builder)]
 #[builder(pattern = "owned")]
 #[builder(name = "UserDictBuilderOptions")]
@@ -32,94 +40,201 @@ pub struct UserDictBuilder {
     simple_context_id: u16,
     #[builder(default = "true")]
     flexible_csv: bool,
+    /* The names of the fields of simple entries, used to make simple entries from named fields. */
+    #[builder(
+        default = "vec![\"surface\".to_string(), \"pos\".to_string(), \"reading\".to_string()]"
+    )]
+    simple_userdic_field_names: Vec<String>,
+    #[builder(setter(strip_option), default = "None")]
+    simple_userdic_details_handler: Option<StringRecordProcessor>,
+    /* If set, the context ids and the word cost of simple entries are estimated from the system dictionary. */
     #[builder(setter(strip_option), default = "None")]
-    simple_userdic_details_handler:
-        Option<Box<dyn Fn(&StringRecord) -> LinderaResult<Vec<String>>>>,
+    simple_userdic_estimator: Option<CostEstimator>,
+    /* If set, context ids are assigned from the features of entries that do not specify them. */
+    #[builder(setter(strip_option), default = "None")]
+    id_definitions: Option<IdDefinitions>,
+    /* If unset, the details of the words are not stored, for tokenizers that only segment the text. */
+    #[builder(default = "true")]
+    details: bool,
+}
+
+/// A user dictionary word with its cost, context ids and details resolved.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+struct Word {
+    surface: String,
+    left_id: u16,
+    right_id: u16,
+    word_cost: i16,
+    details: Vec<String>,
 }
 
 impl UserDictBuilder {
+    /// Build the user dictionary from a source file, of which the format is detected from the
+    /// extension. Files with an unknown extension are read as CSV.
     pub fn build(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
+        let format =
+            UserDictionaryFormat::from_path(input_file).unwrap_or(UserDictionaryFormat::Csv);
+        self.build_with_format(input_file, format)
+    }
+
+    /// Build the user dictionary from a source file in the given format.
+    pub fn build_with_format(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
         debug!("reading {:?}", input_file);
 
+        let file = File::open(input_file).map_err(|err| {
+            LinderaErrorKind::Io
+                .with_error(anyhow::anyhow!(err))
+                .add_location(ErrorLocation::new().with_path(input_file))
+        })?;
+        self.build_from_reader(file, format)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(input_file)))
+    }
+
+    /// Build the user dictionary from a source in the given format read from `reader`.
+    pub fn build_from_reader<R: Read>(
+        &self,
+        reader: R,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        match format {
+            UserDictionaryFormat::Csv => self.build_from_records(self.read_records(reader)?),
+            UserDictionaryFormat::Bin => Err(LinderaErrorKind::Args
+                .with_error(anyhow::anyhow!("the source is a built user dictionary"))),
+            _ => self.build_from_entries(read_entries(reader, format)?),
+        }
+    }
+
+    fn read_records<R: Read>(&self, reader: R) -> LinderaResult<Vec<StringRecord>> {
         let mut rdr = csv::ReaderBuilder::new()
             .has_headers(false)
             .flexible(self.flexible_csv)
-            .from_path(input_file)
-            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            .from_reader(reader);
 
         let mut rows: Vec<StringRecord> = vec![];
         for result in rdr.records() {
-            let record =
-                result.map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))?;
+            let record = result.map_err(|err| {
+                let mut location = ErrorLocation::new();
+                if let Some(position) = err.position() {
+                    location = location.with_line(position.line() as usize);
+                }
+                LinderaErrorKind::Content
+                    .with_error(anyhow::anyhow!(err))
+                    .add_location(location)
+            })?;
             rows.push(record);
         }
-        rows.sort_by_key(|row| row[0].to_string());
+        Ok(rows)
+    }
 
-        let mut word_entry_map: BTreeMap<String, Vec<WordEntry>> = BTreeMap::new();
+    /// Build the user dictionary from rows that are already read.
+    pub fn build_from_records(&self, rows: Vec<StringRecord>) -> LinderaResult<UserDictionary> {
+        let words = rows
+            .iter()
+            .map(|row| self.record_word(row))
+            .collect::<LinderaResult<Vec<Word>>>()?;
+        self.build_from_words(words)
+    }
 
-        for (row_id, row) in rows.iter().enumerate() {
-            let surface = row[0].to_string();
-            let word_cost = if row.len() == self.simple_userdic_fields_num {
-                self.simple_word_cost
-            } else {
-                row[3].parse::<i16>().map_err(|_err| {
-                    LinderaErrorKind::Parse.with_error(anyhow::anyhow!("failed to parse word cost"))
-                })?
-            };
-            let (left_id, right_id) = if row.len() == self.simple_userdic_fields_num {
-                (self.simple_context_id, self.simple_context_id)
-            } else {
-                (
-                    row[1].parse::<u16>().map_err(|_err| {
-                        LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse left context id"))
-                    })?,
-                    row[2].parse::<u16>().map_err(|_err| {
-                        LinderaErrorKind::Parse
-                            .with_error(anyhow::anyhow!("failed to parse left context id"))
-                    })?,
-                )
-            };
-
-            word_entry_map.entry(surface).or_default().push(WordEntry {
-                word_id: WordId(row_id as u32, false),
+    /// Build the user dictionary from entries with named fields.
+    pub fn build_from_entries<I>(&self, entries: I) -> LinderaResult<UserDictionary>
+    where
+        I: IntoIterator<Item = UserDictionaryEntry>,
+    {
+        let words = entries
+            .into_iter()
+            .map(|entry| self.entry_word(entry))
+            .collect::<LinderaResult<Vec<Word>>>()?;
+        self.build_from_words(words)
+    }
+
+    fn record_word(&self, row: &StringRecord) -> LinderaResult<Word> {
+        if row.len() == self.simple_userdic_fields_num {
+            let (word_cost, left_id, right_id) = self.simple_cost_and_context_ids(row)?;
+            Ok(Word {
+                surface: row[0].to_string(),
+                left_id,
+                right_id,
                 word_cost,
+                details: self.simple_details(row)?,
+            })
+        } else if row.len() >= self.detailed_userdic_fields_num {
+            let (word_cost, left_id, right_id) = self.detailed_cost_and_context_ids(row)?;
+            Ok(Word {
+                surface: row[0].to_string(),
                 left_id,
                 right_id,
-            });
-        }
-
-        let mut words_data = Vec::<u8>::new();
-        let mut words_idx_data = Vec::<u8>::new();
-        for row in rows.iter() {
-            let word_detail = if row.len() == self.simple_userdic_fields_num {
-                if let Some(handler) = &self.simple_userdic_details_handler {
-                    handler(row)?
-                } else {
-                    row.iter()
-                        .skip(1)
-                        .map(|s| s.to_string())
-                        .collect::<Vec<String>>()
-                }
-            } else if row.len() >= self.detailed_userdic_fields_num {
-                let mut tmp_word_detail = Vec::new();
-                for item in row.iter().skip(4) {
-                    tmp_word_detail.push(item.to_string());
-                }
-                tmp_word_detail
-            } else {
-                return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                word_cost,
+                details: row.iter().skip(4).map(|item| item.to_string()).collect(),
+            })
+        } else {
+            Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!(
                     "user dictionary should be a CSV with {} or {}+ fields",
                     self.simple_userdic_fields_num,
                     self.detailed_userdic_fields_num
-                )));
-            };
+                ))
+                .add_location(record_location(None, row)))
+        }
+    }
+
+    fn entry_word(&self, entry: UserDictionaryEntry) -> LinderaResult<Word> {
+        let (details, (word_cost, left_id, right_id)) = if entry.features.is_empty() {
+            // The details, the cost and the context ids of a simple entry with the same fields
+            let row = StringRecord::from(
+                self.simple_userdic_field_names
+                    .iter()
+                    .map(|name| entry.field(name).unwrap_or_else(|| "*".to_string()))
+                    .collect::<Vec<String>>(),
+            );
+            (
+                self.simple_details(&row)?,
+                self.simple_cost_and_context_ids(&row)?,
+            )
+        } else {
+            let defaults = self.features_cost_and_context_ids(&entry.surface, &entry.features);
+            (entry.features, defaults)
+        };
 
+        Ok(Word {
+            surface: entry.surface,
+            left_id: entry.left_id.unwrap_or(left_id),
+            right_id: entry.right_id.unwrap_or(right_id),
+            word_cost: entry.cost.unwrap_or(word_cost),
+            details,
+        })
+    }
+
+    fn build_from_words(&self, mut words: Vec<Word>) -> LinderaResult<UserDictionary> {
+        // Sort by surface, and then by all the fields, so that the word ids do not depend on the
+        // order of the rows.
+        words.sort();
+
+        let mut word_entry_map: BTreeMap<String, Vec<WordEntry>> = BTreeMap::new();
+
+        for (row_id, word) in words.iter().enumerate() {
+            word_entry_map
+                .entry(word.surface.clone())
+                .or_default()
+                .push(WordEntry {
+                    word_id: WordId(row_id as u32, false),
+                    word_cost: word.word_cost,
+                    left_id: word.left_id,
+                    right_id: word.right_id,
+                });
+        }
+
+        let mut words_data = Vec::<u8>::new();
+        let mut words_idx_data = Vec::<u8>::new();
+        for word in words.iter().filter(|_| self.details) {
             let offset = words_data.len();
             words_idx_data
                 .write_u32::<LittleEndian>(offset as u32)
                 .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
-            bincode::serialize_into(&mut words_data, &word_detail)
+            bincode::serialize_into(&mut words_data, &word.details)
                 .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
         }
 
@@ -159,6 +274,105 @@ impl UserDictBuilder {
             words_data,
         })
     }
+
+    fn simple_details(&self, row: &StringRecord) -> LinderaResult<Vec<String>> {
+        if let Some(handler) = &self.simple_userdic_details_handler {
+            handler(row)
+        } else {
+            Ok(row
+                .iter()
+                .skip(1)
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>())
+        }
+    }
+
+    fn simple_cost_and_context_ids(&self, row: &StringRecord) -> LinderaResult<(i16, u16, u16)> {
+        let details = self.simple_details(row)?;
+        let context_ids = self
+            .id_definitions
+            .as_ref()
+            .and_then(|id_definitions| id_definitions.context_ids(&details));
+
+        if let Some(estimator) = &self.simple_userdic_estimator {
+            if let Some(estimated) = estimator.estimate_word(&row[0], &details, context_ids) {
+                return Ok((estimated.word_cost, estimated.left_id, estimated.right_id));
+            }
+            warn!("failed to estimate cost, use default cost: {:?}", row);
+        }
+
+        let (left_id, right_id) =
+            context_ids.unwrap_or((self.simple_context_id, self.simple_context_id));
+        Ok((self.simple_word_cost, left_id, right_id))
+    }
+
+    /// The cost and the context ids of an entry with features that does not give them.
+    fn features_cost_and_context_ids(&self, surface: &str, features: &[String]) -> (i16, u16, u16) {
+        let (left_id, right_id) = self
+            .id_definitions
+            .as_ref()
+            .and_then(|id_definitions| id_definitions.context_ids(features))
+            .unwrap_or((self.simple_context_id, self.simple_context_id));
+        let word_cost = self
+            .simple_userdic_estimator
+            .as_ref()
+            .and_then(|estimator| {
+                estimator.estimate_word(surface, features, Some((left_id, right_id)))
+            })
+            .map(|estimated| estimated.word_cost)
+            .unwrap_or(self.simple_word_cost);
+        (word_cost, left_id, right_id)
+    }
+
+    fn detailed_cost_and_context_ids(&self, row: &StringRecord) -> LinderaResult<(i16, u16, u16)> {
+        let details = row.iter().skip(4).collect::<Vec<&str>>();
+
+        // Context ids can be omitted if they can be assigned from the features.
+        let (left_id, right_id) = match &self.id_definitions {
+            Some(id_definitions) if row[1].trim().is_empty() && row[2].trim().is_empty() => {
+                id_definitions.context_ids(&details).ok_or_else(|| {
+                    LinderaErrorKind::Content
+                        .with_error(anyhow::anyhow!(
+                            "failed to assign context ids from features: {:?}",
+                            row
+                        ))
+                        .add_location(record_location(None, row))
+                })?
+            }
+            _ => (
+                row[1].parse::<u16>().map_err(|_err| {
+                    LinderaErrorKind::Parse
+                        .with_error(anyhow::anyhow!("failed to parse left context id"))
+                        .add_location(record_location(None, row).with_field(1, &row[1]))
+                })?,
+                row[2].parse::<u16>().map_err(|_err| {
+                    LinderaErrorKind::Parse
+                        .with_error(anyhow::anyhow!("failed to parse right context id"))
+                        .add_location(record_location(None, row).with_field(2, &row[2]))
+                })?,
+            ),
+        };
+
+        // The word cost can be omitted if it can be estimated.
+        let word_cost = match &self.simple_userdic_estimator {
+            Some(estimator) if row[3].trim().is_empty() => {
+                match estimator.estimate_word(&row[0], &details, Some((left_id, right_id))) {
+                    Some(estimated) => estimated.word_cost,
+                    None => {
+                        warn!("failed to estimate cost, use default cost: {:?}", row);
+                        self.simple_word_cost
+                    }
+                }
+            }
+            _ => row[3].parse::<i16>().map_err(|_err| {
+                LinderaErrorKind::Parse
+                    .with_error(anyhow::anyhow!("failed to parse word cost"))
+                    .add_location(record_location(None, row).with_field(3, &row[3]))
+            })?,
+        };
+
+        Ok((word_cost, left_id, right_id))
+    }
 }
 
 pub fn build_user_dictionary(user_dict: UserDictionary, output_file: &Path) -> LinderaResult<()> {
@@ -184,3 +398,169 @@ pub fn build_user_dictionary(user_dict: UserDictionary, output_file: &Path) -> L
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+    use lindera_core::user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat};
+
+    use crate::user_dict::UserDictBuilderOptions;
+
+    #[test]
+    fn test_invalid_word_cost_location() {
+        let input_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("ipadic_userdic_invalid_word_cost.csv");
+
+        let err = UserDictBuilderOptions::default()
+            .detailed_userdic_fields_num(13)
+            .builder()
+            .unwrap()
+            .build(&input_file)
+            .err()
+            .unwrap();
+
+        assert_eq!(err.kind(), LinderaErrorKind::Parse);
+        assert_eq!(
+            err.location(),
+            Some(
+                &ErrorLocation::new()
+                    .with_path(&input_file)
+                    .with_line(1)
+                    .with_field(3, "hoge")
+            )
+        );
+    }
+
+    #[test]
+    fn test_insufficient_number_of_fields_location() {
+        let input_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../resources")
+            .join("ipadic_userdic_insufficient_number_of_fields.csv");
+
+        let err = UserDictBuilderOptions::default()
+            .detailed_userdic_fields_num(13)
+            .builder()
+            .unwrap()
+            .build(&input_file)
+            .err()
+            .unwrap();
+
+        assert_eq!(err.kind(), LinderaErrorKind::Content);
+        assert_eq!(
+            err.location(),
+            Some(&ErrorLocation::new().with_path(&input_file).with_line(1))
+        );
+    }
+
+    #[test]
+    fn test_build_with_format() {
+        let temp_dir = std::env::temp_dir().join("lindera-user-dict-format-test");
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let jsonl_file = temp_dir.join("userdic.jsonl");
+        fs::write(
+            &jsonl_file,
+            "{\"surface\": \"東京スカイツリー\", \"pos\": \"カスタム名詞\", \"reading\": \"トウキョウスカイツリー\"}\n\
+             {\"surface\": \"東武スカイツリーライン\", \"left_id\": 1288, \"right_id\": 1288, \"cost\": -1000, \"features\": [\"名詞\", \"固有名詞\"]}\n",
+        )
+        .unwrap();
+        // The same entries as TSV, but the extension does not tell the format.
+        let tsv_file = temp_dir.join("userdic.txt");
+        fs::write(
+            &tsv_file,
+            "surface\tpos\treading\n東京スカイツリー\tカスタム名詞\tトウキョウスカイツリー\n",
+        )
+        .unwrap();
+
+        let builder = UserDictBuilderOptions::default().builder().unwrap();
+
+        let user_dict = builder.build(&jsonl_file).unwrap();
+        let entries = user_dict.dict.find_surface("東京スカイツリー");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].word_cost, -10000);
+        assert_eq!(
+            user_dict.word_details(entries[0].word_id.0 as usize),
+            Some(vec![
+                "カスタム名詞".to_string(),
+                "トウキョウスカイツリー".to_string()
+            ])
+        );
+        let entries = user_dict.dict.find_surface("東武スカイツリーライン");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            (
+                entries[0].left_id,
+                entries[0].right_id,
+                entries[0].word_cost
+            ),
+            (1288, 1288, -1000)
+        );
+        assert_eq!(
+            user_dict.word_details(entries[0].word_id.0 as usize),
+            Some(vec!["名詞".to_string(), "固有名詞".to_string()])
+        );
+
+        let user_dict = builder
+            .build_with_format(&tsv_file, UserDictionaryFormat::Tsv)
+            .unwrap();
+        let entries = user_dict.dict.find_surface("東京スカイツリー");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            user_dict.word_details(entries[0].word_id.0 as usize),
+            Some(vec![
+                "カスタム名詞".to_string(),
+                "トウキョウスカイツリー".to_string()
+            ])
+        );
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_build_from_entries_and_reader() {
+        let builder = UserDictBuilderOptions::default().builder().unwrap();
+
+        let from_entries = builder
+            .build_from_entries(vec![
+                UserDictionaryEntry {
+                    pos: Some("カスタム名詞".to_string()),
+                    reading: Some("トウキョウスカイツリー".to_string()),
+                    ..UserDictionaryEntry::new("東京スカイツリー")
+                },
+                UserDictionaryEntry {
+                    pos: Some("カスタム名詞".to_string()),
+                    reading: Some("トウブスカイツリーライン".to_string()),
+                    ..UserDictionaryEntry::new("東武スカイツリーライン")
+                },
+            ])
+            .unwrap();
+        // The same entries in CSV, in a different order.
+        let csv = "東武スカイツリーライン,カスタム名詞,トウブスカイツリーライン\n\
+                   東京スカイツリー,カスタム名詞,トウキョウスカイツリー\n";
+        let from_reader = builder
+            .build_from_reader(csv.as_bytes(), UserDictionaryFormat::Csv)
+            .unwrap();
+
+        for surface in ["東京スカイツリー", "東武スカイツリーライン"] {
+            let entries = from_entries.dict.find_surface(surface);
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries, from_reader.dict.find_surface(surface));
+            assert_eq!(
+                from_entries.word_details(entries[0].word_id.0 as usize),
+                from_reader.word_details(entries[0].word_id.0 as usize)
+            );
+        }
+        assert_eq!(from_entries.words_data, from_reader.words_data);
+
+        let err = builder
+            .build_from_reader("東京スカイツリー\n".as_bytes(), UserDictionaryFormat::Csv)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), LinderaErrorKind::Content);
+        assert_eq!(err.location(), Some(&ErrorLocation::new().with_line(1)));
+    }
+}
@@ -0,0 +1,186 @@
+use std::io::{BufRead, BufReader, Read};
+
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+use lindera_core::user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat};
+use lindera_core::LinderaResult;
+
+/// Read the entries of a user dictionary source in the TSV, JSON or JSONL format.
+/// CSV sources are read as records by `UserDictBuilder` instead, because their layout depends on
+/// the dictionary.
+pub fn read_entries<R: Read>(
+    reader: R,
+    format: UserDictionaryFormat,
+) -> LinderaResult<Vec<UserDictionaryEntry>> {
+    match format {
+        UserDictionaryFormat::Tsv => read_tsv_entries(reader),
+        UserDictionaryFormat::Json => read_json_entries(reader),
+        UserDictionaryFormat::Jsonl => read_jsonl_entries(reader),
+        UserDictionaryFormat::Csv | UserDictionaryFormat::Bin => Err(LinderaErrorKind::Args
+            .with_error(anyhow::anyhow!(
+                "{:?} user dictionaries have no named fields",
+                format
+            ))),
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(
+    value: &str,
+    line: usize,
+    field: usize,
+) -> LinderaResult<Option<T>> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value.parse::<T>().map(Some).map_err(|_err| {
+        LinderaErrorKind::Parse
+            .with_error(anyhow::anyhow!("failed to parse {:?}", value))
+            .add_location(
+                ErrorLocation::new()
+                    .with_line(line)
+                    .with_field(field, value),
+            )
+    })
+}
+
+/// The first row names the columns. The columns that are not fields of `UserDictionaryEntry` are
+/// the features, in the order of the columns.
+fn read_tsv_entries<R: Read>(reader: R) -> LinderaResult<Vec<UserDictionaryEntry>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(b'\t')
+        .quoting(false)
+        .has_headers(true)
+        .from_reader(reader);
+
+    let csv_error = |err: csv::Error| {
+        let mut location = ErrorLocation::new();
+        if let Some(position) = err.position() {
+            location = location.with_line(position.line() as usize);
+        }
+        LinderaErrorKind::Content
+            .with_error(anyhow::anyhow!(err))
+            .add_location(location)
+    };
+
+    let headers = rdr.headers().map_err(csv_error)?.clone();
+    if !headers.iter().any(|header| header == "surface") {
+        return Err(LinderaErrorKind::Content
+            .with_error(anyhow::anyhow!("TSV user dictionary has no surface column"))
+            .add_location(ErrorLocation::new().with_line(1)));
+    }
+
+    let mut entries = Vec::new();
+    for result in rdr.records() {
+        let record = result.map_err(csv_error)?;
+        let line = record
+            .position()
+            .map(|position| position.line() as usize)
+            .unwrap_or_default();
+
+        let mut entry = UserDictionaryEntry::default();
+        for (field, (header, value)) in headers.iter().zip(record.iter()).enumerate() {
+            match header {
+                "surface" => entry.surface = value.to_string(),
+                "pos" => entry.pos = Some(value.to_string()).filter(|pos| !pos.is_empty()),
+                "reading" => {
+                    entry.reading = Some(value.to_string()).filter(|reading| !reading.is_empty())
+                }
+                "cost" | "word_cost" => entry.cost = parse_field(value, line, field)?,
+                "left_id" => entry.left_id = parse_field(value, line, field)?,
+                "right_id" => entry.right_id = parse_field(value, line, field)?,
+                _ => entry.features.push(value.to_string()),
+            }
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn json_error(err: serde_json::Error, line_offset: usize) -> lindera_core::error::LinderaError {
+    let location = ErrorLocation::new()
+        .with_line(line_offset + err.line())
+        .with_column(err.column());
+    LinderaErrorKind::Deserialize
+        .with_error(anyhow::anyhow!(err))
+        .add_location(location)
+}
+
+fn read_json_entries<R: Read>(reader: R) -> LinderaResult<Vec<UserDictionaryEntry>> {
+    serde_json::from_reader(reader).map_err(|err| json_error(err, 0))
+}
+
+/// Blank lines are skipped.
+fn read_jsonl_entries<R: Read>(reader: R) -> LinderaResult<Vec<UserDictionaryEntry>> {
+    let mut entries = Vec::new();
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line.map_err(|err| {
+            LinderaErrorKind::Io
+                .with_error(anyhow::anyhow!(err))
+                .add_location(ErrorLocation::new().with_line(i + 1))
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line).map_err(|err| json_error(err, i))?);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+    use lindera_core::user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat};
+
+    use crate::user_dict_source::read_entries;
+
+    #[test]
+    fn test_read_tsv_entries() {
+        let tsv = "surface\tleft_id\tright_id\tcost\tpos\treading\n\
+                   東京スカイツリー\t1288\t1288\t-1000\t名詞\tトウキョウ,スカイツリー\n\
+                   とうきょうスカイツリー駅\t\t\t\t\t\n";
+        let entries = read_entries(tsv.as_bytes(), UserDictionaryFormat::Tsv).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                UserDictionaryEntry {
+                    pos: Some("名詞".to_string()),
+                    reading: Some("トウキョウ,スカイツリー".to_string()),
+                    cost: Some(-1000),
+                    left_id: Some(1288),
+                    right_id: Some(1288),
+                    ..UserDictionaryEntry::new("東京スカイツリー")
+                },
+                UserDictionaryEntry::new("とうきょうスカイツリー駅"),
+            ]
+        );
+
+        let err = read_entries(
+            "surface\tcost\n東京\thoge\n".as_bytes(),
+            UserDictionaryFormat::Tsv,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), LinderaErrorKind::Parse);
+        assert_eq!(
+            err.location(),
+            Some(&ErrorLocation::new().with_line(2).with_field(1, "hoge"))
+        );
+    }
+
+    #[test]
+    fn test_read_jsonl_entries() {
+        let jsonl = "{\"surface\": \"東京スカイツリー\", \"pos\": \"カスタム名詞\"}\n\
+                     \n\
+                     {\"surface\": \"東武スカイツリーライン\", \"features\": [\"名詞\", \"固有名詞\"]}\n";
+        let entries = read_entries(jsonl.as_bytes(), UserDictionaryFormat::Jsonl).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].pos, Some("カスタム名詞".to_string()));
+        assert_eq!(entries[1].features, vec!["名詞", "固有名詞"]);
+
+        let err = read_entries(
+            "{\"surface\": \"東京\"}\n{\"surface\": 1}\n".as_bytes(),
+            UserDictionaryFormat::Jsonl,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), LinderaErrorKind::Deserialize);
+        assert_eq!(err.location().unwrap().line, Some(2));
+    }
+}
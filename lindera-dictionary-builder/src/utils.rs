@@ -1,11 +1,13 @@
-use std::io::Write;
+use std::fs::File;
+use std::io::{self, BufReader, Write};
 use std::path::Path;
 
 use anyhow::anyhow;
+use csv::StringRecord;
 use encoding_rs::Encoding;
 #[cfg(feature = "compress")]
-use lindera_compress::compress;
-use lindera_core::error::LinderaErrorKind;
+use lindera_compress::{compress, compress_stream};
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
 use lindera_core::file_util::read_file;
 use lindera_core::LinderaResult;
 use lindera_decompress::Algorithm;
@@ -37,12 +39,148 @@ pub fn compress_write<W: Write>(
     Ok(())
 }
 
+/// Write the content of the file at `path` like [`compress_write`], without holding the content
+/// in memory.
+#[cfg(feature = "compress")]
+pub fn compress_write_file<W: Write>(
+    path: &Path,
+    algorithm: Algorithm,
+    writer: &mut W,
+) -> LinderaResult<()> {
+    // The compressed data is prefixed with its length, so it is written to a temporary file first
+    let compressed_path = path.with_extension("compressed");
+    let result = (|| {
+        let mut reader = BufReader::new(
+            File::open(path).map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?,
+        );
+        let compressed_file = compress_stream(
+            &mut reader,
+            algorithm,
+            io::BufWriter::new(
+                File::create(&compressed_path)
+                    .map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?,
+            ),
+        )
+        .map_err(|err| LinderaErrorKind::Compress.with_error(err))?;
+        compressed_file
+            .into_inner()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err.into_error())))?;
+
+        let compressed_len = std::fs::metadata(&compressed_path)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?
+            .len();
+        // The same layout as `CompressedData` serialized by bincode
+        bincode::serialize_into(&mut *writer, &(algorithm, compressed_len))
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?;
+        io::copy(
+            &mut File::open(&compressed_path)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?,
+            writer,
+        )
+        .map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?;
+        Ok(())
+    })();
+    let _ = std::fs::remove_file(&compressed_path);
+    result
+}
+
+/// Write the content of the file at `path` like [`compress_write`], without holding the content
+/// in memory.
+#[cfg(not(feature = "compress"))]
+pub fn compress_write_file<W: Write>(
+    path: &Path,
+    _algorithm: Algorithm,
+    writer: &mut W,
+) -> LinderaResult<()> {
+    let mut reader = BufReader::new(
+        File::open(path).map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?,
+    );
+    io::copy(&mut reader, writer).map_err(|err| LinderaErrorKind::Io.with_error(anyhow!(err)))?;
+
+    Ok(())
+}
+
 pub fn read_file_with_encoding(filepath: &Path, encoding_name: &str) -> LinderaResult<String> {
+    let buffer = read_file(filepath)?;
+    decode_with_encoding(&buffer, encoding_name)
+}
+
+pub fn decode_with_encoding(buffer: &[u8], encoding_name: &str) -> LinderaResult<String> {
+    let encoding = Encoding::for_label_no_replacement(encoding_name.as_bytes());
+    let encoding = encoding.ok_or_else(|| {
+        LinderaErrorKind::Decode.with_error(anyhow!("Invalid encoding: {}", encoding_name))
+    })?;
+
+    Ok(encoding.decode(buffer).0.into_owned())
+}
+
+pub fn write_file_with_encoding(
+    filepath: &Path,
+    content: &str,
+    encoding_name: &str,
+) -> LinderaResult<()> {
     let encoding = Encoding::for_label_no_replacement(encoding_name.as_bytes());
     let encoding = encoding.ok_or_else(|| {
         LinderaErrorKind::Decode.with_error(anyhow!("Invalid encoding: {}", encoding_name))
     })?;
 
-    let buffer = read_file(&filepath)?;
-    Ok(encoding.decode(&buffer).0.into_owned())
+    let (buffer, _, had_errors) = encoding.encode(content);
+    if had_errors {
+        return Err(LinderaErrorKind::Content
+            .with_error(anyhow!("Failed to encode to {}", encoding_name))
+            .add_location(ErrorLocation::new().with_path(filepath)));
+    }
+    std::fs::write(filepath, buffer).map_err(|err| {
+        LinderaErrorKind::Io
+            .with_error(anyhow!(err))
+            .add_location(ErrorLocation::new().with_path(filepath))
+    })
+}
+
+/// Returns the location of a CSV record, with the path of the file if known.
+pub fn record_location(path: Option<&Path>, record: &StringRecord) -> ErrorLocation {
+    let mut location = ErrorLocation::new();
+    if let Some(path) = path {
+        location = location.with_path(path);
+    }
+    if let Some(position) = record.position() {
+        location = location.with_line(position.line() as usize);
+    }
+    location
+}
+
+/// Returns the location of a CSV error, with the path of the file.
+pub fn csv_error_location(path: &Path, err: &csv::Error) -> ErrorLocation {
+    let location = ErrorLocation::new().with_path(path);
+    match err.position() {
+        Some(position) => location.with_line(position.line() as usize),
+        None => location,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use lindera_decompress::Algorithm;
+
+    use crate::utils::{compress_write, compress_write_file};
+
+    #[test]
+    fn test_compress_write_file() {
+        let tmp_dir = std::env::temp_dir().join("lindera-dictionary-builder-utils");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        fs::create_dir_all(&tmp_dir).unwrap();
+        let path = tmp_dir.join("data");
+        let data = (0..100000).map(|i| (i % 7) as u8).collect::<Vec<u8>>();
+        fs::write(&path, &data).unwrap();
+
+        let mut expected = Vec::new();
+        compress_write(&data, Algorithm::Deflate, &mut expected).unwrap();
+        let mut actual = Vec::new();
+        compress_write_file(&path, Algorithm::Deflate, &mut actual).unwrap();
+        assert_eq!(actual, expected);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
 }
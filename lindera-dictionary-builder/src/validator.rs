@@ -0,0 +1,625 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use derive_builder::Builder;
+use encoding_rs::{Encoding, UTF_8};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use glob::glob;
+use log::debug;
+
+use lindera_core::character_definition::CharacterDefinitionsBuilder;
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::validation::{ValidationIssueKind, ValidationReport};
+use lindera_core::LinderaResult;
+
+use crate::utils::read_file_with_encoding;
+
+/// The maximum number of entries with the same surface.
+/// The number of entries is stored in the lower 5 bits of the prefix dictionary values.
+pub const MAX_HOMOGRAPHS: usize = 31;
+
+/// Validates a dictionary source and reports all the problems with their locations
+/// instead of failing on the first one.
+#[derive(Builder, Debug)]
+#[builder(name = "DictionaryValidatorOptions")]
+#[builder(build_fn(name = "builder"))]
+pub struct DictionaryValidator {
+    /* If set to UTF-8, it can also read UTF-16 files with BOM. */
+    #[builder(default = "\"UTF-8\".into()", setter(into))]
+    encoding: Cow<'static, str>,
+    /// The number of fields of a lexicon row, including the surface, the context ids and the cost.
+    #[builder(default = "13")]
+    fields_num: usize,
+    /// If set, lexicon rows may have any number of fields from 4.
+    #[builder(default = "false")]
+    flexible_csv: bool,
+    #[builder(default = "11")]
+    unk_fields_num: usize,
+    #[builder(default = "false")]
+    normalize_details: bool,
+    /// If set, rows with a left context id of -1 are not indexed (e.g. SudachiDict)
+    /// and their context ids are not checked.
+    #[builder(default = "false")]
+    allow_unindexed_entries: bool,
+}
+
+/// The dimensions of `matrix.def`.
+struct MatrixSize {
+    forward_size: i64,
+    backward_size: i64,
+}
+
+impl DictionaryValidator {
+    pub fn validate(&self, input_dir: &Path) -> LinderaResult<ValidationReport> {
+        let mut report = ValidationReport::new();
+
+        let matrix_size = self.validate_cost_matrix(&input_dir.join("matrix.def"), &mut report)?;
+        let categories = self.validate_chardef(&input_dir.join("char.def"), &mut report)?;
+        self.validate_unk(
+            &input_dir.join("unk.def"),
+            categories.as_ref(),
+            matrix_size.as_ref(),
+            &mut report,
+        )?;
+        self.validate_lexicon(input_dir, matrix_size.as_ref(), &mut report)?;
+
+        Ok(report)
+    }
+
+    fn validate_cost_matrix(
+        &self,
+        path: &Path,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<Option<MatrixSize>> {
+        debug!("validating {:?}", path);
+        let Some(content) = self.read(path, report)? else {
+            return Ok(None);
+        };
+
+        let mut lines = content.lines().enumerate();
+        let matrix_size = match lines.next().map(|(_, line)| parse_integers(line)) {
+            Some(Some(header)) if header.len() == 2 && header.iter().all(|size| *size >= 0) => {
+                MatrixSize {
+                    forward_size: header[0],
+                    backward_size: header[1],
+                }
+            }
+            _ => {
+                report.push(
+                    ValidationIssueKind::Parse,
+                    path,
+                    Some(1),
+                    "invalid header, expected the forward size and the backward size",
+                );
+                return Ok(None);
+            }
+        };
+
+        for (index, line) in lines {
+            let line_number = Some(index + 1);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some(fields) = parse_integers(line) else {
+                report.push(
+                    ValidationIssueKind::Parse,
+                    path,
+                    line_number,
+                    format!("invalid line: {}", line),
+                );
+                continue;
+            };
+            if fields.len() != 3 {
+                report.push(
+                    ValidationIssueKind::FieldCount,
+                    path,
+                    line_number,
+                    format!("expected 3 fields, got {}", fields.len()),
+                );
+                continue;
+            }
+            check_context_id(
+                "forward id",
+                fields[0],
+                matrix_size.forward_size,
+                path,
+                line_number,
+                report,
+            );
+            check_context_id(
+                "backward id",
+                fields[1],
+                matrix_size.backward_size,
+                path,
+                line_number,
+                report,
+            );
+            check_cost(fields[2], path, line_number, report);
+        }
+
+        Ok(Some(matrix_size))
+    }
+
+    fn validate_chardef(
+        &self,
+        path: &Path,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<Option<HashSet<String>>> {
+        debug!("validating {:?}", path);
+        let Some(content) = self.read(path, report)? else {
+            return Ok(None);
+        };
+
+        let mut builder = CharacterDefinitionsBuilder::default();
+        let mut categories = HashSet::new();
+        let mut ranges = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line_number = Some(index + 1);
+            if let Err(err) = builder.parse(line) {
+                report.push(
+                    ValidationIssueKind::Parse,
+                    path,
+                    line_number,
+                    err.to_string(),
+                );
+                continue;
+            }
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                Some(field) if field.starts_with("0x") => {
+                    ranges.push((line_number, fields.map(str::to_string).collect::<Vec<_>>()));
+                }
+                Some(field) => {
+                    categories.insert(field.to_string());
+                }
+                None => {}
+            }
+        }
+
+        for (line_number, range_categories) in ranges {
+            for category in range_categories {
+                if !categories.contains(&category) {
+                    report.push(
+                        ValidationIssueKind::UndefinedCategory,
+                        path,
+                        line_number,
+                        format!("undefined category: {}", category),
+                    );
+                }
+            }
+        }
+
+        Ok(Some(categories))
+    }
+
+    fn validate_unk(
+        &self,
+        path: &Path,
+        categories: Option<&HashSet<String>>,
+        matrix_size: Option<&MatrixSize>,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<()> {
+        debug!("validating {:?}", path);
+        let Some(content) = self.read(path, report)? else {
+            return Ok(());
+        };
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = Some(index + 1);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != self.unk_fields_num {
+                report.push(
+                    ValidationIssueKind::FieldCount,
+                    path,
+                    line_number,
+                    format!(
+                        "expected {} fields, got {}",
+                        self.unk_fields_num,
+                        fields.len()
+                    ),
+                );
+                if fields.len() < 4 {
+                    continue;
+                }
+            }
+            if let Some(categories) = categories {
+                if !categories.contains(fields[0]) {
+                    report.push(
+                        ValidationIssueKind::UndefinedCategory,
+                        path,
+                        line_number,
+                        format!("category is not defined in char.def: {}", fields[0]),
+                    );
+                }
+            }
+            check_entry(&fields[1..4], matrix_size, path, line_number, report);
+        }
+
+        Ok(())
+    }
+
+    fn validate_lexicon(
+        &self,
+        input_dir: &Path,
+        matrix_size: Option<&MatrixSize>,
+        report: &mut ValidationReport,
+    ) -> LinderaResult<()> {
+        let pattern = if let Some(path) = input_dir.to_str() {
+            format!("{}/*.csv", path)
+        } else {
+            return Err(
+                LinderaErrorKind::Io.with_error(anyhow::anyhow!("Failed to convert path to &str."))
+            );
+        };
+
+        let mut filenames: Vec<PathBuf> = Vec::new();
+        for entry in
+            glob(&pattern).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
+        {
+            let path = entry.map_err(|err| LinderaErrorKind::Content.with_error(anyhow!(err)))?;
+            filenames.push(path);
+        }
+        filenames.sort();
+
+        let encoding = self.encoding()?;
+
+        // The first location of each row, to find duplicates
+        let mut rows: HashMap<Vec<String>, (usize, Option<usize>)> = HashMap::new();
+        // The locations of the rows of each surface, to find homographs over the limit
+        let mut surfaces: BTreeMap<String, Vec<(usize, Option<usize>)>> = BTreeMap::new();
+
+        for (file_index, filename) in filenames.iter().enumerate() {
+            debug!("validating {:?}", filename);
+
+            let file = File::open(filename)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            let reader: Box<dyn Read> = if encoding == UTF_8 {
+                Box::new(file)
+            } else {
+                Box::new(
+                    DecodeReaderBytesBuilder::new()
+                        .encoding(Some(encoding))
+                        .build(file),
+                )
+            };
+            let mut rdr = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_reader(reader);
+
+            for result in rdr.records() {
+                let record = match result {
+                    Ok(record) => record,
+                    Err(err) => {
+                        let line_number = err.position().map(|pos| pos.line() as usize);
+                        report.push(
+                            ValidationIssueKind::Parse,
+                            filename,
+                            line_number,
+                            err.to_string(),
+                        );
+                        continue;
+                    }
+                };
+                let line_number = record.position().map(|pos| pos.line() as usize);
+
+                let valid_fields_num = if self.flexible_csv {
+                    record.len() >= 4
+                } else {
+                    record.len() == self.fields_num
+                };
+                if !valid_fields_num {
+                    report.push(
+                        ValidationIssueKind::FieldCount,
+                        filename,
+                        line_number,
+                        format!("expected {} fields, got {}", self.fields_num, record.len()),
+                    );
+                    if record.len() < 4 {
+                        continue;
+                    }
+                }
+
+                if self.allow_unindexed_entries && record[1].trim() == "-1" {
+                    continue;
+                }
+
+                let fields: Vec<&str> = record.iter().take(4).collect();
+                check_entry(&fields[1..4], matrix_size, filename, line_number, report);
+
+                let key: Vec<String> = record.iter().map(str::to_string).collect();
+                if let Some((first_file, first_line)) = rows.get(&key) {
+                    report.push(
+                        ValidationIssueKind::DuplicateEntry,
+                        filename,
+                        line_number,
+                        format!(
+                            "duplicate of {}",
+                            location(&filenames[*first_file], *first_line)
+                        ),
+                    );
+                    continue;
+                }
+                rows.insert(key, (file_index, line_number));
+
+                let surface = if self.normalize_details {
+                    normalize(&record[0])
+                } else {
+                    record[0].to_string()
+                };
+                surfaces
+                    .entry(surface)
+                    .or_default()
+                    .push((file_index, line_number));
+            }
+        }
+
+        for (surface, locations) in surfaces {
+            if locations.len() > MAX_HOMOGRAPHS {
+                let (file_index, line_number) = locations[0];
+                report.push(
+                    ValidationIssueKind::TooManyHomographs,
+                    &filenames[file_index],
+                    line_number,
+                    format!(
+                        "{} has {} entries, exceeding the limit of {}",
+                        surface,
+                        locations.len(),
+                        MAX_HOMOGRAPHS
+                    ),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encoding(&self) -> LinderaResult<&'static Encoding> {
+        Encoding::for_label_no_replacement(self.encoding.as_bytes()).ok_or_else(|| {
+            LinderaErrorKind::Decode.with_error(anyhow!("Invalid encoding: {}", self.encoding))
+        })
+    }
+
+    /// Read a definition file, reporting it if it is missing.
+    fn read(&self, path: &Path, report: &mut ValidationReport) -> LinderaResult<Option<String>> {
+        if !path.is_file() {
+            report.push(ValidationIssueKind::Parse, path, None, "file not found");
+            return Ok(None);
+        }
+        read_file_with_encoding(path, &self.encoding).map(Some)
+    }
+}
+
+/// Check the left context id, the right context id and the cost of an entry.
+fn check_entry(
+    fields: &[&str],
+    matrix_size: Option<&MatrixSize>,
+    path: &Path,
+    line_number: Option<usize>,
+    report: &mut ValidationReport,
+) {
+    let names = ["left id", "right id", "word cost"];
+    let mut values = Vec::new();
+    for (name, field) in names.iter().zip(fields) {
+        match field.trim().parse::<i64>() {
+            Ok(value) => values.push(Some(value)),
+            Err(_) => {
+                report.push(
+                    ValidationIssueKind::Parse,
+                    path,
+                    line_number,
+                    format!("failed to parse {}: {}", name, field),
+                );
+                values.push(None);
+            }
+        }
+    }
+
+    if let Some(matrix_size) = matrix_size {
+        // The left id of a word is the backward id of the connection from the preceding word,
+        // and the right id is the forward id of the connection to the following word.
+        if let Some(left_id) = values[0] {
+            check_context_id(
+                "left id",
+                left_id,
+                matrix_size.backward_size,
+                path,
+                line_number,
+                report,
+            );
+        }
+        if let Some(right_id) = values[1] {
+            check_context_id(
+                "right id",
+                right_id,
+                matrix_size.forward_size,
+                path,
+                line_number,
+                report,
+            );
+        }
+    }
+    if let Some(cost) = values[2] {
+        check_cost(cost, path, line_number, report);
+    }
+}
+
+fn check_context_id(
+    name: &str,
+    id: i64,
+    size: i64,
+    path: &Path,
+    line_number: Option<usize>,
+    report: &mut ValidationReport,
+) {
+    if id < 0 || id >= size {
+        report.push(
+            ValidationIssueKind::ContextIdOutOfRange,
+            path,
+            line_number,
+            format!("{} {} is out of range 0..{}", name, id, size),
+        );
+    }
+}
+
+fn check_cost(cost: i64, path: &Path, line_number: Option<usize>, report: &mut ValidationReport) {
+    if cost < i16::MIN as i64 || cost > i16::MAX as i64 {
+        report.push(
+            ValidationIssueKind::CostOutOfRange,
+            path,
+            line_number,
+            format!("cost {} is out of range of i16", cost),
+        );
+    }
+}
+
+fn parse_integers(line: &str) -> Option<Vec<i64>> {
+    line.split_whitespace()
+        .map(|field| field.parse::<i64>().ok())
+        .collect()
+}
+
+fn location(path: &Path, line_number: Option<usize>) -> String {
+    match line_number {
+        Some(line_number) => format!("{}:{}", path.display(), line_number),
+        None => path.display().to_string(),
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.to_string().replace('―', "—").replace('～', "〜")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::validation::ValidationIssueKind;
+
+    use crate::validator::{DictionaryValidatorOptions, MAX_HOMOGRAPHS};
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        let input_dir = std::env::temp_dir().join("lindera-dictionary-builder-validator");
+        let _ = fs::remove_dir_all(&input_dir);
+        fs::create_dir_all(&input_dir).unwrap();
+
+        write(
+            &input_dir,
+            "char.def",
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n0x3041..0x3096 HIRAGANA\n",
+        );
+        write(
+            &input_dir,
+            "unk.def",
+            "DEFAULT,0,0,1000,記号\nKATAKANA,1,1,1000,名詞\n",
+        );
+        write(&input_dir, "matrix.def", "2 2\n0 0 0\n0 1 40000\n");
+        let mut lexicon = String::new();
+        lexicon.push_str("東京,1,1,100,名詞\n"); // line 1: valid
+        lexicon.push_str("東京,1,1,100,名詞\n"); // line 2: duplicate
+        lexicon.push_str("京都,2,1,100,名詞\n"); // line 3: left id out of range
+        lexicon.push_str("大阪,1,1,40000,名詞\n"); // line 4: cost out of range
+        lexicon.push_str("奈良,1,1,100\n"); // line 5: wrong number of fields
+        lexicon.push_str("神戸,1,1,x,名詞\n"); // line 6: invalid cost
+        for i in 0..=MAX_HOMOGRAPHS {
+            lexicon.push_str(&format!("の,0,0,{},助詞\n", i));
+        }
+        write(&input_dir, "lex.csv", &lexicon);
+
+        let report = DictionaryValidatorOptions::default()
+            .fields_num(5)
+            .unk_fields_num(5)
+            .builder()
+            .unwrap()
+            .validate(&input_dir)
+            .unwrap();
+
+        let issues: Vec<(ValidationIssueKind, String, Option<usize>)> = report
+            .issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.kind,
+                    issue
+                        .path
+                        .file_name()
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                        .to_string(),
+                    issue.line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    ValidationIssueKind::CostOutOfRange,
+                    "matrix.def".to_string(),
+                    Some(3)
+                ),
+                (
+                    ValidationIssueKind::UndefinedCategory,
+                    "char.def".to_string(),
+                    Some(4)
+                ),
+                (
+                    ValidationIssueKind::UndefinedCategory,
+                    "unk.def".to_string(),
+                    Some(2)
+                ),
+                (
+                    ValidationIssueKind::DuplicateEntry,
+                    "lex.csv".to_string(),
+                    Some(2)
+                ),
+                (
+                    ValidationIssueKind::ContextIdOutOfRange,
+                    "lex.csv".to_string(),
+                    Some(3)
+                ),
+                (
+                    ValidationIssueKind::CostOutOfRange,
+                    "lex.csv".to_string(),
+                    Some(4)
+                ),
+                (
+                    ValidationIssueKind::FieldCount,
+                    "lex.csv".to_string(),
+                    Some(5)
+                ),
+                (ValidationIssueKind::Parse, "lex.csv".to_string(), Some(6)),
+                (
+                    ValidationIssueKind::TooManyHomographs,
+                    "lex.csv".to_string(),
+                    Some(7)
+                ),
+            ]
+        );
+        assert_eq!(
+            report.issues[3].to_string(),
+            format!(
+                "{}:2: duplicate of {}:1",
+                input_dir.join("lex.csv").display(),
+                input_dir.join("lex.csv").display()
+            )
+        );
+
+        fs::remove_dir_all(&input_dir).unwrap();
+    }
+}
@@ -0,0 +1,687 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use lindera_core::character_definition::{CategoryData, CharacterDefinitions};
+use lindera_core::connection::ConnectionCostMatrix;
+use lindera_core::dictionary::Dictionary;
+use lindera_core::unknown_dictionary::UnknownDictionary;
+
+/// The largest code point looked up when comparing the character definitions.
+const MAX_CODE_POINT: u32 = 0x10FFFF;
+
+/// A lexicon entry of a built dictionary.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+pub struct DictionaryEntry {
+    pub surface: String,
+    pub left_id: u16,
+    pub right_id: u16,
+    pub word_cost: i16,
+    pub details: Vec<String>,
+}
+
+impl DictionaryEntry {
+    fn same_costs(&self, other: &DictionaryEntry) -> bool {
+        self.left_id == other.left_id
+            && self.right_id == other.right_id
+            && self.word_cost == other.word_cost
+    }
+}
+
+impl fmt::Display for DictionaryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{},{},{}\t{}",
+            self.surface,
+            self.left_id,
+            self.right_id,
+            self.word_cost,
+            self.details.join(",")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub enum EntryChange {
+    Added(DictionaryEntry),
+    Removed(DictionaryEntry),
+    /// An entry of which the context ids, the cost or the details changed.
+    Changed {
+        old: DictionaryEntry,
+        new: DictionaryEntry,
+    },
+}
+
+impl fmt::Display for EntryChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryChange::Added(entry) => write!(f, "+ {}", entry),
+            EntryChange::Removed(entry) => write!(f, "- {}", entry),
+            EntryChange::Changed { old, new } => write!(
+                f,
+                "~ {}\t{},{},{}\t{} => {},{},{}\t{}",
+                old.surface,
+                old.left_id,
+                old.right_id,
+                old.word_cost,
+                old.details.join(","),
+                new.left_id,
+                new.right_id,
+                new.word_cost,
+                new.details.join(",")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub struct ConnectionCostChange {
+    pub forward_id: u32,
+    pub backward_id: u32,
+    pub old_cost: i32,
+    pub new_cost: i32,
+}
+
+impl fmt::Display for ConnectionCostChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "~ matrix {} {}\t{} => {}",
+            self.forward_id, self.backward_id, self.old_cost, self.new_cost
+        )
+    }
+}
+
+/// The differences between two connection cost matrices.
+/// If the sizes differ, only the costs of the ids that are in both matrices are compared.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct ConnectionDiff {
+    /// The (forward, backward) sizes of the old matrix
+    pub old_size: (u32, u32),
+    /// The (forward, backward) sizes of the new matrix
+    pub new_size: (u32, u32),
+    pub changes: Vec<ConnectionCostChange>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub enum CategoryChange {
+    Added {
+        name: String,
+        data: CategoryData,
+    },
+    Removed {
+        name: String,
+        data: CategoryData,
+    },
+    Changed {
+        name: String,
+        old: CategoryData,
+        new: CategoryData,
+    },
+}
+
+fn format_category_data(data: &CategoryData) -> String {
+    format!("{} {} {}", data.invoke as u8, data.group as u8, data.length)
+}
+
+impl fmt::Display for CategoryChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoryChange::Added { name, data } => {
+                write!(f, "+ category {}\t{}", name, format_category_data(data))
+            }
+            CategoryChange::Removed { name, data } => {
+                write!(f, "- category {}\t{}", name, format_category_data(data))
+            }
+            CategoryChange::Changed { name, old, new } => write!(
+                f,
+                "~ category {}\t{} => {}",
+                name,
+                format_category_data(old),
+                format_category_data(new)
+            ),
+        }
+    }
+}
+
+/// A range of code points of which the categories changed.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct CharRangeChange {
+    pub start: u32,
+    /// The last code point of the range (inclusive)
+    pub end: u32,
+    pub old_categories: Vec<String>,
+    pub new_categories: Vec<String>,
+}
+
+impl fmt::Display for CharRangeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "~ char 0x{:04X}..0x{:04X}\t{} => {}",
+            self.start,
+            self.end,
+            self.old_categories.join(" "),
+            self.new_categories.join(" ")
+        )
+    }
+}
+
+/// The unknown word entries of a category that changed.
+/// The entries are given as `DictionaryEntry`s with the category name as the surface.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct UnknownEntriesChange {
+    pub category: String,
+    pub old_entries: Vec<DictionaryEntry>,
+    pub new_entries: Vec<DictionaryEntry>,
+}
+
+fn format_unknown_entries(entries: &[DictionaryEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            let mut fields = vec![
+                entry.left_id.to_string(),
+                entry.right_id.to_string(),
+                entry.word_cost.to_string(),
+            ];
+            fields.extend(entry.details.iter().cloned());
+            fields.join(",")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl fmt::Display for UnknownEntriesChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "~ unk {}\t{} => {}",
+            self.category,
+            format_unknown_entries(&self.old_entries),
+            format_unknown_entries(&self.new_entries)
+        )
+    }
+}
+
+/// The number of changes of each kind in a `DictionaryDiff`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct DictionaryDiffSummary {
+    pub added_entries: usize,
+    pub removed_entries: usize,
+    pub changed_entries: usize,
+    pub changed_connection_costs: usize,
+    pub matrix_size_changed: bool,
+    pub changed_categories: usize,
+    pub changed_char_ranges: usize,
+    pub changed_unknown_categories: usize,
+}
+
+impl fmt::Display for DictionaryDiffSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "entries: {} added, {} removed, {} changed",
+            self.added_entries, self.removed_entries, self.changed_entries
+        )?;
+        writeln!(
+            f,
+            "matrix.def: {} costs changed{}",
+            self.changed_connection_costs,
+            if self.matrix_size_changed {
+                ", size changed"
+            } else {
+                ""
+            }
+        )?;
+        writeln!(
+            f,
+            "char.def: {} categories changed, {} character ranges changed",
+            self.changed_categories, self.changed_char_ranges
+        )?;
+        writeln!(
+            f,
+            "unk.def: {} categories changed",
+            self.changed_unknown_categories
+        )
+    }
+}
+
+/// The differences between two built dictionaries.
+///
+/// Lexicon entries are matched by surface. Entries with the same surface are matched by their
+/// details first, then by their context ids and cost, and the ones left are reported as added
+/// or removed.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct DictionaryDiff {
+    pub entries: Vec<EntryChange>,
+    pub connection: ConnectionDiff,
+    pub categories: Vec<CategoryChange>,
+    pub char_ranges: Vec<CharRangeChange>,
+    pub unknown_entries: Vec<UnknownEntriesChange>,
+}
+
+impl DictionaryDiff {
+    pub fn new(old: &Dictionary, new: &Dictionary) -> Self {
+        DictionaryDiff {
+            entries: diff_entries(old, new),
+            connection: diff_connection(&old.cost_matrix, &new.cost_matrix),
+            categories: diff_categories(&old.char_definitions, &new.char_definitions),
+            char_ranges: diff_char_ranges(&old.char_definitions, &new.char_definitions),
+            unknown_entries: diff_unknown_entries(
+                (&old.char_definitions, &old.unknown_dictionary),
+                (&new.char_definitions, &new.unknown_dictionary),
+            ),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+            && self.connection.old_size == self.connection.new_size
+            && self.connection.changes.is_empty()
+            && self.categories.is_empty()
+            && self.char_ranges.is_empty()
+            && self.unknown_entries.is_empty()
+    }
+
+    pub fn summary(&self) -> DictionaryDiffSummary {
+        let mut summary = DictionaryDiffSummary {
+            changed_connection_costs: self.connection.changes.len(),
+            matrix_size_changed: self.connection.old_size != self.connection.new_size,
+            changed_categories: self.categories.len(),
+            changed_char_ranges: self.char_ranges.len(),
+            changed_unknown_categories: self.unknown_entries.len(),
+            ..Default::default()
+        };
+        for change in &self.entries {
+            match change {
+                EntryChange::Added(_) => summary.added_entries += 1,
+                EntryChange::Removed(_) => summary.removed_entries += 1,
+                EntryChange::Changed { .. } => summary.changed_entries += 1,
+            }
+        }
+        summary
+    }
+}
+
+impl fmt::Display for DictionaryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.entries {
+            writeln!(f, "{}", change)?;
+        }
+        if self.connection.old_size != self.connection.new_size {
+            writeln!(
+                f,
+                "~ matrix size\t{} {} => {} {}",
+                self.connection.old_size.0,
+                self.connection.old_size.1,
+                self.connection.new_size.0,
+                self.connection.new_size.1
+            )?;
+        }
+        for change in &self.connection.changes {
+            writeln!(f, "{}", change)?;
+        }
+        for change in &self.categories {
+            writeln!(f, "{}", change)?;
+        }
+        for change in &self.char_ranges {
+            writeln!(f, "{}", change)?;
+        }
+        for change in &self.unknown_entries {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+fn lexicon(dictionary: &Dictionary) -> BTreeMap<String, Vec<DictionaryEntry>> {
+    dictionary
+        .dict
+        .entries()
+        .into_iter()
+        .map(|(surface, word_entries)| {
+            let entries = word_entries
+                .iter()
+                .map(|word_entry| DictionaryEntry {
+                    surface: surface.clone(),
+                    left_id: word_entry.left_id,
+                    right_id: word_entry.right_id,
+                    word_cost: word_entry.word_cost,
+                    details: dictionary
+                        .word_details(word_entry.word_id.0 as usize)
+                        .unwrap_or_default(),
+                })
+                .collect();
+            (surface, entries)
+        })
+        .collect()
+}
+
+/// Removes the first pair of entries of `olds` and `news` that `matches`, repeatedly.
+fn take_matches(
+    olds: &mut Vec<DictionaryEntry>,
+    news: &mut Vec<DictionaryEntry>,
+    matches: impl Fn(&DictionaryEntry, &DictionaryEntry) -> bool,
+) -> Vec<(DictionaryEntry, DictionaryEntry)> {
+    let mut pairs = Vec::new();
+    let mut i = 0;
+    while i < olds.len() {
+        match news.iter().position(|new| matches(&olds[i], new)) {
+            Some(j) => pairs.push((olds.remove(i), news.remove(j))),
+            None => i += 1,
+        }
+    }
+    pairs
+}
+
+fn diff_entries(old: &Dictionary, new: &Dictionary) -> Vec<EntryChange> {
+    let mut old_lexicon = lexicon(old);
+    let mut new_lexicon = lexicon(new);
+
+    let surfaces: BTreeSet<String> = old_lexicon
+        .keys()
+        .chain(new_lexicon.keys())
+        .cloned()
+        .collect();
+
+    let mut changes = Vec::new();
+    for surface in surfaces {
+        let mut olds = old_lexicon.remove(&surface).unwrap_or_default();
+        let mut news = new_lexicon.remove(&surface).unwrap_or_default();
+        olds.sort();
+        news.sort();
+
+        take_matches(&mut olds, &mut news, |old, new| old == new);
+        for (old, new) in take_matches(&mut olds, &mut news, |old, new| old.details == new.details)
+            .into_iter()
+            .chain(take_matches(&mut olds, &mut news, |old, new| {
+                old.same_costs(new)
+            }))
+        {
+            changes.push(EntryChange::Changed { old, new });
+        }
+        changes.extend(olds.into_iter().map(EntryChange::Removed));
+        changes.extend(news.into_iter().map(EntryChange::Added));
+    }
+    changes
+}
+
+/// Returns the (forward, backward) sizes of the matrix.
+fn matrix_size(matrix: &ConnectionCostMatrix) -> (u32, u32) {
+    if matrix.backward_size == 0 {
+        return (0, 0);
+    }
+    let forward_size = matrix.costs_data.len() as u32 / 2 / matrix.backward_size;
+    (forward_size, matrix.backward_size)
+}
+
+fn diff_connection(old: &ConnectionCostMatrix, new: &ConnectionCostMatrix) -> ConnectionDiff {
+    let old_size = matrix_size(old);
+    let new_size = matrix_size(new);
+
+    let mut changes = Vec::new();
+    for forward_id in 0..old_size.0.min(new_size.0) {
+        for backward_id in 0..old_size.1.min(new_size.1) {
+            let old_cost = old.cost(forward_id, backward_id);
+            let new_cost = new.cost(forward_id, backward_id);
+            if old_cost != new_cost {
+                changes.push(ConnectionCostChange {
+                    forward_id,
+                    backward_id,
+                    old_cost,
+                    new_cost,
+                });
+            }
+        }
+    }
+
+    ConnectionDiff {
+        old_size,
+        new_size,
+        changes,
+    }
+}
+
+fn categories(char_definitions: &CharacterDefinitions) -> BTreeMap<&str, CategoryData> {
+    char_definitions
+        .category_names
+        .iter()
+        .map(String::as_str)
+        .zip(char_definitions.category_definitions.iter().copied())
+        .collect()
+}
+
+fn diff_categories(old: &CharacterDefinitions, new: &CharacterDefinitions) -> Vec<CategoryChange> {
+    let old_categories = categories(old);
+    let new_categories = categories(new);
+
+    let names: BTreeSet<&str> = old_categories
+        .keys()
+        .chain(new_categories.keys())
+        .copied()
+        .collect();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let name_string = name.to_string();
+            match (old_categories.get(name), new_categories.get(name)) {
+                (Some(&old), Some(&new)) if old != new => Some(CategoryChange::Changed {
+                    name: name_string,
+                    old,
+                    new,
+                }),
+                (Some(&data), None) => Some(CategoryChange::Removed {
+                    name: name_string,
+                    data,
+                }),
+                (None, Some(&data)) => Some(CategoryChange::Added {
+                    name: name_string,
+                    data,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn category_names(char_definitions: &CharacterDefinitions, code_point: u32) -> Vec<&str> {
+    char_definitions
+        .mapping
+        .eval(code_point)
+        .iter()
+        .map(|&category_id| char_definitions.category_name(category_id))
+        .collect()
+}
+
+fn diff_char_ranges(
+    old: &CharacterDefinitions,
+    new: &CharacterDefinitions,
+) -> Vec<CharRangeChange> {
+    let mut changes: Vec<CharRangeChange> = Vec::new();
+    for code_point in 0..=MAX_CODE_POINT {
+        let old_categories = category_names(old, code_point);
+        let new_categories = category_names(new, code_point);
+        if old_categories == new_categories {
+            continue;
+        }
+
+        // Extend the previous range if the change is the same
+        if let Some(last) = changes.last_mut() {
+            if last.end + 1 == code_point
+                && last.old_categories == old_categories
+                && last.new_categories == new_categories
+            {
+                last.end = code_point;
+                continue;
+            }
+        }
+        changes.push(CharRangeChange {
+            start: code_point,
+            end: code_point,
+            old_categories: old_categories.iter().map(|name| name.to_string()).collect(),
+            new_categories: new_categories.iter().map(|name| name.to_string()).collect(),
+        });
+    }
+    changes
+}
+
+fn unknown_entries(
+    (char_definitions, unknown_dictionary): (&CharacterDefinitions, &UnknownDictionary),
+) -> BTreeMap<String, Vec<DictionaryEntry>> {
+    char_definitions
+        .category_names
+        .iter()
+        .enumerate()
+        .map(|(category_id, name)| {
+            let entries = unknown_dictionary
+                .category_references
+                .get(category_id)
+                .map(|word_ids| {
+                    word_ids
+                        .iter()
+                        .map(|&word_id| {
+                            let word_entry = unknown_dictionary.word_entry(word_id);
+                            DictionaryEntry {
+                                surface: name.clone(),
+                                left_id: word_entry.left_id,
+                                right_id: word_entry.right_id,
+                                word_cost: word_entry.word_cost,
+                                details: unknown_dictionary
+                                    .word_details(word_id)
+                                    .unwrap_or_default(),
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            (name.clone(), entries)
+        })
+        .collect()
+}
+
+fn diff_unknown_entries(
+    old: (&CharacterDefinitions, &UnknownDictionary),
+    new: (&CharacterDefinitions, &UnknownDictionary),
+) -> Vec<UnknownEntriesChange> {
+    let mut old_entries = unknown_entries(old);
+    let mut new_entries = unknown_entries(new);
+
+    let categories: BTreeSet<String> = old_entries
+        .keys()
+        .chain(new_entries.keys())
+        .cloned()
+        .collect();
+
+    categories
+        .into_iter()
+        .filter_map(|category| {
+            let old_entries = old_entries.remove(&category).unwrap_or_default();
+            let new_entries = new_entries.remove(&category).unwrap_or_default();
+            (old_entries != new_entries).then_some(UnknownEntriesChange {
+                category,
+                old_entries,
+                new_entries,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_dictionary_builder::{DictionarySchema, SchemaDictionaryBuilder};
+
+    use crate::diff::{
+        CategoryChange, CharRangeChange, ConnectionCostChange, DictionaryDiff, EntryChange,
+    };
+    use crate::DictionaryLoader;
+
+    fn write_source(dir: &Path, char_def: &str, unk_def: &str, matrix_def: &str, lex: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("char.def"), char_def).unwrap();
+        fs::write(dir.join("unk.def"), unk_def).unwrap();
+        fs::write(dir.join("matrix.def"), matrix_def).unwrap();
+        fs::write(dir.join("lex.csv"), lex).unwrap();
+    }
+
+    #[test]
+    fn test_diff() {
+        let tmp_dir = std::env::temp_dir().join("lindera-dictionary-diff");
+        let _ = fs::remove_dir_all(&tmp_dir);
+
+        let old_dir = tmp_dir.join("old");
+        write_source(
+            &old_dir,
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+            "DEFAULT,0,0,1000,記号\nKANJI,1,1,2000,名詞\n",
+            "2 2\n0 0 0\n0 1 10\n1 0 20\n1 1 30\n",
+            "東京,1,1,100,名詞\n京都,1,1,200,名詞\n大阪,1,1,300,名詞\n",
+        );
+        let new_dir = tmp_dir.join("new");
+        write_source(
+            &new_dir,
+            "DEFAULT 0 1 0\nKANJI 1 0 2\n0x4E00..0x4E01 DEFAULT\n0x4E02..0x9FA5 KANJI\n",
+            "DEFAULT,0,0,1000,記号\nKANJI,1,1,1500,名詞\n",
+            "2 2\n0 0 0\n0 1 10\n1 0 25\n1 1 30\n",
+            "東京,1,1,50,名詞\n京都,1,1,200,名詞\n神戸,1,1,400,名詞\n",
+        );
+
+        let schema = DictionarySchema::from_slice(br#"{"fields": ["pos"]}"#).unwrap();
+        let builder = SchemaDictionaryBuilder::new(schema);
+        let old = DictionaryLoader::load_dictionary_from_source(&builder, &old_dir).unwrap();
+        let new = DictionaryLoader::load_dictionary_from_source(&builder, &new_dir).unwrap();
+
+        assert!(DictionaryDiff::new(&old, &old).is_empty());
+
+        let diff = DictionaryDiff::new(&old, &new);
+        assert_eq!(
+            diff.entries
+                .iter()
+                .map(|change| change.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "- 大阪\t1,1,300\t名詞",
+                "~ 東京\t1,1,100\t名詞 => 1,1,50\t名詞",
+                "+ 神戸\t1,1,400\t名詞",
+            ]
+        );
+        assert!(matches!(diff.entries[1], EntryChange::Changed { .. }));
+        assert_eq!(
+            diff.connection.changes,
+            vec![ConnectionCostChange {
+                forward_id: 1,
+                backward_id: 0,
+                old_cost: 20,
+                new_cost: 25,
+            }]
+        );
+        assert!(matches!(
+            &diff.categories[..],
+            [CategoryChange::Changed { name, .. }] if name == "KANJI"
+        ));
+        assert_eq!(
+            diff.char_ranges,
+            vec![CharRangeChange {
+                start: 0x4E00,
+                end: 0x4E01,
+                old_categories: vec!["KANJI".to_string()],
+                new_categories: vec!["DEFAULT".to_string()],
+            }]
+        );
+        assert_eq!(diff.unknown_entries.len(), 1);
+        assert_eq!(diff.unknown_entries[0].category, "KANJI");
+
+        let summary = diff.summary();
+        assert_eq!(summary.added_entries, 1);
+        assert_eq!(summary.removed_entries, 1);
+        assert_eq!(summary.changed_entries, 1);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
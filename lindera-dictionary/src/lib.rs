@@ -1,7 +1,11 @@
 use std::borrow::Cow;
+use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
@@ -13,14 +17,20 @@ use lindera_core::connection::ConnectionCostMatrix;
 use lindera_core::dictionary::{Dictionary, UserDictionary};
 use lindera_core::dictionary_builder::DictionaryBuilder;
 use lindera_core::error::{LinderaError, LinderaErrorKind};
+use lindera_core::id_definition::IdDefinitions;
 use lindera_core::prefix_dict::PrefixDict;
 use lindera_core::unknown_dictionary::UnknownDictionary;
+use lindera_core::user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat};
 use lindera_core::LinderaResult;
+use lindera_dictionary_builder::{DictionarySchema, SchemaDictionaryBuilder};
 use lindera_ipadic_builder::ipadic_builder::IpadicBuilder;
 use lindera_ipadic_neologd_builder::ipadic_neologd_builder::IpadicNeologdBuilder;
 use lindera_ko_dic_builder::ko_dic_builder::KoDicBuilder;
+use lindera_sudachi_builder::sudachi_builder::SudachiBuilder;
 use lindera_unidic_builder::unidic_builder::UnidicBuilder;
 
+pub mod diff;
+
 #[derive(Debug, Clone, EnumIter, Deserialize, Serialize, PartialEq, Eq)]
 pub enum DictionaryKind {
     #[serde(rename = "ipadic")]
@@ -33,6 +43,8 @@ pub enum DictionaryKind {
     KoDic,
     #[serde(rename = "cc-cedict")]
     CcCedict,
+    #[serde(rename = "sudachi")]
+    Sudachi,
 }
 
 impl DictionaryKind {
@@ -49,6 +61,8 @@ impl DictionaryKind {
                 DictionaryKind::UniDic => cfg!(feature = "unidic"),
                 DictionaryKind::KoDic => cfg!(feature = "ko-dic"),
                 DictionaryKind::CcCedict => cfg!(feature = "cc-cedict"),
+                // SudachiDict is only available as an external dictionary.
+                DictionaryKind::Sudachi => false,
             })
             .collect::<Vec<_>>()
     }
@@ -60,6 +74,7 @@ impl DictionaryKind {
             DictionaryKind::UniDic => "unidic",
             DictionaryKind::KoDic => "ko-dic",
             DictionaryKind::CcCedict => "cc-cedict",
+            DictionaryKind::Sudachi => "sudachi",
         }
     }
 }
@@ -73,6 +88,7 @@ impl FromStr for DictionaryKind {
             "unidic" => Ok(DictionaryKind::UniDic),
             "ko-dic" => Ok(DictionaryKind::KoDic),
             "cc-cedict" => Ok(DictionaryKind::CcCedict),
+            "sudachi" => Ok(DictionaryKind::Sudachi),
             _ => Err(LinderaErrorKind::DictionaryKindError
                 .with_error(anyhow::anyhow!("Invalid dictionary kind: {}", input))),
         }
@@ -101,8 +117,17 @@ pub struct DictionaryConfig {
 pub struct UserDictionaryConfig {
     /// Path to the user dictionary file.
     pub path: PathBuf,
-    /// If the user dictionary was in CSV format, specify the dictionary type (IPADIC, UniDic, ko-dic or CC-CEDICT).
+    /// If the user dictionary was a source (CSV, TSV, JSON or JSONL), specify the dictionary type (IPADIC, UniDic, ko-dic, CC-CEDICT or SudachiDict).
     pub kind: Option<DictionaryKind>,
+    /// The format of the user dictionary file. If not specified, it is detected from the extension of the file.
+    #[serde(default)]
+    pub format: Option<UserDictionaryFormat>,
+    /// If the user dictionary was a source for a dictionary built from a schema, specify the path to the schema file instead of the dictionary type.
+    #[serde(default)]
+    pub schema: Option<PathBuf>,
+    /// If the user dictionary was a source, estimate the context ids and the word costs of its simple entries from the system dictionary.
+    #[serde(default)]
+    pub estimate_cost: bool,
 }
 
 pub struct DictionaryBuilderResolver {}
@@ -117,10 +142,22 @@ impl DictionaryBuilderResolver {
             DictionaryKind::UniDic => Ok(Box::new(UnidicBuilder::new())),
             DictionaryKind::KoDic => Ok(Box::new(KoDicBuilder::new())),
             DictionaryKind::CcCedict => Ok(Box::new(CcCedictBuilder::new())),
+            DictionaryKind::Sudachi => Ok(Box::new(SudachiBuilder::new())),
         }
     }
+
+    /// Resolve a builder for a dictionary described by a schema file instead of a dictionary kind.
+    pub fn resolve_builder_from_schema(
+        schema_path: &Path,
+    ) -> LinderaResult<Box<dyn DictionaryBuilder>> {
+        let schema = DictionarySchema::from_file(schema_path)?;
+        Ok(Box::new(SchemaDictionaryBuilder::new(schema)))
+    }
 }
 
+/// Used to name the temporary directories the dictionary sources are built into.
+static TEMP_DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
 pub struct DictionaryLoader {}
 
 impl DictionaryLoader {
@@ -172,17 +209,56 @@ impl DictionaryLoader {
         Self::read_file(path)
     }
 
+    pub fn id_def(dir: PathBuf) -> LinderaResult<IdDefinitions> {
+        let path = dir.join("id_def.bin");
+        if !path.exists() {
+            // Dictionaries built by older versions don't have id definitions.
+            return Ok(IdDefinitions::default());
+        }
+        let data = Self::read_file(path)?;
+
+        IdDefinitions::load(data.as_slice())
+    }
+
     pub fn load_dictionary(path: PathBuf) -> LinderaResult<Dictionary> {
+        Ok(Dictionary {
+            words_idx_data: Cow::Owned(Self::words_idx_data(path.clone())?),
+            words_data: Cow::Owned(Self::words_data(path.clone())?),
+            ..Self::load_dictionary_without_details(path)?
+        })
+    }
+
+    /// Load a dictionary without the details of the words, for tokenizers that only segment the
+    /// text. `dict.words` and `dict.wordsidx` are not read.
+    pub fn load_dictionary_without_details(path: PathBuf) -> LinderaResult<Dictionary> {
         Ok(Dictionary {
             dict: Self::prefix_dict(path.clone())?,
             cost_matrix: Self::connection(path.clone())?,
             char_definitions: Self::char_def(path.clone())?,
             unknown_dictionary: Self::unknown_dict(path.clone())?,
-            words_idx_data: Cow::Owned(Self::words_idx_data(path.clone())?),
-            words_data: Cow::Owned(Self::words_data(path)?),
+            words_idx_data: Cow::Borrowed(&[]),
+            words_data: Cow::Borrowed(&[]),
+            id_definitions: Self::id_def(path)?,
         })
     }
 
+    /// Build a dictionary source into a temporary directory and load it.
+    pub fn load_dictionary_from_source(
+        builder: &dyn DictionaryBuilder,
+        input_dir: &Path,
+    ) -> LinderaResult<Dictionary> {
+        let output_dir = env::temp_dir().join(format!(
+            "lindera-dictionary-{}-{}",
+            process::id(),
+            TEMP_DIR_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let dictionary = builder
+            .build_dictionary(input_dir, &output_dir)
+            .and_then(|_| Self::load_dictionary(output_dir.clone()));
+        let _ = fs::remove_dir_all(&output_dir);
+        dictionary
+    }
+
     pub fn load_dictionary_from_kind(kind: DictionaryKind) -> LinderaResult<Dictionary> {
         // The dictionary specified by the feature flag will be loaded.
         match kind {
@@ -228,6 +304,57 @@ impl DictionaryLoader {
         }
     }
 
+    /// Load a self-contained dictionary without the details of the words.
+    pub fn load_dictionary_from_kind_without_details(
+        kind: DictionaryKind,
+    ) -> LinderaResult<Dictionary> {
+        // The dictionary specified by the feature flag will be loaded.
+        match kind {
+            #[cfg(feature = "ipadic")]
+            DictionaryKind::IPADIC => lindera_ipadic::load_dictionary_without_details()
+                .map_err(|e| LinderaErrorKind::DictionaryNotFound.with_error(e)),
+            #[cfg(feature = "ipadic-neologd")]
+            DictionaryKind::IPADICNEologd => {
+                lindera_ipadic_neologd::load_dictionary_without_details()
+                    .map_err(|e| LinderaErrorKind::DictionaryNotFound.with_error(e))
+            }
+            #[cfg(feature = "unidic")]
+            DictionaryKind::UniDic => lindera_unidic::load_dictionary_without_details()
+                .map_err(|e| LinderaErrorKind::DictionaryNotFound.with_error(e)),
+            #[cfg(feature = "ko-dic")]
+            DictionaryKind::KoDic => lindera_ko_dic::load_dictionary_without_details()
+                .map_err(|e| LinderaErrorKind::DictionaryNotFound.with_error(e)),
+            #[cfg(feature = "cc-cedict")]
+            DictionaryKind::CcCedict => lindera_cc_cedict::load_dictionary_without_details()
+                .map_err(|e| LinderaErrorKind::DictionaryNotFound.with_error(e)),
+            #[allow(unreachable_patterns)]
+            _ => Err(LinderaErrorKind::Args
+                .with_error(anyhow::anyhow!("Invalid dictionary type: {:?}", kind))),
+        }
+    }
+
+    /// Load the dictionary of a config without the details of the words.
+    pub fn load_dictionary_from_config_without_details(
+        dictionary_config: DictionaryConfig,
+    ) -> LinderaResult<Dictionary> {
+        match dictionary_config.kind {
+            Some(kind) => {
+                // The dictionary specified by the feature flag will be loaded.
+                Self::load_dictionary_from_kind_without_details(kind)
+            }
+            None => {
+                match dictionary_config.path {
+                    Some(path) => {
+                        // load external dictionary from path
+                        Self::load_dictionary_without_details(path)
+                    }
+                    None => Err(LinderaErrorKind::Args
+                        .with_error(anyhow::anyhow!("Dictionary must be specified"))),
+                }
+            }
+        }
+    }
+
     pub fn load_user_dictionary_from_csv(
         kind: DictionaryKind,
         path: PathBuf,
@@ -242,24 +369,371 @@ impl DictionaryLoader {
         UserDictionary::load(&Self::read_file(path)?)
     }
 
+    pub fn load_user_dictionary_from_source(
+        kind: DictionaryKind,
+        path: PathBuf,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        let builder = DictionaryBuilderResolver::resolve_builder(kind)?;
+        builder
+            .build_user_dict_with_format(path.as_path(), format)
+            .map_err(|err| LinderaErrorKind::DictionaryBuildError.with_error(err))
+    }
+
+    /// Load a user dictionary source, estimating the costs of its simple entries from the
+    /// system dictionary `dictionary`.
+    pub fn load_user_dictionary_from_source_with_estimation(
+        kind: DictionaryKind,
+        path: PathBuf,
+        format: UserDictionaryFormat,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<UserDictionary> {
+        let builder = DictionaryBuilderResolver::resolve_builder(kind)?;
+        builder
+            .build_user_dict_with_estimation(path.as_path(), format, dictionary)
+            .map_err(|err| LinderaErrorKind::DictionaryBuildError.with_error(err))
+    }
+
+    pub fn load_user_dictionary_from_entries(
+        kind: DictionaryKind,
+        entries: Vec<UserDictionaryEntry>,
+    ) -> LinderaResult<UserDictionary> {
+        let builder = DictionaryBuilderResolver::resolve_builder(kind)?;
+        builder
+            .build_user_dict_from_entries(entries)
+            .map_err(|err| LinderaErrorKind::DictionaryBuildError.with_error(err))
+    }
+
+    /// Load a user dictionary read from `reader`. A built user dictionary is loaded as it is,
+    /// and a source is built with the builder of `kind`.
+    pub fn load_user_dictionary_from_reader<R: Read>(
+        kind: DictionaryKind,
+        mut reader: R,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        if format == UserDictionaryFormat::Bin {
+            let mut data = Vec::new();
+            reader
+                .read_to_end(&mut data)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            return UserDictionary::load(&data);
+        }
+
+        let builder = DictionaryBuilderResolver::resolve_builder(kind)?;
+        builder
+            .build_user_dict_from_reader(&mut reader, format)
+            .map_err(|err| LinderaErrorKind::DictionaryBuildError.with_error(err))
+    }
+
     pub fn load_user_dictionary_from_config(
         dictionary_config: UserDictionaryConfig,
     ) -> LinderaResult<UserDictionary> {
-        match dictionary_config.path.extension() {
-            Some(ext) => match ext.to_str() {
-                Some("csv") => match dictionary_config.kind {
-                    Some(kind) => Self::load_user_dictionary_from_csv(kind, dictionary_config.path),
-                    None => Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
-                        "Dictionary type must be specified if CSV file specified"
-                    ))),
-                },
-                Some("bin") => Self::load_user_dictionary_from_bin(dictionary_config.path),
-                _ => Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
-                    "Invalid user dictionary source file extension"
-                ))),
-            },
-            None => Err(LinderaErrorKind::Args
-                .with_error(anyhow::anyhow!("Invalid user dictionary source file"))),
+        Self::load_user_dictionary(dictionary_config, None, true)
+    }
+
+    /// Load a user dictionary for the system dictionary `dictionary`, which is needed to
+    /// estimate the costs of the entries if `estimate_cost` is set.
+    pub fn load_user_dictionary_from_config_with_dictionary(
+        dictionary_config: UserDictionaryConfig,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<UserDictionary> {
+        Self::load_user_dictionary(dictionary_config, Some(dictionary), true)
+    }
+
+    /// Load a user dictionary without the details of the words, for tokenizers that only
+    /// segment the text. The costs cannot be estimated, as the system dictionary has no details.
+    pub fn load_user_dictionary_from_config_without_details(
+        dictionary_config: UserDictionaryConfig,
+    ) -> LinderaResult<UserDictionary> {
+        if dictionary_config.estimate_cost {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "The costs of a user dictionary cannot be estimated from a dictionary without details"
+            )));
+        }
+        // Only the prefix dictionary is needed to segment the text.
+        Self::load_user_dictionary(dictionary_config, None, false)
+    }
+
+    fn load_user_dictionary(
+        dictionary_config: UserDictionaryConfig,
+        dictionary: Option<&Dictionary>,
+        details: bool,
+    ) -> LinderaResult<UserDictionary> {
+        let format = dictionary_config
+            .format
+            .or_else(|| UserDictionaryFormat::from_path(&dictionary_config.path));
+        match format {
+            Some(UserDictionaryFormat::Bin) if details => {
+                Self::load_user_dictionary_from_bin(dictionary_config.path)
+            }
+            Some(UserDictionaryFormat::Bin) => {
+                UserDictionary::load_without_details(&Self::read_file(dictionary_config.path)?)
+            }
+            Some(format) => {
+                let builder = match (&dictionary_config.schema, dictionary_config.kind) {
+                    (Some(schema_path), _) => {
+                        DictionaryBuilderResolver::resolve_builder_from_schema(schema_path)?
+                    }
+                    (None, Some(kind)) => DictionaryBuilderResolver::resolve_builder(kind)?,
+                    (None, None) => {
+                        return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                            "Dictionary type or schema must be specified if a user dictionary source file specified"
+                        )))
+                    }
+                };
+                let path = dictionary_config.path.as_path();
+                let user_dictionary = if dictionary_config.estimate_cost {
+                    let dictionary = dictionary.ok_or_else(|| {
+                        LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                            "The system dictionary must be given to estimate the costs of a user dictionary"
+                        ))
+                    })?;
+                    builder.build_user_dict_with_estimation(path, format, dictionary)
+                } else if details {
+                    builder.build_user_dict_with_format(path, format)
+                } else {
+                    builder.build_user_dict_without_details(path, format)
+                };
+                user_dictionary
+                    .map_err(|err| LinderaErrorKind::DictionaryBuildError.with_error(err))
+            }
+            None => Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "Invalid user dictionary source file extension"
+            ))),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use lindera_core::dictionary_builder::DictionaryBuilder;
+    use lindera_core::mode::Mode;
+    use lindera_core::viterbi::{EdgeType, Lattice};
+    use lindera_dictionary_builder::user_dict::build_user_dictionary;
+    use lindera_dictionary_builder::{DictionarySchema, SchemaDictionaryBuilder};
+
+    use crate::diff::DictionaryDiff;
+    use crate::{DictionaryKind, DictionaryLoader, UserDictionaryConfig};
+
+    #[test]
+    fn test_export_dictionary() {
+        let tmp_dir = std::env::temp_dir().join("lindera-dictionary-export");
+        let _ = fs::remove_dir_all(&tmp_dir);
+
+        let source_dir = tmp_dir.join("source");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(
+            source_dir.join("char.def"),
+            "DEFAULT 0 1 0\nHIRAGANA 1 1 0\nKANJI 0 0 2\nKANJINUMERIC 1 1 0\n\
+             0x3041..0x309F HIRAGANA\n0x4E00..0x9FA5 KANJI\n0x4E00 KANJINUMERIC KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            source_dir.join("unk.def"),
+            "DEFAULT,0,0,1000,記号\nHIRAGANA,1,1,1500,名詞\nKANJI,1,1,2000,名詞\nKANJI,0,0,2500,記号\n\
+             KANJINUMERIC,1,1,1800,名詞\n",
+        )
+        .unwrap();
+        fs::write(
+            source_dir.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 10\n1 0 20\n",
+        )
+        .unwrap();
+        fs::write(
+            source_dir.join("lex.csv"),
+            "東京,1,1,100,名詞\n京都,1,1,200,名詞\n東京,0,0,300,\"名詞,固有名詞\"\n",
+        )
+        .unwrap();
+
+        let schema = DictionarySchema::from_slice(br#"{"fields": ["pos"]}"#).unwrap();
+        let builder = SchemaDictionaryBuilder::new(schema);
+        let dictionary =
+            DictionaryLoader::load_dictionary_from_source(&builder, &source_dir).unwrap();
+
+        let exported_dir = tmp_dir.join("exported");
+        builder
+            .export_dictionary(&dictionary, &exported_dir)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(exported_dir.join("unk.def")).unwrap(),
+            "DEFAULT,0,0,1000,記号\nHIRAGANA,1,1,1500,名詞\nKANJI,1,1,2000,名詞\nKANJI,0,0,2500,記号\n\
+             KANJINUMERIC,1,1,1800,名詞\n"
+        );
+
+        // The dictionary rebuilt from the exported source is the same.
+        let rebuilt =
+            DictionaryLoader::load_dictionary_from_source(&builder, &exported_dir).unwrap();
+        assert!(DictionaryDiff::new(&dictionary, &rebuilt).is_empty());
+        assert_eq!(
+            dictionary.cost_matrix.cost(1, 1),
+            rebuilt.cost_matrix.cost(1, 1)
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_user_dictionary_with_estimation() {
+        let tmp_dir = std::env::temp_dir().join("lindera-dictionary-estimation");
+        let _ = fs::remove_dir_all(&tmp_dir);
+
+        let source_dir = tmp_dir.join("source");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(source_dir.join("char.def"), "DEFAULT 0 1 0\n").unwrap();
+        fs::write(source_dir.join("unk.def"), "DEFAULT,0,0,1000,記号\n").unwrap();
+        fs::write(
+            source_dir.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+        )
+        .unwrap();
+        fs::write(
+            source_dir.join("lex.csv"),
+            "東京,1,1,100,名詞\n京都,1,1,300,名詞\nは,0,0,500,助詞\n",
+        )
+        .unwrap();
+        let schema = DictionarySchema::from_slice(br#"{"fields": ["pos"]}"#).unwrap();
+        let dictionary = DictionaryLoader::load_dictionary_from_source(
+            &SchemaDictionaryBuilder::new(schema),
+            &source_dir,
+        )
+        .unwrap();
+
+        let userdic_file = tmp_dir.join("userdic.csv");
+        fs::write(&userdic_file, "東京都庁,名詞,トウキョウトチョウ\n").unwrap();
+        let config = UserDictionaryConfig {
+            path: userdic_file,
+            kind: Some(DictionaryKind::IPADIC),
+            format: None,
+            schema: None,
+            estimate_cost: true,
+        };
+
+        // The system dictionary is needed to estimate the costs.
+        assert!(DictionaryLoader::load_user_dictionary_from_config(config.clone()).is_err());
+
+        let user_dictionary =
+            DictionaryLoader::load_user_dictionary_from_config_with_dictionary(config, &dictionary)
+                .unwrap();
+        let word_entry = user_dictionary.dict.find_surface("東京都庁")[0];
+        assert_eq!(
+            (
+                word_entry.left_id,
+                word_entry.right_id,
+                word_entry.word_cost
+            ),
+            (1, 1, 200)
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_estimated_cost_beats_split() {
+        let tmp_dir = std::env::temp_dir().join("lindera-dictionary-estimation-split");
+        let _ = fs::remove_dir_all(&tmp_dir);
+
+        // The nouns cost 4550 on average, but the surface of the user entry is split into two
+        // cheap nouns, which cost 200 in total.
+        let source_dir = tmp_dir.join("source");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(source_dir.join("char.def"), "DEFAULT 0 1 0\n").unwrap();
+        fs::write(source_dir.join("unk.def"), "DEFAULT,0,0,1000,記号\n").unwrap();
+        fs::write(
+            source_dir.join("matrix.def"),
+            "2 2\n0 0 0\n0 1 0\n1 0 0\n1 1 0\n",
+        )
+        .unwrap();
+        fs::write(
+            source_dir.join("lex.csv"),
+            "東京,1,1,100,名詞\n都庁,1,1,100,名詞\n京都,1,1,9000,名詞\n大阪,1,1,9000,名詞\n",
+        )
+        .unwrap();
+        let schema = DictionarySchema::from_slice(br#"{"fields": ["pos"]}"#).unwrap();
+        let dictionary = DictionaryLoader::load_dictionary_from_source(
+            &SchemaDictionaryBuilder::new(schema),
+            &source_dir,
+        )
+        .unwrap();
+
+        let userdic_file = tmp_dir.join("userdic.csv");
+        fs::write(&userdic_file, "東京都庁,名詞,トウキョウトチョウ\n").unwrap();
+        let config = UserDictionaryConfig {
+            path: userdic_file,
+            kind: Some(DictionaryKind::IPADIC),
+            format: None,
+            schema: None,
+            estimate_cost: true,
+        };
+        let user_dictionary =
+            DictionaryLoader::load_user_dictionary_from_config_with_dictionary(config, &dictionary)
+                .unwrap();
+        let word_entry = user_dictionary.dict.find_surface("東京都庁")[0];
+        assert!(word_entry.word_cost < 200);
+
+        let mut lattice = Lattice::default();
+        lattice.set_text(
+            &dictionary.dict,
+            &Some(&user_dictionary.dict),
+            &dictionary.char_definitions,
+            &dictionary.unknown_dictionary,
+            "東京都庁",
+            &Mode::Normal,
+        );
+        lattice.calculate_path_costs(&dictionary.cost_matrix, &Mode::Normal);
+        let edges = lattice.token_edges();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].edge_type, EdgeType::USER);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_user_dictionary_without_details() {
+        let tmp_dir = std::env::temp_dir().join("lindera-dictionary-without-details");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        fs::create_dir_all(&tmp_dir).unwrap();
+        let userdic_file = tmp_dir.join("userdic.csv");
+        fs::write(&userdic_file, "東京都庁,名詞,トウキョウトチョウ\n").unwrap();
+        let mut config = UserDictionaryConfig {
+            path: userdic_file,
+            kind: Some(DictionaryKind::IPADIC),
+            format: None,
+            schema: None,
+            estimate_cost: false,
+        };
+
+        let user_dictionary =
+            DictionaryLoader::load_user_dictionary_from_config_without_details(config.clone())
+                .unwrap();
+        assert_eq!(user_dictionary.dict.find_surface("東京都庁").len(), 1);
+        assert!(user_dictionary.words_idx_data.is_empty());
+        assert_eq!(user_dictionary.word_details(0), None);
+
+        // The details of a built user dictionary are not decoded.
+        let userdic_bin = tmp_dir.join("userdic.bin");
+        build_user_dictionary(
+            DictionaryLoader::load_user_dictionary_from_config(config.clone()).unwrap(),
+            &userdic_bin,
+        )
+        .unwrap();
+        let user_dictionary = DictionaryLoader::load_user_dictionary_from_config_without_details(
+            UserDictionaryConfig {
+                path: userdic_bin,
+                ..config.clone()
+            },
+        )
+        .unwrap();
+        assert_eq!(user_dictionary.dict.find_surface("東京都庁").len(), 1);
+        assert!(user_dictionary.words_data.is_empty());
+
+        // The costs cannot be estimated without the details.
+        config.estimate_cost = true;
+        assert!(
+            DictionaryLoader::load_user_dictionary_from_config_without_details(config).is_err()
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
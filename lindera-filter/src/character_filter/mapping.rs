@@ -173,7 +173,7 @@ mod tests {
         }
         "#;
         let result = MappingCharacterFilter::from_slice(config_str.as_bytes());
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -219,7 +219,7 @@ mod tests {
             "#;
             let filter = MappingCharacterFilter::from_slice(config_str.as_bytes()).unwrap();
             let text = "ﾘﾝﾃﾞﾗ";
-            let (filterd_text, offsets, diffs) = filter.apply(&text).unwrap();
+            let (filterd_text, offsets, diffs) = filter.apply(text).unwrap();
             assert_eq!("リンデラ", filterd_text);
             assert_eq!(vec![9], offsets);
             assert_eq!(vec![3], diffs);
@@ -129,7 +129,7 @@ mod tests {
         }
         "#;
         let result = RegexCharacterFilterConfig::from_slice(config_str.as_bytes());
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -1,7 +1,14 @@
 use serde::Serialize;
 
+use lindera_core::features::Features;
+use lindera_core::offsets::TokenOffsets;
+use lindera_core::viterbi::EdgeType;
 use lindera_core::word_entry::WordId;
 
+/// The tag that matches the unknown words in the part-of-speech tag filters. It was the only
+/// detail of the unknown words before they had the features of `unk.def`.
+pub(crate) const UNKNOWN_TAG: &str = "UNK";
+
 #[derive(Serialize, Clone)]
 pub struct Token {
     /// Text content of the token.
@@ -22,7 +29,34 @@ pub struct Token {
     /// The ID of the word and a flag to indicate whether the word is registered in the dictionary.
     pub word_id: WordId,
 
+    /// The offsets in characters, UTF-16 code units and grapheme clusters,
+    /// if the tokenizer computes them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offsets: Option<TokenOffsets>,
+
+    /// The kind of the lattice edge of the token, which tells the words of the system
+    /// dictionary, the user dictionary and the unknown words apart.
+    pub edge_type: EdgeType,
+
     /// Detailes about the token.
     /// It contains metadata for tokens, such as part-of-speech information.
     pub details: Vec<String>,
 }
+
+impl Token {
+    /// Returns whether the word is not in the dictionaries.
+    pub fn is_unknown(&self) -> bool {
+        self.edge_type == EdgeType::UNKNOWN
+    }
+
+    /// Returns whether the word is in the user dictionary.
+    pub fn is_user(&self) -> bool {
+        self.edge_type == EdgeType::USER
+    }
+
+    /// Returns a typed view of the details for the layout of a dictionary, such as
+    /// `IpadicFeatures`.
+    pub fn features<'a, F: Features<'a>>(&'a self) -> F {
+        F::from_details(&self.details)
+    }
+}
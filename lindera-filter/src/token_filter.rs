@@ -81,6 +81,12 @@ use crate::token_filter::uppercase::{UppercaseTokenFilter, UPPERCASE_TOKEN_FILTE
 pub trait TokenFilter: 'static + Send + Sync + TokenFilterClone {
     fn name(&self) -> &str;
     fn apply(&self, tokens: &mut Vec<Token>) -> LinderaResult<()>;
+
+    /// Returns whether the filter reads the details of the tokens, so that it cannot be used
+    /// with a tokenizer that only segments the text.
+    fn uses_details(&self) -> bool {
+        false
+    }
 }
 
 pub struct BoxTokenFilter(Box<dyn TokenFilter + 'static + Send + Sync>);
@@ -56,9 +56,13 @@ impl TokenFilter for JapaneseBaseFormTokenFilter {
         JAPANESE_BASE_FORM_TOKEN_FILTER_NAME
     }
 
+    fn uses_details(&self) -> bool {
+        true
+    }
+
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
-            if token.details[0] == "UNK" {
+            if token.is_unknown() {
                 // NOOP
                 continue;
             }
@@ -87,6 +91,8 @@ impl TokenFilter for JapaneseBaseFormTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
     use lindera_core::word_entry::WordId;
     #[cfg(all(any(feature = "ipadic", feature = "unidic",), feature = "filter"))]
@@ -171,6 +177,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(321702, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -190,6 +198,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(53041, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "格助詞".to_string(),
@@ -209,6 +219,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(3222, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "動詞".to_string(),
                     "自立".to_string(),
@@ -228,6 +240,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(68730, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助動詞".to_string(),
                     "*".to_string(),
@@ -269,6 +283,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(618177, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -296,6 +312,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(587348, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "普通名詞".to_string(),
@@ -323,6 +341,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(106480, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "格助詞".to_string(),
@@ -350,6 +370,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(6075, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "動詞".to_string(),
                     "非自立可能".to_string(),
@@ -377,6 +399,8 @@ mod tests {
                 position: 4,
                 position_length: 1,
                 word_id: WordId(140895, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助動詞".to_string(),
                     "*".to_string(),
@@ -94,6 +94,11 @@ impl JapaneseCompoundWordTokenFilter {
     fn concat_token(&self, token1: &mut Token, token2: &Token) {
         token1.text = format!("{}{}", token1.text, token2.text);
         token1.byte_end = token2.byte_end;
+        if let (Some(offsets1), Some(offsets2)) = (token1.offsets.as_mut(), token2.offsets) {
+            offsets1.char_end = offsets2.char_end;
+            offsets1.utf16_end = offsets2.utf16_end;
+            offsets1.grapheme_end = offsets2.grapheme_end;
+        }
         token1.position_length += token2.position_length;
 
         let mut formatted_details = match self.config.kind {
@@ -128,6 +133,10 @@ impl TokenFilter for JapaneseCompoundWordTokenFilter {
         JAPANESE_COMPOUND_WORD_TOKEN_FILTER_NAME
     }
 
+    fn uses_details(&self) -> bool {
+        true
+    }
+
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         let mut new_tokens = Vec::new();
         let mut compound_token_opt = None;
@@ -183,6 +192,8 @@ impl TokenFilter for JapaneseCompoundWordTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
@@ -258,6 +269,8 @@ mod tests {
                     position: 0,
                     position_length: 1,
                     word_id: WordId(391174, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "数".to_string(),
@@ -277,6 +290,8 @@ mod tests {
                     position: 1,
                     position_length: 1,
                     word_id: WordId(391171, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "数".to_string(),
@@ -296,6 +311,8 @@ mod tests {
                     position: 2,
                     position_length: 1,
                     word_id: WordId(391171, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "数".to_string(),
@@ -315,6 +332,8 @@ mod tests {
                     position: 3,
                     position_length: 1,
                     word_id: WordId(137904, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "接尾".to_string(),
@@ -334,6 +353,8 @@ mod tests {
                     position: 4,
                     position_length: 1,
                     word_id: WordId(287427, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "接尾".to_string(),
@@ -353,6 +374,8 @@ mod tests {
                     position: 5,
                     position_length: 1,
                     word_id: WordId(80582, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "接尾".to_string(),
@@ -372,6 +395,8 @@ mod tests {
                     position: 6,
                     position_length: 1,
                     word_id: WordId(228047, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "動詞".to_string(),
                         "自立".to_string(),
@@ -82,6 +82,8 @@ impl TokenFilter for JapaneseKanaTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
@@ -124,7 +126,7 @@ mod tests {
         "#;
         let result = JapaneseKanaTokenFilter::from_slice(config_str.as_bytes());
 
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -136,7 +138,7 @@ mod tests {
         "#;
         let result = JapaneseKanaTokenFilter::from_slice(config_str.as_bytes());
 
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -157,6 +159,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(321702, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -176,6 +180,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(374175, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "サ変接続".to_string(),
@@ -195,6 +201,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(4294967295, true),
+                offsets: None,
+                edge_type: EdgeType::UNKNOWN,
                 details: vec!["UNK".to_string()],
             },
         ];
@@ -225,6 +233,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(171030, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -244,6 +254,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(298064, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
@@ -263,6 +275,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(28502, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -282,6 +296,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(202045, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
@@ -323,6 +339,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(321702, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -342,6 +360,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(374175, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "サ変接続".to_string(),
@@ -361,6 +381,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(4294967295, true),
+                offsets: None,
+                edge_type: EdgeType::UNKNOWN,
                 details: vec!["UNK".to_string()],
             },
         ];
@@ -391,6 +413,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(171030, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -410,6 +434,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(298064, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
@@ -429,6 +455,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(28502, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -448,6 +476,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(202045, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
@@ -489,6 +519,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(250023, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -508,6 +540,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(364736, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
@@ -527,6 +561,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(927, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -546,6 +582,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(202045, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
@@ -587,6 +625,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(151151, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -606,6 +646,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(166998, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -625,6 +667,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(383791, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
@@ -102,6 +102,8 @@ fn is_katakana(text: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
@@ -187,6 +189,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(94843, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -206,6 +210,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(100137, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -101,6 +101,10 @@ impl TokenFilter for JapaneseNumberTokenFilter {
         JAPANESE_NUMBER_TOKEN_FILTER_NAME
     }
 
+    fn uses_details(&self) -> bool {
+        true
+    }
+
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
             let mut tag_vec = ["*", "*", "*", "*"];
@@ -293,6 +297,9 @@ mod tests {
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    use lindera_core::viterbi::EdgeType;
+
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     use crate::{
         token::Token,
@@ -934,6 +941,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(102657, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "数".to_string(),
@@ -961,6 +970,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(102657, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "数".to_string(),
@@ -990,6 +1001,8 @@ mod tests {
                         position: 0,
                         position_length: 1,
                         word_id: WordId(102657, true),
+                        offsets: None,
+                        edge_type: EdgeType::KNOWN,
                         details: vec![
                             "名詞".to_string(),
                             "数".to_string(),
@@ -1019,6 +1032,8 @@ mod tests {
                     position: 0,
                     position_length: 1,
                     word_id: WordId(368893, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "固有名詞".to_string(),
@@ -1038,6 +1053,8 @@ mod tests {
                     position: 0,
                     position_length: 1,
                     word_id: WordId(103913, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "固有名詞".to_string(),
@@ -1077,6 +1094,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(102657, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "数".to_string(),
@@ -1104,6 +1123,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(102657, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "数".to_string(),
@@ -1133,6 +1154,8 @@ mod tests {
                         position: 0,
                         position_length: 1,
                         word_id: WordId(102657, true),
+                        offsets: None,
+                        edge_type: EdgeType::KNOWN,
                         details: vec![
                             "名詞".to_string(),
                             "数".to_string(),
@@ -1162,6 +1185,8 @@ mod tests {
                     position: 0,
                     position_length: 1,
                     word_id: WordId(368893, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "固有名詞".to_string(),
@@ -1181,6 +1206,8 @@ mod tests {
                     position: 0,
                     position_length: 1,
                     word_id: WordId(103913, true),
+                    offsets: None,
+                    edge_type: EdgeType::KNOWN,
                     details: vec![
                         "名詞".to_string(),
                         "固有名詞".to_string(),
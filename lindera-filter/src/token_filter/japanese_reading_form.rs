@@ -55,9 +55,13 @@ impl TokenFilter for JapaneseReadingFormTokenFilter {
         JAPANESE_READING_FORM_TOKEN_FILTER_NAME
     }
 
+    fn uses_details(&self) -> bool {
+        true
+    }
+
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
-            if token.details[0] == "UNK" {
+            if token.is_unknown() {
                 // NOOP
                 continue;
             }
@@ -95,6 +99,11 @@ mod tests {
         all(feature = "ipadic", feature = "filter",),
         all(feature = "unidic", feature = "filter",)
     ))]
+    use lindera_core::viterbi::EdgeType;
+    #[cfg(any(
+        all(feature = "ipadic", feature = "filter",),
+        all(feature = "unidic", feature = "filter",)
+    ))]
     use lindera_core::word_entry::WordId;
     #[cfg(any(
         all(feature = "ipadic", feature = "filter",),
@@ -185,6 +194,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(321702, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -204,6 +215,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(374175, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "サ変接続".to_string(),
@@ -223,6 +236,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(4294967295, true),
+                offsets: None,
+                edge_type: EdgeType::UNKNOWN,
                 details: vec!["UNK".to_string()],
             },
         ];
@@ -253,6 +268,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(618177, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -280,6 +297,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(587348, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "普通名詞".to_string(),
@@ -307,6 +326,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(720499, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "普通名詞".to_string(),
@@ -334,6 +355,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(216230, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "普通名詞".to_string(),
@@ -361,6 +384,8 @@ mod tests {
                 position: 4,
                 position_length: 1,
                 word_id: WordId(223781, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "普通名詞".to_string(),
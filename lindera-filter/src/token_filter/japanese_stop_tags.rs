@@ -6,7 +6,7 @@ use serde_json::Value;
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
-use crate::token::Token;
+use crate::token::{Token, UNKNOWN_TAG};
 use crate::token_filter::TokenFilter;
 
 pub const JAPANESE_STOP_TAGS_TOKEN_FILTER_NAME: &str = "japanese_stop_tags";
@@ -59,6 +59,18 @@ impl JapaneseStopTagsTokenFilterConfig {
             .collect::<LinderaResult<HashSet<String>>>()?;
         Ok(Self::new(tags))
     }
+
+    /// Returns whether the part-of-speech of the token is one of the tags. An unknown word also
+    /// matches `UNK`.
+    fn contains(&self, token: &Token) -> bool {
+        let mut formatted_tags = ["*", "*", "*", "*"];
+        let tags_len = if token.details.len() >= 4 { 4 } else { 1 };
+        for (i, j) in token.details[0..tags_len].iter().enumerate() {
+            formatted_tags[i] = j;
+        }
+        self.tags.contains(&formatted_tags.join(","))
+            || (token.is_unknown() && self.tags.contains(&format!("{},*,*,*", UNKNOWN_TAG)))
+    }
 }
 
 /// Remove tokens with the specified part-of-speech tag.
@@ -85,15 +97,12 @@ impl TokenFilter for JapaneseStopTagsTokenFilter {
         JAPANESE_STOP_TAGS_TOKEN_FILTER_NAME
     }
 
+    fn uses_details(&self) -> bool {
+        true
+    }
+
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
-        tokens.retain(|token| {
-            let mut formatted_tags = ["*", "*", "*", "*"];
-            let tags_len = if token.details.len() >= 4 { 4 } else { 1 };
-            for (i, j) in token.details[0..tags_len].iter().enumerate() {
-                formatted_tags[i] = j;
-            }
-            !self.config.tags.contains(&formatted_tags.join(","))
-        });
+        tokens.retain(|token| !self.config.contains(token));
 
         Ok(())
     }
@@ -101,6 +110,8 @@ impl TokenFilter for JapaneseStopTagsTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(feature = "ipadic", feature = "filter"))]
     use lindera_core::word_entry::WordId;
 
@@ -235,6 +246,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(36165, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -254,6 +267,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(73246, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "係助詞".to_string(),
@@ -273,6 +288,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(74990, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -292,6 +309,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(73246, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "係助詞".to_string(),
@@ -311,6 +330,8 @@ mod tests {
                 position: 4,
                 position_length: 1,
                 word_id: WordId(74990, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -330,6 +351,8 @@ mod tests {
                 position: 5,
                 position_length: 1,
                 word_id: WordId(55831, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "連体化".to_string(),
@@ -349,6 +372,8 @@ mod tests {
                 position: 6,
                 position_length: 1,
                 word_id: WordId(8029, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "非自立".to_string(),
@@ -371,4 +396,60 @@ mod tests {
         assert_eq!(&tokens[2].text, "もも");
         assert_eq!(&tokens[3].text, "うち");
     }
+
+    #[test]
+    #[cfg(all(feature = "ipadic", feature = "filter"))]
+    fn test_japanese_stop_tags_token_filter_apply_unknown_ipadic() {
+        let filter =
+            JapaneseStopTagsTokenFilter::from_slice(r#"{"tags": ["UNK"]}"#.as_bytes()).unwrap();
+
+        // The unknown words have the features of unk.def, and still match UNK.
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "すもも".to_string(),
+                byte_start: 0,
+                byte_end: 9,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(36165, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
+                details: vec![
+                    "名詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "すもも".to_string(),
+                    "スモモ".to_string(),
+                    "スモモ".to_string(),
+                ],
+            },
+            Token {
+                text: "ｘｙｚ".to_string(),
+                byte_start: 9,
+                byte_end: 18,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(u32::MAX, true),
+                offsets: None,
+                edge_type: EdgeType::UNKNOWN,
+                details: vec![
+                    "名詞".to_string(),
+                    "一般".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(&tokens[0].text, "すもも");
+    }
 }
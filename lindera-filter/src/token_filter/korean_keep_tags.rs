@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use lindera_core::error::LinderaErrorKind;
 use lindera_core::LinderaResult;
 
-use crate::token::Token;
+use crate::token::{Token, UNKNOWN_TAG};
 use crate::token_filter::TokenFilter;
 
 pub const KOREAN_KEEP_TAGS_TOKEN_FILTER_NAME: &str = "korean_keep_tags";
@@ -29,6 +29,13 @@ impl KoreanKeepTagsTokenFilterConfig {
         serde_json::from_value::<KoreanKeepTagsTokenFilterConfig>(value.clone())
             .map_err(|err| LinderaErrorKind::Deserialize.with_error(err))
     }
+
+    /// Returns whether the part-of-speech of the token is one of the tags. An unknown word also
+    /// matches `UNK`.
+    fn contains(&self, token: &Token) -> bool {
+        self.tags.contains(&token.details[0])
+            || (token.is_unknown() && self.tags.contains(UNKNOWN_TAG))
+    }
 }
 
 /// Keep only tokens with the specified part-of-speech tag.
@@ -55,8 +62,12 @@ impl TokenFilter for KoreanKeepTagsTokenFilter {
         KOREAN_KEEP_TAGS_TOKEN_FILTER_NAME
     }
 
+    fn uses_details(&self) -> bool {
+        true
+    }
+
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
-        tokens.retain(|token| self.config.tags.contains(&token.details[0]));
+        tokens.retain(|token| self.config.contains(token));
 
         Ok(())
     }
@@ -64,6 +75,8 @@ impl TokenFilter for KoreanKeepTagsTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ko-dic", feature = "filter",))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(feature = "ko-dic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
@@ -126,6 +139,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(770060, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "*".to_string(),
@@ -144,6 +159,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(576336, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "JKG".to_string(),
                     "*".to_string(),
@@ -162,6 +179,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(787807, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "*".to_string(),
@@ -180,6 +199,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(383955, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "행위".to_string(),
@@ -198,6 +219,8 @@ mod tests {
                 position: 4,
                 position_length: 1,
                 word_id: WordId(574939, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "JKO".to_string(),
                     "*".to_string(),
@@ -216,6 +239,8 @@ mod tests {
                 position: 5,
                 position_length: 1,
                 word_id: WordId(774117, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "VV+ETM".to_string(),
                     "*".to_string(),
@@ -234,6 +259,8 @@ mod tests {
                 position: 6,
                 position_length: 1,
                 word_id: WordId(444151, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "*".to_string(),
@@ -252,6 +279,8 @@ mod tests {
                 position: 7,
                 position_length: 1,
                 word_id: WordId(602850, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "VX".to_string(),
                     "*".to_string(),
@@ -270,6 +299,8 @@ mod tests {
                 position: 8,
                 position_length: 1,
                 word_id: WordId(458024, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "EF".to_string(),
                     "*".to_string(),
@@ -291,4 +322,60 @@ mod tests {
         assert_eq!(&tokens[2].text, "분석");
         assert_eq!(&tokens[3].text, "수");
     }
+
+    #[test]
+    #[cfg(all(feature = "ko-dic", feature = "filter",))]
+    fn test_korean_keep_tags_token_filter_apply_unknown() {
+        let filter =
+            KoreanKeepTagsTokenFilter::from_slice(r#"{"tags": ["UNK"]}"#.as_bytes()).unwrap();
+
+        // The unknown words have the features of unk.def, and still match UNK.
+        let mut tokens: Vec<Token> = vec![
+            Token {
+                text: "한국어".to_string(),
+                byte_start: 0,
+                byte_end: 9,
+                position: 0,
+                position_length: 1,
+                word_id: WordId(770, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
+                details: vec![
+                    "NNG".to_string(),
+                    "*".to_string(),
+                    "F".to_string(),
+                    "한국어".to_string(),
+                    "Compound".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "한국/NNG/*+어/NNG/*".to_string(),
+                ],
+            },
+            Token {
+                text: "ｘｙｚ".to_string(),
+                byte_start: 9,
+                byte_end: 18,
+                position: 1,
+                position_length: 1,
+                word_id: WordId(u32::MAX, true),
+                offsets: None,
+                edge_type: EdgeType::UNKNOWN,
+                details: vec![
+                    "SL".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                    "*".to_string(),
+                ],
+            },
+        ];
+
+        filter.apply(&mut tokens).unwrap();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(&tokens[0].text, "ｘｙｚ");
+    }
 }
@@ -27,9 +27,13 @@ impl TokenFilter for KoreanReadingFormTokenFilter {
         KOREAN_READING_FORM_TOKEN_FILTER_NAME
     }
 
+    fn uses_details(&self) -> bool {
+        true
+    }
+
     fn apply<'a>(&self, tokens: &mut Vec<Token>) -> LinderaResult<()> {
         for token in tokens.iter_mut() {
-            if token.details[0] != "UNK" {
+            if !token.is_unknown() {
                 token.text = token.details[3].to_string();
             }
         }
@@ -40,6 +44,8 @@ impl TokenFilter for KoreanReadingFormTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ko-dic", feature = "filter",))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(feature = "ko-dic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
@@ -62,6 +68,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(770060, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "*".to_string(),
@@ -80,6 +88,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(576336, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "JKG".to_string(),
                     "*".to_string(),
@@ -98,6 +108,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(787807, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "*".to_string(),
@@ -116,6 +128,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(383955, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "행위".to_string(),
@@ -134,6 +148,8 @@ mod tests {
                 position: 4,
                 position_length: 1,
                 word_id: WordId(574939, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "JKO".to_string(),
                     "*".to_string(),
@@ -152,6 +168,8 @@ mod tests {
                 position: 5,
                 position_length: 1,
                 word_id: WordId(774117, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "VV+ETM".to_string(),
                     "*".to_string(),
@@ -170,6 +188,8 @@ mod tests {
                 position: 6,
                 position_length: 1,
                 word_id: WordId(444151, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "NNG".to_string(),
                     "*".to_string(),
@@ -188,6 +208,8 @@ mod tests {
                 position: 7,
                 position_length: 1,
                 word_id: WordId(602850, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "VX".to_string(),
                     "*".to_string(),
@@ -206,6 +228,8 @@ mod tests {
                 position: 8,
                 position_length: 1,
                 word_id: WordId(458024, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "EF".to_string(),
                     "*".to_string(),
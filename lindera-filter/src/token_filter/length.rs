@@ -74,6 +74,8 @@ impl TokenFilter for LengthTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(feature = "ipadic")]
     use lindera_core::word_entry::WordId;
 
@@ -125,7 +127,7 @@ mod tests {
             "#;
         let result = LengthTokenFilter::from_slice(config_str.as_bytes());
 
-        assert_eq!(result.is_ok(), true);
+        assert!(result.is_ok());
 
         let config_str = r#"
             {
@@ -134,7 +136,7 @@ mod tests {
             "#;
         let result = LengthTokenFilter::from_slice(config_str.as_bytes());
 
-        assert_eq!(result.is_ok(), true);
+        assert!(result.is_ok());
 
         let config_str = r#"
             {
@@ -143,7 +145,7 @@ mod tests {
             "#;
         let result = LengthTokenFilter::from_slice(config_str.as_bytes());
 
-        assert_eq!(result.is_ok(), true);
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -165,6 +167,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(36165, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -184,6 +188,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(73246, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "係助詞".to_string(),
@@ -203,6 +209,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(74990, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -222,6 +230,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(73246, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "係助詞".to_string(),
@@ -241,6 +251,8 @@ mod tests {
                 position: 4,
                 position_length: 1,
                 word_id: WordId(74990, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -260,6 +272,8 @@ mod tests {
                 position: 5,
                 position_length: 1,
                 word_id: WordId(55831, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "連体化".to_string(),
@@ -279,6 +293,8 @@ mod tests {
                 position: 6,
                 position_length: 1,
                 word_id: WordId(8029, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "非自立".to_string(),
@@ -38,6 +38,8 @@ impl TokenFilter for LowercaseTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(feature = "ipadic")]
     use lindera_core::word_entry::WordId;
 
@@ -59,6 +61,8 @@ mod tests {
             position: 0,
             position_length: 1,
             word_id: WordId(4294967295, true),
+            offsets: None,
+            edge_type: EdgeType::UNKNOWN,
             details: vec!["UNK".to_string()],
         }];
 
@@ -115,6 +115,8 @@ impl TokenFilter for MappingTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(all(feature = "ipadic", feature = "filter",))]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(all(feature = "ipadic", feature = "filter",))]
     use lindera_core::word_entry::WordId;
 
@@ -153,7 +155,7 @@ mod tests {
         }
         "#;
         let result = MappingTokenFilter::from_slice(config_str.as_bytes());
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -176,6 +178,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(312630, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "固有名詞".to_string(),
@@ -195,6 +199,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(383791, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "接尾".to_string(),
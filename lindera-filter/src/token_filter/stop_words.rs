@@ -62,6 +62,8 @@ impl TokenFilter for StopWordsTokenFilter {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "ipadic")]
+    use lindera_core::viterbi::EdgeType;
     #[cfg(feature = "ipadic")]
     use lindera_core::word_entry::WordId;
 
@@ -96,7 +98,7 @@ mod tests {
             "#;
         let result = StopWordsTokenFilter::from_slice(config_str.as_bytes());
 
-        assert_eq!(true, result.is_ok());
+        assert!(result.is_ok());
     }
 
     #[test]
@@ -120,6 +122,8 @@ mod tests {
                 position: 0,
                 position_length: 1,
                 word_id: WordId(36165, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -139,6 +143,8 @@ mod tests {
                 position: 1,
                 position_length: 1,
                 word_id: WordId(73246, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "係助詞".to_string(),
@@ -158,6 +164,8 @@ mod tests {
                 position: 2,
                 position_length: 1,
                 word_id: WordId(74990, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -177,6 +185,8 @@ mod tests {
                 position: 3,
                 position_length: 1,
                 word_id: WordId(73246, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "係助詞".to_string(),
@@ -196,6 +206,8 @@ mod tests {
                 position: 4,
                 position_length: 1,
                 word_id: WordId(74990, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "一般".to_string(),
@@ -215,6 +227,8 @@ mod tests {
                 position: 5,
                 position_length: 1,
                 word_id: WordId(55831, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "助詞".to_string(),
                     "連体化".to_string(),
@@ -234,6 +248,8 @@ mod tests {
                 position: 6,
                 position_length: 1,
                 word_id: WordId(8029, true),
+                offsets: None,
+                edge_type: EdgeType::KNOWN,
                 details: vec![
                     "名詞".to_string(),
                     "非自立".to_string(),
@@ -1,22 +1,30 @@
-use std::{fs, path::Path};
+use std::{fs, io::Read, path::Path};
 
 use lindera_core::{
-    character_definition::CharacterDefinitions, dictionary::UserDictionary,
-    dictionary_builder::DictionaryBuilder, error::LinderaErrorKind, LinderaResult,
+    character_definition::CharacterDefinitions,
+    dictionary::{Dictionary, UserDictionary},
+    dictionary_builder::DictionaryBuilder,
+    error::LinderaErrorKind,
+    id_definition::IdDefinitions,
+    user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat},
+    validation::ValidationReport,
+    LinderaResult,
 };
 use lindera_decompress::Algorithm;
 use lindera_dictionary_builder::{
-    build_user_dictionary, CharDefBuilderOptions, CostMatrixBuilderOptions, DictBuilderOptions,
+    build_user_dictionary, CharDefBuilderOptions, CostEstimator, CostMatrixBuilderOptions,
+    DictBuilderOptions, DictionaryExporterOptions, DictionaryValidatorOptions, IdDefBuilderOptions,
     UnkBuilderOptions, UserDictBuilderOptions,
 };
 
 const SIMPLE_USERDIC_FIELDS_NUM: usize = 3;
 const SIMPLE_WORD_COST: i16 = -10000;
 const SIMPLE_CONTEXT_ID: u16 = 0;
+const SIMPLE_USERDIC_POS_FIELDS_NUM: usize = 4;
 const DETAILED_USERDIC_FIELDS_NUM: usize = 13;
 const COMPRESS_ALGORITHM: Algorithm = Algorithm::Deflate;
 const UNK_FIELDS_NUM: usize = 11;
-const ENCODING: &'static str = "EUC-JP";
+const ENCODING: &str = "EUC-JP";
 
 pub struct IpadicBuilder {}
 
@@ -24,6 +32,28 @@ impl IpadicBuilder {
     pub fn new() -> Self {
         IpadicBuilder {}
     }
+
+    fn user_dict_builder_options(&self) -> UserDictBuilderOptions {
+        UserDictBuilderOptions::default()
+            .simple_userdic_fields_num(SIMPLE_USERDIC_FIELDS_NUM)
+            .detailed_userdic_fields_num(DETAILED_USERDIC_FIELDS_NUM)
+            .simple_word_cost(SIMPLE_WORD_COST)
+            .simple_context_id(SIMPLE_CONTEXT_ID)
+            .flexible_csv(true)
+            .simple_userdic_details_handler(Box::new(|row| {
+                Ok(vec![
+                    row[1].to_string(), // POS
+                    "*".to_string(),    // POS subcategory 1
+                    "*".to_string(),    // POS subcategory 2
+                    "*".to_string(),    // POS subcategory 3
+                    "*".to_string(),    // Conjugation type
+                    "*".to_string(),    // Conjugation form
+                    row[0].to_string(), // Base form
+                    row[2].to_string(), // Reading
+                    "*".to_string(),    // Pronunciation
+                ])
+            }))
+    }
 }
 
 impl Default for IpadicBuilder {
@@ -41,6 +71,7 @@ impl DictionaryBuilder for IpadicBuilder {
         self.build_unk(input_dir, &chardef, output_dir)?;
         self.build_dict(input_dir, output_dir)?;
         self.build_cost_matrix(input_dir, output_dir)?;
+        self.build_id_def(input_dir, output_dir)?;
 
         Ok(())
     }
@@ -50,6 +81,18 @@ impl DictionaryBuilder for IpadicBuilder {
         build_user_dictionary(user_dict, output_file)
     }
 
+    fn build_user_dictionary_with_estimation(
+        &self,
+        input_file: &Path,
+        output_file: &Path,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<()> {
+        let format =
+            UserDictionaryFormat::from_path(input_file).unwrap_or(UserDictionaryFormat::Csv);
+        let user_dict = self.build_user_dict_with_estimation(input_file, format, dictionary)?;
+        build_user_dictionary(user_dict, output_file)
+    }
+
     fn build_chardef(
         &self,
         input_dir: &Path,
@@ -95,31 +138,103 @@ impl DictionaryBuilder for IpadicBuilder {
             .compress_algorithm(COMPRESS_ALGORITHM)
             .builder()
             .unwrap()
-            .build(&input_dir, output_dir)
+            .build(input_dir, output_dir)
+    }
+
+    fn build_id_def(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<IdDefinitions> {
+        IdDefBuilderOptions::default()
+            .encoding(ENCODING)
+            .compress_algorithm(COMPRESS_ALGORITHM)
+            .builder()
+            .unwrap()
+            .build(input_dir, output_dir)
     }
 
     fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
-        UserDictBuilderOptions::default()
-            .simple_userdic_fields_num(SIMPLE_USERDIC_FIELDS_NUM)
-            .detailed_userdic_fields_num(DETAILED_USERDIC_FIELDS_NUM)
-            .simple_word_cost(SIMPLE_WORD_COST)
-            .simple_context_id(SIMPLE_CONTEXT_ID)
-            .flexible_csv(true)
-            .simple_userdic_details_handler(Box::new(|row| {
-                Ok(vec![
-                    row[1].to_string(), // POS
-                    "*".to_string(),    // POS subcategory 1
-                    "*".to_string(),    // POS subcategory 2
-                    "*".to_string(),    // POS subcategory 3
-                    "*".to_string(),    // Conjugation type
-                    "*".to_string(),    // Conjugation form
-                    row[0].to_string(), // Base form
-                    row[2].to_string(), // Reading
-                    "*".to_string(),    // Pronunciation
-                ])
-            }))
+        self.user_dict_builder_options()
             .builder()
             .unwrap()
             .build(input_file)
     }
+
+    fn build_user_dict_with_format(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build_with_format(input_file, format)
+    }
+
+    fn build_user_dict_from_entries(
+        &self,
+        entries: Vec<UserDictionaryEntry>,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build_from_entries(entries)
+    }
+
+    fn build_user_dict_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build_from_reader(reader, format)
+    }
+
+    fn build_user_dict_with_estimation(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .simple_userdic_estimator(CostEstimator::from_dictionary(
+                dictionary,
+                SIMPLE_USERDIC_POS_FIELDS_NUM,
+            ))
+            .id_definitions(dictionary.id_definitions.clone())
+            .builder()
+            .unwrap()
+            .build_with_format(input_file, format)
+    }
+
+    fn build_user_dict_without_details(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .details(false)
+            .builder()
+            .unwrap()
+            .build_with_format(input_file, format)
+    }
+
+    fn export_dictionary(&self, dictionary: &Dictionary, output_dir: &Path) -> LinderaResult<()> {
+        DictionaryExporterOptions::default()
+            .encoding(ENCODING)
+            .unk_fields_num(UNK_FIELDS_NUM)
+            .builder()
+            .unwrap()
+            .export(dictionary, output_dir)
+    }
+
+    fn validate_dictionary(&self, input_dir: &Path) -> LinderaResult<ValidationReport> {
+        DictionaryValidatorOptions::default()
+            .encoding(ENCODING)
+            .fields_num(DETAILED_USERDIC_FIELDS_NUM)
+            .unk_fields_num(UNK_FIELDS_NUM)
+            .normalize_details(true)
+            .builder()
+            .unwrap()
+            .validate(input_dir)
+    }
 }
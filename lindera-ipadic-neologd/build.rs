@@ -9,6 +9,8 @@ fn main() -> Result<(), Box<dyn Error>> {
             output_dir: "lindera-ipadic-neologd",
             download_url:
                 "https://dlwqk3ibdg1xh.cloudfront.net/mecab-ipadic-neologd-0.0.7-20200820.tar.gz",
+            env_prefix: "LINDERA_IPADIC_NEOLOGD",
+            sha256: None,
             dummy_input: "テスト,1288,1288,-1000,名詞,固有名詞,一般,*,*,*,*,*,*\n",
         },
         lindera_ipadic_neologd_builder::ipadic_neologd_builder::IpadicNeologdBuilder::new(),
@@ -8,6 +8,8 @@ fn main() -> Result<(), Box<dyn Error>> {
             input_dir: "mecab-ipadic-2.7.0-20070801",
             output_dir: "lindera-ipadic",
             download_url: "https://dlwqk3ibdg1xh.cloudfront.net/mecab-ipadic-2.7.0-20070801.tar.gz",
+            env_prefix: "LINDERA_IPADIC",
+            sha256: Some("b62f527d881c504576baed9c6ef6561554658b175ce6ae0096a60307e49e3523"),
             dummy_input: "テスト,1288,1288,-1000,名詞,固有名詞,一般,*,*,*,*,*,*\n",
         },
         lindera_ipadic_builder::ipadic_builder::IpadicBuilder::new(),
@@ -8,6 +8,8 @@ fn main() -> Result<(), Box<dyn Error>> {
             input_dir: "mecab-ko-dic-2.1.1-20180720",
             output_dir: "lindera-ko-dic",
             download_url: "https://dlwqk3ibdg1xh.cloudfront.net/mecab-ko-dic-2.1.1-20180720.tar.gz",
+            env_prefix: "LINDERA_KO_DIC",
+            sha256: None,
             dummy_input: "테스트,1785,3543,4721,NNG,행위,F,테스트,*,*,*,*\n",
         },
         lindera_ko_dic_builder::ko_dic_builder::KoDicBuilder::new(),
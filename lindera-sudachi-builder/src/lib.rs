@@ -0,0 +1 @@
+pub mod sudachi_builder;
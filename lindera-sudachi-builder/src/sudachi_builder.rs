@@ -0,0 +1,526 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use csv::StringRecord;
+use glob::glob;
+use log::debug;
+
+use lindera_core::{
+    character_definition::CharacterDefinitions,
+    dictionary::{Dictionary, UserDictionary},
+    dictionary_builder::DictionaryBuilder,
+    error::{ErrorLocation, LinderaErrorKind},
+    id_definition::IdDefinitions,
+    user_dictionary_source::{UserDictionaryEntry, UserDictionaryFormat},
+    validation::ValidationReport,
+    LinderaResult,
+};
+use lindera_decompress::Algorithm;
+use lindera_dictionary_builder::{
+    build_user_dictionary,
+    utils::{decode_with_encoding, read_file_with_encoding},
+    CharDefBuilderOptions, CostEstimator, CostMatrixBuilderOptions, DictBuilderOptions,
+    DictionaryExporterOptions, DictionaryValidatorOptions, IdDefBuilderOptions, UnkBuilderOptions,
+    UserDictBuilderOptions,
+};
+
+const SIMPLE_USERDIC_FIELDS_NUM: usize = 3;
+const SIMPLE_WORD_COST: i16 = -10000;
+const SIMPLE_CONTEXT_ID: u16 = 0;
+const SIMPLE_USERDIC_POS_FIELDS_NUM: usize = 4;
+const DETAILED_USERDIC_FIELDS_NUM: usize = 16;
+/// The number of fields of a SudachiDict lexicon row. Newer releases append more fields, which are
+/// ignored.
+const SUDACHI_FIELDS_NUM: usize = 18;
+const COMPRESS_ALGORITHM: Algorithm = Algorithm::Deflate;
+const UNK_FIELDS_NUM: usize = 10;
+const ENCODING: &str = "UTF-8";
+/// Word ids in split information refer to the rows of the lexicon files concatenated in this
+/// order, as SudachiDict builds its system dictionaries.
+const LEXICON_FILES: [&str; 3] = ["small_lex.csv", "core_lex.csv", "notcore_lex.csv"];
+
+/// Read the lexicon files in `input_dir`, in the order of [`LEXICON_FILES`] followed by the other
+/// CSV files in name order.
+fn read_lexicon(input_dir: &Path) -> LinderaResult<Vec<StringRecord>> {
+    let pattern = input_dir.join("*.csv");
+    let pattern = pattern.to_str().ok_or_else(|| {
+        LinderaErrorKind::Io.with_error(anyhow::anyhow!("Failed to convert path to &str."))
+    })?;
+    let mut filenames: Vec<PathBuf> = Vec::new();
+    for entry in
+        glob(pattern).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
+    {
+        filenames.push(entry.map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?);
+    }
+    filenames.sort_by_key(|filename| {
+        let name = filename
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let order = LEXICON_FILES
+            .iter()
+            .position(|lexicon_file| *lexicon_file == name)
+            .unwrap_or(LEXICON_FILES.len());
+        (order, name)
+    });
+
+    let mut rows = Vec::new();
+    for filename in filenames {
+        debug!("reading {:?}", filename);
+        let records = read_records(&read_file_with_encoding(&filename, ENCODING)?)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(&filename)))?;
+        rows.extend(records);
+    }
+    Ok(rows)
+}
+
+fn read_records(content: &str) -> LinderaResult<Vec<StringRecord>> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes())
+        .records()
+        .map(|result| {
+            result.map_err(|err| {
+                let line = err.position().map(|position| position.line() as usize);
+                let err = LinderaErrorKind::Content.with_error(anyhow::anyhow!(err));
+                match line {
+                    Some(line) => err.add_location(ErrorLocation::new().with_line(line)),
+                    None => err,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Resolve a reference to another word to its surface.
+///
+/// A reference is a word id, a user dictionary word id prefixed with `U`, or an inline word
+/// definition starting with the surface. Word ids of the system dictionary cannot be resolved in
+/// a user dictionary, so they are kept as they are.
+fn resolve_reference(
+    reference: &str,
+    system_rows: Option<&[StringRecord]>,
+    user_rows: Option<&[StringRecord]>,
+) -> LinderaResult<String> {
+    let (rows, id) = match reference.strip_prefix('U') {
+        Some(id) if user_rows.is_some() && id.parse::<usize>().is_ok() => (user_rows, id),
+        _ => (system_rows, reference),
+    };
+    match id.parse::<usize>() {
+        Ok(id) => match rows {
+            Some(rows) => rows.get(id).map(|row| row[0].to_string()).ok_or_else(|| {
+                LinderaErrorKind::Content
+                    .with_error(anyhow::anyhow!("Invalid word id: {}", reference))
+            }),
+            None => Ok(reference.to_string()),
+        },
+        // Inline word definition
+        Err(_) => Ok(reference.split(',').next().unwrap_or_default().to_string()),
+    }
+}
+
+/// Resolve the split information, i.e. word references separated by `/`, to the surfaces
+/// separated by `/`.
+fn resolve_split(
+    split: &str,
+    system_rows: Option<&[StringRecord]>,
+    user_rows: Option<&[StringRecord]>,
+) -> LinderaResult<String> {
+    if split == "*" || split.is_empty() {
+        return Ok("*".to_string());
+    }
+    Ok(split
+        .split('/')
+        .map(|reference| resolve_reference(reference, system_rows, user_rows))
+        .collect::<LinderaResult<Vec<String>>>()?
+        .join("/"))
+}
+
+/// Convert SudachiDict lexicon rows to rows of the surface, the context ids, the cost and the
+/// details. Rows whose left context id is `-1` are only used as the targets of references, and
+/// are not included.
+///
+/// If `user` is true, `rows` are a user dictionary, and simple entries are kept as they are.
+fn convert_records(rows: &[StringRecord], user: bool) -> LinderaResult<Vec<StringRecord>> {
+    let (system_rows, user_rows) = if user {
+        (None, Some(rows))
+    } else {
+        (Some(rows), None)
+    };
+
+    let mut records = Vec::new();
+    for row in rows {
+        if user_rows.is_some() && row.len() == SIMPLE_USERDIC_FIELDS_NUM {
+            records.push(row.clone());
+            continue;
+        }
+        if row.len() < SUDACHI_FIELDS_NUM {
+            return Err(LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "Invalid number of fields. Expect {}, got {}: {:?}",
+                SUDACHI_FIELDS_NUM,
+                row.len(),
+                row
+            )));
+        }
+        if row[1].trim() == "-1" {
+            continue;
+        }
+
+        let dictionary_form = if row[13].trim() == "*" {
+            row[0].to_string()
+        } else {
+            resolve_reference(row[13].trim(), system_rows, user_rows)?
+        };
+
+        let mut record = StringRecord::new();
+        for field in row.iter().take(4) {
+            record.push_field(field); // Surface, left context id, right context id, cost
+        }
+        for field in row.iter().skip(5).take(7) {
+            record.push_field(field); // Part-of-speech, conjugation type and form, reading
+        }
+        record.push_field(&row[12]); // Normalized form
+        record.push_field(&dictionary_form); // Dictionary form
+        record.push_field(&row[14]); // Split type
+        record.push_field(&resolve_split(row[15].trim(), system_rows, user_rows)?); // A unit split
+        record.push_field(&resolve_split(row[16].trim(), system_rows, user_rows)?); // B unit split
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Restore a SudachiDict lexicon row from a row converted by [`convert_records`].
+///
+/// References to other words were resolved to their surfaces, so they are restored as inline
+/// word definitions, which are resolved to the same surfaces.
+fn restore_record(row: Vec<String>) -> Vec<String> {
+    if row.len() < DETAILED_USERDIC_FIELDS_NUM {
+        return row;
+    }
+
+    let mut record = Vec::with_capacity(SUDACHI_FIELDS_NUM);
+    record.extend(row[0..4].iter().cloned()); // Surface, left context id, right context id, cost
+    record.push(row[0].clone()); // Written form
+    record.extend(row[4..11].iter().cloned()); // Part-of-speech, conjugation type and form, reading
+    record.push(row[11].clone()); // Normalized form
+    record.push(if row[12] == row[0] {
+        "*".to_string()
+    } else {
+        row[12].clone()
+    }); // Dictionary form
+    record.extend(row[13..16].iter().cloned()); // Split type, A unit split, B unit split
+    record.push("*".to_string()); // Word structure
+    record
+}
+
+pub struct SudachiBuilder {}
+
+impl SudachiBuilder {
+    pub fn new() -> Self {
+        SudachiBuilder {}
+    }
+
+    fn user_dict_builder_options(&self) -> UserDictBuilderOptions {
+        UserDictBuilderOptions::default()
+            .simple_userdic_fields_num(SIMPLE_USERDIC_FIELDS_NUM)
+            .detailed_userdic_fields_num(DETAILED_USERDIC_FIELDS_NUM)
+            .simple_word_cost(SIMPLE_WORD_COST)
+            .simple_context_id(SIMPLE_CONTEXT_ID)
+            .flexible_csv(true)
+            .simple_userdic_details_handler(Box::new(|row| {
+                Ok(vec![
+                    row[1].to_string(), // Part-of-speech 1
+                    "*".to_string(),    // Part-of-speech 2
+                    "*".to_string(),    // Part-of-speech 3
+                    "*".to_string(),    // Part-of-speech 4
+                    "*".to_string(),    // Conjugation type
+                    "*".to_string(),    // Conjugation form
+                    row[2].to_string(), // Reading
+                    row[0].to_string(), // Normalized form
+                    row[0].to_string(), // Dictionary form
+                    "A".to_string(),    // Split type
+                    "*".to_string(),    // A unit split
+                    "*".to_string(),    // B unit split
+                ])
+            }))
+    }
+
+    fn build_user_dict_with_options(
+        &self,
+        options: UserDictBuilderOptions,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        debug!("reading {:?}", input_file);
+        let mut file = fs::File::open(input_file).map_err(|err| {
+            LinderaErrorKind::Io
+                .with_error(anyhow::anyhow!(err))
+                .add_location(ErrorLocation::new().with_path(input_file))
+        })?;
+        self.build_user_dict_from_reader_with_options(options, &mut file, format)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(input_file)))
+    }
+
+    /// CSV sources are in the SudachiDict layout and are converted to the MeCab layout first.
+    fn build_user_dict_from_reader_with_options(
+        &self,
+        options: UserDictBuilderOptions,
+        reader: &mut dyn Read,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        let builder = options.builder().unwrap();
+        match format {
+            UserDictionaryFormat::Csv => {
+                let mut buffer = Vec::new();
+                reader
+                    .read_to_end(&mut buffer)
+                    .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+                let records = read_records(&decode_with_encoding(&buffer, ENCODING)?)?;
+                builder.build_from_records(convert_records(&records, true)?)
+            }
+            _ => builder.build_from_reader(reader, format),
+        }
+    }
+}
+
+impl Default for SudachiBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DictionaryBuilder for SudachiBuilder {
+    fn build_dictionary(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        fs::create_dir_all(output_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+
+        let chardef = self.build_chardef(input_dir, output_dir)?;
+        self.build_unk(input_dir, &chardef, output_dir)?;
+        self.build_dict(input_dir, output_dir)?;
+        self.build_cost_matrix(input_dir, output_dir)?;
+        self.build_id_def(input_dir, output_dir)?;
+
+        Ok(())
+    }
+
+    fn build_user_dictionary(&self, input_file: &Path, output_file: &Path) -> LinderaResult<()> {
+        let user_dict = self.build_user_dict(input_file)?;
+        build_user_dictionary(user_dict, output_file)
+    }
+
+    fn build_user_dictionary_with_estimation(
+        &self,
+        input_file: &Path,
+        output_file: &Path,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<()> {
+        let format =
+            UserDictionaryFormat::from_path(input_file).unwrap_or(UserDictionaryFormat::Csv);
+        let user_dict = self.build_user_dict_with_estimation(input_file, format, dictionary)?;
+        build_user_dictionary(user_dict, output_file)
+    }
+
+    fn build_chardef(
+        &self,
+        input_dir: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<CharacterDefinitions> {
+        CharDefBuilderOptions::default()
+            .encoding(ENCODING)
+            .compress_algorithm(COMPRESS_ALGORITHM)
+            .builder()
+            .unwrap()
+            .build(input_dir, output_dir)
+    }
+
+    fn build_unk(
+        &self,
+        input_dir: &Path,
+        chardef: &CharacterDefinitions,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        UnkBuilderOptions::default()
+            .encoding(ENCODING)
+            .compress_algorithm(COMPRESS_ALGORITHM)
+            .unk_fields_num(UNK_FIELDS_NUM)
+            .builder()
+            .unwrap()
+            .build(input_dir, chardef, output_dir)
+    }
+
+    fn build_dict(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        let rows = convert_records(&read_lexicon(input_dir)?, false)?;
+        DictBuilderOptions::default()
+            .flexible_csv(false)
+            .encoding(ENCODING)
+            .compress_algorithm(COMPRESS_ALGORITHM)
+            .builder()
+            .unwrap()
+            .build_from_records(rows, output_dir)
+    }
+
+    fn build_cost_matrix(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<()> {
+        CostMatrixBuilderOptions::default()
+            .encoding(ENCODING)
+            .compress_algorithm(COMPRESS_ALGORITHM)
+            .builder()
+            .unwrap()
+            .build(input_dir, output_dir)
+    }
+
+    fn build_id_def(&self, input_dir: &Path, output_dir: &Path) -> LinderaResult<IdDefinitions> {
+        IdDefBuilderOptions::default()
+            .encoding(ENCODING)
+            .compress_algorithm(COMPRESS_ALGORITHM)
+            .builder()
+            .unwrap()
+            .build(input_dir, output_dir)
+    }
+
+    fn build_user_dict(&self, input_file: &Path) -> LinderaResult<UserDictionary> {
+        let format =
+            UserDictionaryFormat::from_path(input_file).unwrap_or(UserDictionaryFormat::Csv);
+        self.build_user_dict_with_format(input_file, format)
+    }
+
+    fn build_user_dict_with_format(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.build_user_dict_with_options(self.user_dict_builder_options(), input_file, format)
+    }
+
+    fn build_user_dict_from_entries(
+        &self,
+        entries: Vec<UserDictionaryEntry>,
+    ) -> LinderaResult<UserDictionary> {
+        self.user_dict_builder_options()
+            .builder()
+            .unwrap()
+            .build_from_entries(entries)
+    }
+
+    fn build_user_dict_from_reader(
+        &self,
+        reader: &mut dyn Read,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        self.build_user_dict_from_reader_with_options(
+            self.user_dict_builder_options(),
+            reader,
+            format,
+        )
+    }
+
+    fn build_user_dict_with_estimation(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+        dictionary: &Dictionary,
+    ) -> LinderaResult<UserDictionary> {
+        let options = self
+            .user_dict_builder_options()
+            .simple_userdic_estimator(CostEstimator::from_dictionary(
+                dictionary,
+                SIMPLE_USERDIC_POS_FIELDS_NUM,
+            ))
+            .id_definitions(dictionary.id_definitions.clone());
+        self.build_user_dict_with_options(options, input_file, format)
+    }
+
+    fn build_user_dict_without_details(
+        &self,
+        input_file: &Path,
+        format: UserDictionaryFormat,
+    ) -> LinderaResult<UserDictionary> {
+        let options = self.user_dict_builder_options().details(false);
+        self.build_user_dict_with_options(options, input_file, format)
+    }
+
+    fn export_dictionary(&self, dictionary: &Dictionary, output_dir: &Path) -> LinderaResult<()> {
+        DictionaryExporterOptions::default()
+            .encoding(ENCODING)
+            .unk_fields_num(UNK_FIELDS_NUM)
+            .lexicon_row_handler(Box::new(|row| Ok(restore_record(row))))
+            .builder()
+            .unwrap()
+            .export(dictionary, output_dir)
+    }
+
+    fn validate_dictionary(&self, input_dir: &Path) -> LinderaResult<ValidationReport> {
+        DictionaryValidatorOptions::default()
+            .encoding(ENCODING)
+            .fields_num(SUDACHI_FIELDS_NUM)
+            .unk_fields_num(UNK_FIELDS_NUM)
+            .allow_unindexed_entries(true)
+            .builder()
+            .unwrap()
+            .validate(input_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sudachi_builder::{convert_records, read_records};
+
+    #[test]
+    fn test_convert_records() {
+        let rows = read_records(
+            "東京都,5,5,2816,東京都,名詞,固有名詞,地名,一般,*,*,トウキョウト,東京都,*,B,1/2,*,*\n\
+             東京,-1,-1,0,東京,名詞,固有名詞,地名,一般,*,*,トウキョウ,東京,*,A,*,*,*\n\
+             都,5,5,9000,都,名詞,普通名詞,一般,*,*,*,ト,都,*,A,*,*,*\n\
+             行っ,700,700,5000,行っ,動詞,非自立可能,*,*,五段-カ行,連用形-促音便,イッ,行く,4,A,*,*,*\n\
+             行く,700,700,5000,行く,動詞,非自立可能,*,*,五段-カ行,終止形-一般,イク,行く,*,A,*,*,*\n",
+        )
+        .unwrap();
+
+        let records = convert_records(&rows, false).unwrap();
+        // The row with the left context id -1 is only used for the split information.
+        assert_eq!(records.len(), 4);
+        assert_eq!(
+            records[0].iter().collect::<Vec<&str>>(),
+            vec![
+                "東京都",
+                "5",
+                "5",
+                "2816",
+                "名詞",
+                "固有名詞",
+                "地名",
+                "一般",
+                "*",
+                "*",
+                "トウキョウト",
+                "東京都",
+                "東京都",
+                "B",
+                "東京/都",
+                "*"
+            ]
+        );
+        // Dictionary form
+        assert_eq!(&records[2][12], "行く");
+
+        assert!(convert_records(&read_records("東京,1,1,0,名詞\n").unwrap(), false).is_err());
+    }
+
+    #[test]
+    fn test_convert_user_records() {
+        let rows = read_records(
+            "東京タワー,5,5,2000,東京タワー,名詞,固有名詞,一般,*,*,*,トウキョウタワー,東京タワー,*,B,1/U2,*,*\n\
+             東京スカイツリー,カスタム名詞,トウキョウスカイツリー\n\
+             タワー,5,5,4000,タワー,名詞,普通名詞,一般,*,*,*,タワー,タワー,*,A,*,*,*\n",
+        )
+        .unwrap();
+
+        let records = convert_records(&rows, true).unwrap();
+        assert_eq!(records.len(), 3);
+        // Word ids of the system dictionary are kept as they are.
+        assert_eq!(&records[0][14], "1/タワー");
+        assert_eq!(records[1].len(), 3);
+    }
+}
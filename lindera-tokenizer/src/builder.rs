@@ -0,0 +1,400 @@
+use std::path::{Path, PathBuf};
+
+use lindera_core::mode::{Mode, Penalty};
+use lindera_core::user_dictionary_source::UserDictionaryFormat;
+use lindera_core::LinderaResult;
+use lindera_dictionary::{DictionaryKind, UserDictionaryConfig};
+
+use crate::tokenizer::{Tokenizer, TokenizerConfig};
+
+/// Builds a `Tokenizer` step by step, starting from the default config or from a config file.
+///
+/// ```no_run
+/// use lindera_core::mode::Penalty;
+/// use lindera_dictionary::DictionaryKind;
+/// use lindera_tokenizer::builder::TokenizerBuilder;
+///
+/// let tokenizer = TokenizerBuilder::new()
+///     .dictionary_kind(DictionaryKind::IPADIC)
+///     .user_dictionary_path("userdic.csv", Some(DictionaryKind::IPADIC))
+///     .penalty(Penalty {
+///         kanji_penalty_length_threshold: 3,
+///         ..Penalty::default()
+///     })
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TokenizerBuilder {
+    config: TokenizerConfig,
+}
+
+impl TokenizerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a builder that starts from a config.
+    pub fn from_config(config: TokenizerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Create a builder that starts from a JSON, TOML or YAML config file.
+    pub fn from_file(path: &Path) -> LinderaResult<Self> {
+        Ok(Self::from_config(TokenizerConfig::from_file(path)?))
+    }
+
+    /// Use a self-contained dictionary.
+    pub fn dictionary_kind(mut self, kind: DictionaryKind) -> Self {
+        self.config.dictionary.kind = Some(kind);
+        self.config.dictionary.path = None;
+        self
+    }
+
+    /// Use a pre-built dictionary in a directory.
+    pub fn dictionary_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.dictionary.kind = None;
+        self.config.dictionary.path = Some(path.into());
+        self
+    }
+
+    /// Use a user dictionary.
+    pub fn user_dictionary(mut self, user_dictionary: UserDictionaryConfig) -> Self {
+        self.config.user_dictionary = Some(user_dictionary);
+        self
+    }
+
+    /// Use a user dictionary file, of which the format is detected from the extension.
+    /// The kind of dictionary is needed if the file is a source rather than a built dictionary.
+    pub fn user_dictionary_path<P: Into<PathBuf>>(
+        self,
+        path: P,
+        kind: Option<DictionaryKind>,
+    ) -> Self {
+        self.user_dictionary(UserDictionaryConfig {
+            path: path.into(),
+            kind,
+            format: None,
+            schema: None,
+            estimate_cost: false,
+        })
+    }
+
+    /// Use a user dictionary file in the given format.
+    pub fn user_dictionary_with_format<P: Into<PathBuf>>(
+        self,
+        path: P,
+        kind: Option<DictionaryKind>,
+        format: UserDictionaryFormat,
+    ) -> Self {
+        self.user_dictionary(UserDictionaryConfig {
+            path: path.into(),
+            kind,
+            format: Some(format),
+            schema: None,
+            estimate_cost: false,
+        })
+    }
+
+    /// Use a user dictionary source for a dictionary built from the schema file at `schema_path`.
+    pub fn user_dictionary_with_schema<P: Into<PathBuf>, S: Into<PathBuf>>(
+        self,
+        path: P,
+        schema_path: S,
+    ) -> Self {
+        self.user_dictionary(UserDictionaryConfig {
+            path: path.into(),
+            kind: None,
+            format: None,
+            schema: Some(schema_path.into()),
+            estimate_cost: false,
+        })
+    }
+
+    /// Do not use a user dictionary.
+    pub fn no_user_dictionary(mut self) -> Self {
+        self.config.user_dictionary = None;
+        self
+    }
+
+    /// Set the tokenization mode.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.config.mode = mode;
+        self
+    }
+
+    /// Use the decompose mode with the given penalties.
+    pub fn penalty(self, penalty: Penalty) -> Self {
+        self.mode(Mode::Decompose(penalty))
+    }
+
+    /// Set the characters that end a sentence.
+    pub fn sentence_delimiters(mut self, sentence_delimiters: Vec<char>) -> Self {
+        self.config.sentence_delimiters = sentence_delimiters;
+        self
+    }
+
+    /// Set whether the text is split into sentences by the default delimiters, or not split.
+    pub fn split_sentences(self, split_sentences: bool) -> Self {
+        let sentence_delimiters = if split_sentences {
+            TokenizerConfig::default().sentence_delimiters
+        } else {
+            Vec::new()
+        };
+        self.sentence_delimiters(sentence_delimiters)
+    }
+
+    /// Set whether the details of the tokens are looked up while tokenizing.
+    pub fn with_details(mut self, with_details: bool) -> Self {
+        self.config.with_details = with_details;
+        self
+    }
+
+    /// Set whether the text is only segmented, without loading the details of the words.
+    pub fn segmentation_only(mut self, segmentation_only: bool) -> Self {
+        self.config.segmentation_only = segmentation_only;
+        self
+    }
+
+    /// Returns the config that is built.
+    pub fn config(&self) -> &TokenizerConfig {
+        &self.config
+    }
+
+    /// Load the dictionaries and create the tokenizer.
+    pub fn build(self) -> LinderaResult<Tokenizer> {
+        Tokenizer::from_config(self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::mode::{Mode, Penalty};
+    use lindera_dictionary::DictionaryBuilderResolver;
+
+    use crate::builder::TokenizerBuilder;
+    use crate::tokenizer::{ConfigFormat, TokenizerConfig};
+
+    /// Build a small dictionary into `dir/dict` and a user dictionary source into `dir`.
+    fn build_dictionary(dir: &Path) {
+        let source_dir = dir.join("source");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(
+            source_dir.join("char.def"),
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            source_dir.join("unk.def"),
+            "DEFAULT,0,0,1000,未知語\nKANJI,0,0,5000,未知語\n",
+        )
+        .unwrap();
+        fs::write(source_dir.join("matrix.def"), "1 1\n0 0 0\n").unwrap();
+        fs::write(
+            source_dir.join("lex.csv"),
+            "東京,0,0,100,地名\n都,0,0,100,接尾\n京都,0,0,100,地名\n。,0,0,100,記号\n",
+        )
+        .unwrap();
+        fs::write(source_dir.join("schema.json"), r#"{"fields": ["pos"]}"#).unwrap();
+
+        let builder =
+            DictionaryBuilderResolver::resolve_builder_from_schema(&source_dir.join("schema.json"))
+                .unwrap();
+        builder
+            .build_dictionary(&source_dir, &dir.join("dict"))
+            .unwrap();
+        builder
+            .build_user_dictionary(
+                &{
+                    let path = dir.join("userdic.csv");
+                    fs::write(&path, "東京都,0,0,-1000,都市\n").unwrap();
+                    path
+                },
+                &dir.join("userdic.bin"),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_tokenizer_builder() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-builder");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        build_dictionary(&tmp_dir);
+
+        let tokenizer = TokenizerBuilder::new()
+            .dictionary_path(tmp_dir.join("dict"))
+            .user_dictionary_path(tmp_dir.join("userdic.bin"), None)
+            .with_details(true)
+            .build()
+            .unwrap();
+        assert!(tokenizer.eager_details);
+        let tokens = tokenizer.tokenize("東京都。京都").unwrap();
+        assert_eq!(
+            tokens.iter().map(|token| token.text).collect::<Vec<_>>(),
+            vec!["東京都", "。", "京都"]
+        );
+
+        // The user dictionary source is built with the schema of the dictionary.
+        let tokenizer = TokenizerBuilder::new()
+            .dictionary_path(tmp_dir.join("dict"))
+            .user_dictionary_with_schema(
+                tmp_dir.join("userdic.csv"),
+                tmp_dir.join("source").join("schema.json"),
+            )
+            .build()
+            .unwrap();
+        let mut tokens = tokenizer.tokenize("東京都。").unwrap();
+        assert_eq!(tokens[0].text, "東京都");
+        assert!(tokens[0].is_user());
+        assert_eq!(tokens[0].get_details().unwrap(), vec!["都市"]);
+
+        // The user dictionary is dropped and the text is not split into sentences.
+        let builder = TokenizerBuilder::new()
+            .dictionary_path(tmp_dir.join("dict"))
+            .user_dictionary_path(tmp_dir.join("userdic.bin"), None)
+            .no_user_dictionary()
+            .split_sentences(false)
+            .penalty(Penalty {
+                kanji_penalty_length_threshold: 1,
+                ..Penalty::default()
+            });
+        assert!(builder.config().sentence_delimiters.is_empty());
+        assert_eq!(
+            builder.config().mode,
+            Mode::Decompose(Penalty {
+                kanji_penalty_length_threshold: 1,
+                ..Penalty::default()
+            })
+        );
+        let tokenizer = builder.build().unwrap();
+        assert!(tokenizer.user_dictionary.is_none());
+        assert!(tokenizer.sentence_delimiters.is_empty());
+
+        // Only segment the text, without the details of the words.
+        let tokenizer = TokenizerBuilder::new()
+            .dictionary_path(tmp_dir.join("dict"))
+            .user_dictionary_path(tmp_dir.join("userdic.bin"), None)
+            .segmentation_only(true)
+            .build()
+            .unwrap();
+        assert!(tokenizer.is_segmentation_only());
+        assert!(tokenizer.dictionary.words_data.is_empty());
+        assert!(tokenizer
+            .user_dictionary
+            .as_ref()
+            .unwrap()
+            .words_data
+            .is_empty());
+        let mut tokens = tokenizer.tokenize("東京都。京都𠮷").unwrap();
+        assert_eq!(
+            tokens.iter().map(|token| token.text).collect::<Vec<_>>(),
+            vec!["東京都", "。", "京都", "𠮷"]
+        );
+        assert!(tokens.iter_mut().all(|token| token.get_details().is_none()));
+        let owned_tokens = tokenizer.tokenize_owned("東京都", true).unwrap();
+        assert_eq!(owned_tokens[0].details, None);
+
+        // The details cannot be looked up while only segmenting.
+        assert!(TokenizerBuilder::new()
+            .dictionary_path(tmp_dir.join("dict"))
+            .segmentation_only(true)
+            .with_details(true)
+            .build()
+            .is_err());
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_tokenizer_config_formats() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-config-formats");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        build_dictionary(&tmp_dir);
+        let dict_dir = tmp_dir.join("dict");
+        let userdic_path = tmp_dir.join("userdic.bin");
+
+        let expected = TokenizerConfig {
+            user_dictionary: TokenizerBuilder::new()
+                .user_dictionary_path(&userdic_path, None)
+                .config()
+                .user_dictionary
+                .clone(),
+            mode: Mode::Decompose(Penalty {
+                kanji_penalty_length_threshold: 3,
+                ..Penalty::default()
+            }),
+            sentence_delimiters: vec!['。', '\n'],
+            with_details: true,
+            ..TokenizerBuilder::new()
+                .dictionary_path(&dict_dir)
+                .config()
+                .clone()
+        };
+
+        let yaml = format!(
+            "dictionary:\n  path: {}\nuser_dictionary:\n  path: {}\nmode:\n  decompose:\n    kanji_penalty_length_threshold: 3\nsentence_delimiters: [\"。\", \"\\n\"]\nwith_details: true\n",
+            dict_dir.display(),
+            userdic_path.display()
+        );
+        let toml = format!(
+            "sentence_delimiters = [\"。\", \"\\n\"]\nwith_details = true\n\n[dictionary]\npath = \"{}\"\n\n[user_dictionary]\npath = \"{}\"\n\n[mode.decompose]\nkanji_penalty_length_threshold = 3\n",
+            dict_dir.display(),
+            userdic_path.display()
+        );
+        let json = serde_json::to_string(&expected).unwrap();
+
+        for (name, text) in [
+            ("config.yaml", yaml),
+            ("config.toml", toml),
+            ("config.json", json),
+        ] {
+            let path = tmp_dir.join(name);
+            fs::write(&path, text).unwrap();
+            assert_eq!(TokenizerConfig::from_file(&path).unwrap(), expected);
+
+            let tokenizer = TokenizerBuilder::from_file(&path).unwrap().build().unwrap();
+            assert!(tokenizer.mode.is_search());
+            assert_eq!(tokenizer.sentence_delimiters, vec!['。', '\n']);
+        }
+
+        // The defaults of the fields that are not given
+        let config =
+            TokenizerConfig::from_slice(b"dictionary:\n  kind: ~\n", ConfigFormat::Yaml).unwrap();
+        assert_eq!(config, TokenizerConfig::default());
+
+        // Unknown fields and formats are rejected.
+        assert!(TokenizerConfig::from_slice(
+            br#"{"dictionary": {}, "with_detail": true}"#,
+            ConfigFormat::Json
+        )
+        .is_err());
+        assert!(ConfigFormat::from_path(Path::new("config.ini")).is_err());
+
+        // The parse errors have the line and the column in every format.
+        for (text, format, expected) in [
+            (
+                "{\n  \"with_details\": yes\n}\n",
+                ConfigFormat::Json,
+                (2, 19),
+            ),
+            ("with_details: true\nmode: [\n", ConfigFormat::Yaml, (3, 1)),
+            (
+                "with_details = true\n[mode.decompose]\nkanji_penalty = yes\n",
+                ConfigFormat::Toml,
+                (3, 17),
+            ),
+        ] {
+            let err = TokenizerConfig::from_slice(text.as_bytes(), format).unwrap_err();
+            let location = err.location().unwrap();
+            assert_eq!(
+                (location.line, location.column),
+                (Some(expected.0), Some(expected.1))
+            );
+        }
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
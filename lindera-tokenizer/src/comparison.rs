@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+
+use serde::Serialize;
+
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+use crate::tokenizer::Tokenizer;
+
+const DEFAULT_MAX_EXAMPLES: usize = 10;
+const DEFAULT_MAX_FREQUENT_CHANGES: usize = 20;
+
+/// A token as compared by `TokenizerComparison`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ComparedToken {
+    pub text: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    /// The details compared as the part of speech.
+    pub pos: Vec<String>,
+}
+
+impl fmt::Display for ComparedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.text, self.pos.join(","))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The token boundaries changed.
+    Segmentation,
+    /// The token boundaries are the same, but the part of speech of a token changed.
+    PartOfSpeech,
+}
+
+/// A part of a sentence that is tokenized differently.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+pub struct TokenChange {
+    pub kind: ChangeKind,
+    /// Segmentation changes are described as `東京 都 => 東京都`, and part of speech changes as
+    /// `東京\t名詞,一般 => 名詞,固有名詞`.
+    pub change: String,
+}
+
+/// A sentence that is tokenized differently by the two tokenizers.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct SentenceChange {
+    /// The line number in the corpus, starting from 1
+    pub line: usize,
+    pub sentence: String,
+    pub kind: ChangeKind,
+    pub changes: Vec<TokenChange>,
+    pub old_tokens: Vec<ComparedToken>,
+    pub new_tokens: Vec<ComparedToken>,
+}
+
+impl fmt::Display for SentenceChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |tokens: &[ComparedToken]| {
+            tokens
+                .iter()
+                .map(|token| token.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        writeln!(f, "line {}: {}", self.line, self.sentence)?;
+        writeln!(f, "  - {}", join(&self.old_tokens))?;
+        write!(f, "  + {}", join(&self.new_tokens))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ChangeCount {
+    pub kind: ChangeKind,
+    pub change: String,
+    pub count: usize,
+}
+
+/// The result of tokenizing a corpus with two tokenizers.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct ImpactReport {
+    pub sentences: usize,
+    pub segmentation_changed_sentences: usize,
+    pub pos_changed_sentences: usize,
+    pub old_tokens: usize,
+    pub new_tokens: usize,
+    /// The most frequent changes over the corpus, the most frequent first.
+    pub frequent_changes: Vec<ChangeCount>,
+    /// The first changed sentences of the corpus.
+    pub examples: Vec<SentenceChange>,
+}
+
+impl ImpactReport {
+    pub fn changed_sentences(&self) -> usize {
+        self.segmentation_changed_sentences + self.pos_changed_sentences
+    }
+}
+
+impl fmt::Display for ImpactReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ratio = if self.sentences == 0 {
+            0.0
+        } else {
+            self.changed_sentences() as f64 * 100.0 / self.sentences as f64
+        };
+        writeln!(f, "sentences: {}", self.sentences)?;
+        writeln!(
+            f,
+            "changed sentences: {} ({:.2}%)",
+            self.changed_sentences(),
+            ratio
+        )?;
+        writeln!(f, "  segmentation: {}", self.segmentation_changed_sentences)?;
+        writeln!(f, "  part of speech: {}", self.pos_changed_sentences)?;
+        writeln!(f, "tokens: {} => {}", self.old_tokens, self.new_tokens)?;
+
+        if !self.frequent_changes.is_empty() {
+            writeln!(f, "frequent changes:")?;
+            for change_count in &self.frequent_changes {
+                writeln!(f, "  {}\t{}", change_count.count, change_count.change)?;
+            }
+        }
+
+        if !self.examples.is_empty() {
+            writeln!(f, "examples:")?;
+            for example in &self.examples {
+                writeln!(f, "{}", example)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Tokenizes sentences with two tokenizers, e.g. with an old and a new dictionary, and reports the
+/// sentences that changed segmentation or part of speech.
+pub struct TokenizerComparison<'a> {
+    old: &'a Tokenizer,
+    new: &'a Tokenizer,
+    max_examples: usize,
+    max_frequent_changes: usize,
+    pos_fields_num: Option<usize>,
+}
+
+impl<'a> TokenizerComparison<'a> {
+    pub fn new(old: &'a Tokenizer, new: &'a Tokenizer) -> Self {
+        Self {
+            old,
+            new,
+            max_examples: DEFAULT_MAX_EXAMPLES,
+            max_frequent_changes: DEFAULT_MAX_FREQUENT_CHANGES,
+            pos_fields_num: None,
+        }
+    }
+
+    /// Set the number of changed sentences kept as examples in the report.
+    pub fn max_examples(mut self, max_examples: usize) -> Self {
+        self.max_examples = max_examples;
+        self
+    }
+
+    /// Set the number of the most frequent changes kept in the report.
+    pub fn max_frequent_changes(mut self, max_frequent_changes: usize) -> Self {
+        self.max_frequent_changes = max_frequent_changes;
+        self
+    }
+
+    /// Set the number of leading details compared as the part of speech.
+    /// All the details are compared if not set.
+    pub fn pos_fields_num(mut self, pos_fields_num: Option<usize>) -> Self {
+        self.pos_fields_num = pos_fields_num;
+        self
+    }
+
+    fn tokens(&self, tokenizer: &Tokenizer, sentence: &str) -> LinderaResult<Vec<ComparedToken>> {
+        let mut tokens = tokenizer.tokenize(sentence)?;
+        Ok(tokens
+            .iter_mut()
+            .map(|token| {
+                let mut pos: Vec<String> = token
+                    .get_details()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|detail| detail.to_string())
+                    .collect();
+                if let Some(pos_fields_num) = self.pos_fields_num {
+                    pos.truncate(pos_fields_num);
+                }
+                ComparedToken {
+                    text: token.text.to_string(),
+                    byte_start: token.byte_start,
+                    byte_end: token.byte_end,
+                    pos,
+                }
+            })
+            .collect())
+    }
+
+    /// Compare the tokens of a sentence.
+    /// Returns `None` if both tokenizers give the same tokens.
+    pub fn compare_sentence(
+        &self,
+        line: usize,
+        sentence: &str,
+    ) -> LinderaResult<Option<SentenceChange>> {
+        self.compare_tokens(line, sentence)
+            .map(|(change, _, _)| change)
+    }
+
+    /// Compare the tokens of a sentence, and also return the numbers of old and new tokens.
+    fn compare_tokens(
+        &self,
+        line: usize,
+        sentence: &str,
+    ) -> LinderaResult<(Option<SentenceChange>, usize, usize)> {
+        let old_tokens = self.tokens(self.old, sentence)?;
+        let new_tokens = self.tokens(self.new, sentence)?;
+        let (old_len, new_len) = (old_tokens.len(), new_tokens.len());
+
+        let changes = diff_tokens(&old_tokens, &new_tokens);
+        if changes.is_empty() {
+            return Ok((None, old_len, new_len));
+        }
+        let kind = if changes
+            .iter()
+            .any(|change| change.kind == ChangeKind::Segmentation)
+        {
+            ChangeKind::Segmentation
+        } else {
+            ChangeKind::PartOfSpeech
+        };
+
+        let change = SentenceChange {
+            line,
+            sentence: sentence.to_string(),
+            kind,
+            changes,
+            old_tokens,
+            new_tokens,
+        };
+        Ok((Some(change), old_len, new_len))
+    }
+
+    /// Compare the tokens of each line of the corpus.
+    pub fn compare<R: BufRead>(&self, reader: R) -> LinderaResult<ImpactReport> {
+        let mut report = ImpactReport::default();
+        let mut change_counts: HashMap<TokenChange, usize> = HashMap::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            let sentence = line.trim();
+            if sentence.is_empty() {
+                continue;
+            }
+            report.sentences += 1;
+
+            let (change, old_len, new_len) = self.compare_tokens(i + 1, sentence)?;
+            report.old_tokens += old_len;
+            report.new_tokens += new_len;
+
+            let change = match change {
+                Some(change) => change,
+                None => continue,
+            };
+            match change.kind {
+                ChangeKind::Segmentation => report.segmentation_changed_sentences += 1,
+                ChangeKind::PartOfSpeech => report.pos_changed_sentences += 1,
+            }
+            for token_change in &change.changes {
+                *change_counts.entry(token_change.clone()).or_default() += 1;
+            }
+            if report.examples.len() < self.max_examples {
+                report.examples.push(change);
+            }
+        }
+
+        let mut frequent_changes: Vec<ChangeCount> = change_counts
+            .into_iter()
+            .map(|(token_change, count)| ChangeCount {
+                kind: token_change.kind,
+                change: token_change.change,
+                count,
+            })
+            .collect();
+        frequent_changes.sort_by(|a, b| b.count.cmp(&a.count).then(a.change.cmp(&b.change)));
+        frequent_changes.truncate(self.max_frequent_changes);
+        report.frequent_changes = frequent_changes;
+
+        Ok(report)
+    }
+}
+
+fn join_texts(tokens: &[ComparedToken]) -> String {
+    tokens
+        .iter()
+        .map(|token| token.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Align the tokens on their common boundaries, and describe the parts that differ.
+fn diff_tokens(old_tokens: &[ComparedToken], new_tokens: &[ComparedToken]) -> Vec<TokenChange> {
+    let mut changes = Vec::new();
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_tokens.len() || j < new_tokens.len() {
+        let (old_start, new_start) = (i, j);
+        loop {
+            match (old_tokens.get(i), new_tokens.get(j)) {
+                (Some(old), Some(new)) if old.byte_end == new.byte_end => {
+                    i += 1;
+                    j += 1;
+                    break;
+                }
+                (Some(old), Some(new)) if old.byte_end < new.byte_end => i += 1,
+                (Some(_), Some(_)) => j += 1,
+                (Some(_), None) => i += 1,
+                (None, Some(_)) => j += 1,
+                (None, None) => break,
+            }
+        }
+
+        let old_group = &old_tokens[old_start..i];
+        let new_group = &new_tokens[new_start..j];
+        if old_group.len() == 1 && new_group.len() == 1 {
+            if old_group[0].pos != new_group[0].pos {
+                changes.push(TokenChange {
+                    kind: ChangeKind::PartOfSpeech,
+                    change: format!(
+                        "{}\t{} => {}",
+                        old_group[0].text,
+                        old_group[0].pos.join(","),
+                        new_group[0].pos.join(",")
+                    ),
+                });
+            }
+        } else {
+            changes.push(TokenChange {
+                kind: ChangeKind::Segmentation,
+                change: format!("{} => {}", join_texts(old_group), join_texts(new_group)),
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::mode::Mode;
+    use lindera_dictionary::{DictionaryBuilderResolver, DictionaryLoader};
+
+    use crate::comparison::{ChangeKind, TokenizerComparison};
+    use crate::tokenizer::Tokenizer;
+
+    fn tokenizer(dir: &Path, lexicon: &str) -> Tokenizer {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("char.def"),
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("unk.def"),
+            "DEFAULT,0,0,1000,未知語\nKANJI,0,0,5000,未知語\n",
+        )
+        .unwrap();
+        fs::write(dir.join("matrix.def"), "1 1\n0 0 0\n").unwrap();
+        fs::write(dir.join("lex.csv"), lexicon).unwrap();
+        fs::write(dir.join("schema.json"), r#"{"fields": ["pos"]}"#).unwrap();
+
+        let builder =
+            DictionaryBuilderResolver::resolve_builder_from_schema(&dir.join("schema.json"))
+                .unwrap();
+        let dictionary =
+            DictionaryLoader::load_dictionary_from_source(builder.as_ref(), dir).unwrap();
+        Tokenizer::new(dictionary, None, Mode::Normal)
+    }
+
+    #[test]
+    fn test_compare() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-comparison");
+        let _ = fs::remove_dir_all(&tmp_dir);
+
+        let old = tokenizer(
+            &tmp_dir.join("old"),
+            "東京,0,0,100,地名\n都,0,0,100,接尾\n大阪,0,0,100,地名\n京都,0,0,100,地名\n",
+        );
+        let new = tokenizer(
+            &tmp_dir.join("new"),
+            "東京,0,0,100,地名\n都,0,0,100,接尾\n東京都,0,0,100,地名\n大阪,0,0,100,名詞\n京都,0,0,100,地名\n",
+        );
+
+        let corpus = "東京都\n大阪\n京都\n\n東京都\n";
+        let report = TokenizerComparison::new(&old, &new)
+            .max_examples(1)
+            .compare(corpus.as_bytes())
+            .unwrap();
+
+        assert_eq!(report.sentences, 4);
+        assert_eq!(report.segmentation_changed_sentences, 2);
+        assert_eq!(report.pos_changed_sentences, 1);
+        assert_eq!(report.old_tokens, 6);
+        assert_eq!(report.new_tokens, 4);
+
+        assert_eq!(report.frequent_changes.len(), 2);
+        assert_eq!(report.frequent_changes[0].kind, ChangeKind::Segmentation);
+        assert_eq!(report.frequent_changes[0].change, "東京 都 => 東京都");
+        assert_eq!(report.frequent_changes[0].count, 2);
+        assert_eq!(report.frequent_changes[1].kind, ChangeKind::PartOfSpeech);
+        assert_eq!(report.frequent_changes[1].change, "大阪\t地名 => 名詞");
+
+        assert_eq!(report.examples.len(), 1);
+        assert_eq!(report.examples[0].line, 1);
+        assert_eq!(report.examples[0].sentence, "東京都");
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
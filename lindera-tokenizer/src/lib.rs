@@ -1,2 +1,5 @@
+pub mod builder;
+pub mod comparison;
+pub mod stream;
 pub mod token;
 pub mod tokenizer;
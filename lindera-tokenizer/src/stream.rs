@@ -0,0 +1,517 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, ErrorKind};
+
+use lindera_core::character_definition::CharacterDefinitions;
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::offsets::{OffsetCounter, TextPosition};
+use lindera_core::viterbi::Lattice;
+use lindera_core::LinderaResult;
+
+use crate::token::{OwnedToken, Token};
+use crate::tokenizer::{set_offsets, Tokenizer};
+
+/// The default maximum length of a sentence in bytes.
+const DEFAULT_MAX_SENTENCE_LEN: usize = 1024 * 1024;
+
+/// The chunks of the text read from a `BufRead`, split at character boundaries.
+pub struct ReadChunks<R> {
+    reader: R,
+    /// The bytes of an incomplete character at the end of the last chunk.
+    pending: Vec<u8>,
+}
+
+impl<R: BufRead> ReadChunks<R> {
+    pub fn new(reader: R) -> Self {
+        ReadChunks {
+            reader,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ReadChunks<R> {
+    type Item = LinderaResult<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let buffer = match self.reader.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    return Some(Err(LinderaErrorKind::Io.with_error(anyhow::anyhow!(err))))
+                }
+            };
+            if buffer.is_empty() {
+                if self.pending.is_empty() {
+                    return None;
+                }
+                self.pending.clear();
+                return Some(Err(LinderaErrorKind::Decode.with_error(anyhow::anyhow!(
+                    "the text ends with an incomplete character"
+                ))));
+            }
+
+            let len = buffer.len();
+            self.pending.extend_from_slice(buffer);
+            self.reader.consume(len);
+
+            let valid_len = match std::str::from_utf8(&self.pending) {
+                Ok(_) => self.pending.len(),
+                Err(err) if err.error_len().is_none() => err.valid_up_to(),
+                Err(err) => {
+                    self.pending.clear();
+                    return Some(Err(
+                        LinderaErrorKind::Decode.with_error(anyhow::anyhow!(err))
+                    ));
+                }
+            };
+            if valid_len == 0 {
+                continue;
+            }
+            let rest = self.pending.split_off(valid_len);
+            let chunk = std::mem::replace(&mut self.pending, rest);
+            // The bytes are checked above.
+            return Some(Ok(String::from_utf8(chunk).unwrap()));
+        }
+    }
+}
+
+/// The chunks of the text given as strings.
+pub struct TextChunks<I> {
+    chunks: I,
+}
+
+impl<I: Iterator<Item = String>> TextChunks<I> {
+    pub fn new(chunks: I) -> Self {
+        TextChunks { chunks }
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for TextChunks<I> {
+    type Item = LinderaResult<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(Ok)
+    }
+}
+
+/// Tokenizes a text given in chunks, one sentence at a time.
+///
+/// The text is split into sentences as `Tokenizer::tokenize` does, regardless of where the chunks
+/// are split, so only a sentence is kept in memory. A sentence longer than `max_sentence_len`
+/// bytes is split before that length, after the last whitespace or else at the last boundary of
+/// the character categories, so that a word is not cut in half.
+///
+/// The tokens borrow the sentence, so they are returned by `next_sentence` instead of an
+/// `Iterator`. Their byte positions and positions are counted from the start of the text.
+pub struct TokenStream<'a, I> {
+    tokenizer: &'a Tokenizer,
+    chunks: I,
+    finished: bool,
+    /// The text that is read, of which `buffer[buffer_start..]` is not tokenized yet.
+    /// The tokenized text is removed once per chunk read, rather than once per sentence.
+    buffer: String,
+    buffer_start: usize,
+    /// The length of the text after `buffer_start` that has no sentence delimiter.
+    searched_len: usize,
+    /// The sentence that was tokenized last.
+    sentence: String,
+    /// The byte position of `buffer_start` in the text.
+    byte_position: usize,
+    /// The position of the next token.
+    position: usize,
+    /// The position of `buffer_start` in characters, UTF-16 code units and grapheme clusters.
+    text_position: TextPosition,
+    max_sentence_len: usize,
+    lattice: Lattice,
+}
+
+impl<'a, I> TokenStream<'a, I>
+where
+    I: Iterator<Item = LinderaResult<String>>,
+{
+    pub fn new(tokenizer: &'a Tokenizer, chunks: I) -> Self {
+        TokenStream {
+            tokenizer,
+            chunks,
+            finished: false,
+            buffer: String::new(),
+            buffer_start: 0,
+            searched_len: 0,
+            sentence: String::new(),
+            byte_position: 0,
+            position: 0,
+            text_position: TextPosition::default(),
+            max_sentence_len: DEFAULT_MAX_SENTENCE_LEN,
+            lattice: Lattice::default(),
+        }
+    }
+
+    /// Set the maximum length of a sentence in bytes.
+    pub fn max_sentence_len(mut self, max_sentence_len: usize) -> Self {
+        self.max_sentence_len = max_sentence_len.max(1);
+        self
+    }
+
+    /// Returns the tokens of the next sentence, or `None` at the end of the text.
+    pub fn next_sentence(&mut self) -> Option<LinderaResult<Vec<Token<'_>>>> {
+        let sentence_start = match self.read_sentence()? {
+            Ok(sentence_start) => sentence_start,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let mut tokens = Vec::new();
+        self.position = self.tokenizer.tokenize_sentence(
+            &mut self.lattice,
+            &self.sentence,
+            sentence_start,
+            self.position,
+            &mut tokens,
+        );
+        if self.tokenizer.compute_offsets {
+            let mut offset_counter = OffsetCounter::with_base(&self.sentence, self.text_position);
+            set_offsets(&mut tokens, &mut offset_counter, sentence_start);
+            self.text_position = offset_counter.position(self.sentence.len());
+        }
+        Some(Ok(tokens))
+    }
+
+    /// Convert the stream to an `Iterator` of `OwnedToken`s.
+    ///
+    /// # Arguments
+    ///
+    /// * `with_details`: If true, the details of the tokens are looked up.
+    ///
+    pub fn into_owned_tokens(self, with_details: bool) -> OwnedTokens<'a, I> {
+        OwnedTokens {
+            stream: self,
+            with_details,
+            tokens: VecDeque::new(),
+        }
+    }
+
+    /// Move the next sentence from `buffer` to `sentence`, reading chunks as needed, and return
+    /// the byte position of the sentence.
+    fn read_sentence(&mut self) -> Option<LinderaResult<usize>> {
+        loop {
+            let delimiters = self.tokenizer.sentence_delimiters.as_slice();
+            let text = &self.buffer[self.buffer_start..];
+            let sentence_len = match text[self.searched_len..].find(delimiters) {
+                Some(index) => {
+                    let delimiter_len = text[self.searched_len + index..]
+                        .chars()
+                        .next()
+                        .map(char::len_utf8)
+                        .unwrap_or_default();
+                    Some(self.searched_len + index + delimiter_len)
+                }
+                None => {
+                    self.searched_len = text.len();
+                    if text.len() >= self.max_sentence_len {
+                        Some(split_len(
+                            text,
+                            self.max_sentence_len,
+                            &self.tokenizer.dictionary.char_definitions,
+                        ))
+                    } else if self.finished && !text.is_empty() {
+                        Some(text.len())
+                    } else if self.finished {
+                        return None;
+                    } else {
+                        None
+                    }
+                }
+            };
+
+            if let Some(sentence_len) = sentence_len {
+                let sentence_start = self.byte_position;
+                self.sentence.clear();
+                self.sentence.push_str(&text[..sentence_len]);
+                self.buffer_start += sentence_len;
+                self.searched_len = 0;
+                self.byte_position += sentence_len;
+                return Some(Ok(sentence_start));
+            }
+
+            match self.chunks.next() {
+                Some(Ok(chunk)) => {
+                    self.buffer.drain(..self.buffer_start);
+                    self.buffer_start = 0;
+                    self.buffer.push_str(&chunk);
+                }
+                Some(Err(err)) => return Some(Err(err)),
+                None => self.finished = true,
+            }
+        }
+    }
+}
+
+/// The tokens of a `TokenStream` as `OwnedToken`s.
+pub struct OwnedTokens<'a, I> {
+    stream: TokenStream<'a, I>,
+    with_details: bool,
+    /// The tokens of the current sentence that are not returned yet.
+    tokens: VecDeque<OwnedToken>,
+}
+
+impl<'a, I> Iterator for OwnedTokens<'a, I>
+where
+    I: Iterator<Item = LinderaResult<String>>,
+{
+    type Item = LinderaResult<OwnedToken>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.tokens.is_empty() {
+            match self.stream.next_sentence()? {
+                Ok(tokens) => self.tokens.extend(
+                    tokens
+                        .into_iter()
+                        .map(|token| token.into_owned(self.with_details)),
+                ),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        self.tokens.pop_front().map(Ok)
+    }
+}
+
+/// Returns the length of the start of `text` to be tokenized as a sentence when `text` has no
+/// sentence delimiter within `max_len` bytes: up to the last whitespace before `max_len`, or else
+/// up to the last boundary between characters of different categories in `char.def`, or else the
+/// last character boundary.
+fn split_len(text: &str, max_len: usize, char_definitions: &CharacterDefinitions) -> usize {
+    let limit = floor_char_boundary(text, max_len);
+    let head = &text[..limit];
+    if let Some((index, c)) = head.char_indices().rev().find(|(_, c)| c.is_whitespace()) {
+        return index + c.len_utf8();
+    }
+
+    let mut next = text[limit..].chars().next();
+    for (index, c) in head.char_indices().rev() {
+        if let Some(next) = next {
+            if char_definitions.lookup_categories(c) != char_definitions.lookup_categories(next) {
+                return index + c.len_utf8();
+            }
+        }
+        next = Some(c);
+    }
+    limit
+}
+
+/// Returns the largest character boundary of `text` that is not larger than `index`, but at
+/// least the end of the first character.
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut boundary = index.min(text.len());
+    while !text.is_char_boundary(boundary) {
+        boundary -= 1;
+    }
+    if boundary == 0 {
+        boundary = text.chars().next().map(char::len_utf8).unwrap_or_default();
+    }
+    boundary
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::BufReader;
+    use std::path::Path;
+
+    use lindera_core::error::LinderaErrorKind;
+    use lindera_core::mode::Mode;
+    use lindera_core::LinderaResult;
+    use lindera_dictionary::{DictionaryBuilderResolver, DictionaryLoader};
+
+    use crate::stream::TokenStream;
+    use crate::tokenizer::Tokenizer;
+
+    fn tokenizer(dir: &Path) -> Tokenizer {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("char.def"),
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("unk.def"),
+            "DEFAULT,0,0,1000,未知語\nKANJI,0,0,5000,未知語\n",
+        )
+        .unwrap();
+        fs::write(dir.join("matrix.def"), "1 1\n0 0 0\n").unwrap();
+        fs::write(
+            dir.join("lex.csv"),
+            "東京,0,0,100,地名\n都,0,0,100,接尾\n京都,0,0,100,地名\n。,0,0,100,記号\n",
+        )
+        .unwrap();
+        fs::write(dir.join("schema.json"), r#"{"fields": ["pos"]}"#).unwrap();
+
+        let builder =
+            DictionaryBuilderResolver::resolve_builder_from_schema(&dir.join("schema.json"))
+                .unwrap();
+        let dictionary =
+            DictionaryLoader::load_dictionary_from_source(builder.as_ref(), dir).unwrap();
+        Tokenizer::new(dictionary, None, Mode::Normal)
+    }
+
+    fn collect<I>(stream: &mut TokenStream<I>) -> Vec<(String, usize, usize, usize)>
+    where
+        I: Iterator<Item = lindera_core::LinderaResult<String>>,
+    {
+        let mut tokens = Vec::new();
+        while let Some(sentence) = stream.next_sentence() {
+            for token in sentence.unwrap() {
+                tokens.push((
+                    token.text.to_string(),
+                    token.byte_start,
+                    token.byte_end,
+                    token.position,
+                ));
+            }
+        }
+        tokens
+    }
+
+    #[test]
+    fn test_token_stream() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-stream");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        let text = "東京都。京都\n東京都\n京都";
+        let expected = tokenizer
+            .tokenize(text)
+            .unwrap()
+            .into_iter()
+            .map(|token| {
+                (
+                    token.text.to_string(),
+                    token.byte_start,
+                    token.byte_end,
+                    token.position,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Chunks split in the middle of the sentences
+        let chunks = vec![
+            "東".to_string(),
+            "京都。京".to_string(),
+            "都\n東京都\n京都".to_string(),
+        ];
+        assert_eq!(collect(&mut tokenizer.tokenize_chunks(chunks)), expected);
+
+        // A reader with a small buffer, of which the reads split the characters
+        let reader = BufReader::with_capacity(4, text.as_bytes());
+        assert_eq!(collect(&mut tokenizer.tokenize_reader(reader)), expected);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_max_sentence_len() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-stream-max-sentence-len");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        let mut stream = tokenizer
+            .tokenize_chunks(vec!["東京都京都".to_string()])
+            .max_sentence_len(7);
+        assert_eq!(
+            collect(&mut stream),
+            vec![
+                ("東京".to_string(), 0, 6, 0),
+                ("都".to_string(), 6, 9, 1),
+                ("京".to_string(), 9, 12, 2),
+                ("都".to_string(), 12, 15, 3),
+            ]
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_max_sentence_len_split() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-stream-split");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+        let texts = |chunks: Vec<&str>, max_sentence_len: usize| {
+            let mut stream = tokenizer
+                .tokenize_chunks(
+                    chunks
+                        .into_iter()
+                        .map(|chunk| chunk.to_string())
+                        .collect::<Vec<_>>(),
+                )
+                .max_sentence_len(max_sentence_len);
+            collect(&mut stream)
+                .into_iter()
+                .map(|(text, _, _, _)| text)
+                .collect::<Vec<_>>()
+        };
+
+        // Split at the boundary of the character categories, not in the middle of "abcdef".
+        assert_eq!(texts(vec!["東京abc", "def"], 10), vec!["東京", "abcdef"]);
+
+        // Split after the last whitespace.
+        assert_eq!(texts(vec!["abc def", "ghi"], 9), vec!["abc ", "defghi"]);
+
+        // Split at the limit if there is no boundary.
+        assert_eq!(texts(vec!["abcdef"], 4), vec!["abcd", "ef"]);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_into_owned_tokens() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-stream-owned");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        let owned_tokens = tokenizer
+            .tokenize_chunks(vec!["東京都。".to_string(), "京都".to_string()])
+            .into_owned_tokens(true)
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            owned_tokens,
+            tokenizer.tokenize_owned("東京都。京都", true).unwrap()
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_token_stream_offsets() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-stream-offsets");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir).with_offsets(true);
+
+        // Offsets counted across the sentences and the chunks
+        let owned_tokens = tokenizer
+            .tokenize_chunks(vec!["𠮷東京".to_string(), "都。京都\n東京都".to_string()])
+            .into_owned_tokens(false)
+            .collect::<LinderaResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            owned_tokens,
+            tokenizer
+                .tokenize_owned("𠮷東京都。京都\n東京都", false)
+                .unwrap()
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_decode_error() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-stream-decode");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        let mut stream = tokenizer.tokenize_reader(&b"\xE6\x9D\xB1\xE4"[..]);
+        let err = stream.next_sentence().unwrap().err().unwrap();
+        assert_eq!(err.kind(), LinderaErrorKind::Decode);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
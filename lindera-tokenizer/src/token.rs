@@ -1,7 +1,10 @@
 use once_cell::sync::Lazy;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use lindera_core::dictionary::{Dictionary, UserDictionary};
+use lindera_core::features::Features;
+use lindera_core::offsets::TokenOffsets;
+use lindera_core::viterbi::EdgeType;
 use lindera_core::word_entry::WordId;
 
 static UNK: Lazy<Vec<&str>> = Lazy::new(|| vec!["UNK"]);
@@ -26,6 +29,15 @@ pub struct Token<'a> {
     /// The ID of the word and a flag to indicate whether the word is registered in the dictionary.
     pub word_id: WordId,
 
+    /// The offsets in characters, UTF-16 code units and grapheme clusters,
+    /// if the tokenizer computes them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offsets: Option<TokenOffsets>,
+
+    /// The kind of the lattice edge of the token, which tells the words of the system
+    /// dictionary, the user dictionary and the unknown words apart.
+    pub edge_type: EdgeType,
+
     /// Reference of dictionary.
     pub dictionary: &'a Dictionary,
 
@@ -35,6 +47,10 @@ pub struct Token<'a> {
     /// Detailes about the token.
     /// It contains metadata for tokens, such as part-of-speech information.
     details: Option<Vec<String>>,
+
+    /// The id of the entry of the unknown dictionary, for the unknown words.
+    #[serde(skip)]
+    pub(crate) unknown_word_id: Option<u32>,
 }
 
 impl<'a> Token<'a> {
@@ -55,11 +71,38 @@ impl<'a> Token<'a> {
             position,
             position_length: 1,
             word_id,
+            offsets: None,
+            edge_type: if word_id.is_unknown() {
+                EdgeType::UNKNOWN
+            } else if word_id.is_system() {
+                EdgeType::KNOWN
+            } else {
+                EdgeType::USER
+            },
             dictionary,
             user_dictionary,
+            unknown_word_id: None,
         }
     }
 
+    /// Returns whether the word is not in the dictionaries.
+    pub fn is_unknown(&self) -> bool {
+        self.edge_type == EdgeType::UNKNOWN
+    }
+
+    /// Returns whether the word is in the user dictionary.
+    pub fn is_user(&self) -> bool {
+        self.edge_type == EdgeType::USER
+    }
+
+    /// Returns the name of the character category in `char.def` of an unknown word.
+    pub fn category(&self) -> Option<&'a str> {
+        let dictionary = self.dictionary;
+        self.unknown_word_id
+            .and_then(|word_id| dictionary.unknown_dictionary.category_id(word_id))
+            .map(|category_id| dictionary.char_definitions.category_name(category_id))
+    }
+
     fn details(&self) -> Option<Vec<&str>> {
         match &self.details {
             Some(details) => {
@@ -75,13 +118,24 @@ impl<'a> Token<'a> {
     }
 
     // pub fn get_details(&mut self) -> Option<Vec<String>> {
+    /// Returns the details of the token, looking them up if they have not been yet.
+    /// A tokenizer that only segments the text has no details.
     pub fn get_details(&mut self) -> Option<Vec<&str>> {
         if self.details.is_some() {
             return self.details();
         }
 
-        if self.word_id.is_unknown() {
-            self.set_details(Some(UNK.iter().map(|v| v.to_string()).collect()));
+        if !self.dictionary.has_details() {
+            return None;
+        }
+
+        if self.is_unknown() {
+            // The features of the entry of the unknown dictionary, as MeCab does.
+            let details = self
+                .unknown_word_id
+                .and_then(|word_id| self.dictionary.unknown_dictionary.word_details(word_id))
+                .unwrap_or_else(|| UNK.iter().map(|v| v.to_string()).collect());
+            self.set_details(Some(details));
             return self.details();
         }
 
@@ -100,4 +154,274 @@ impl<'a> Token<'a> {
         self.details = details;
         self
     }
+
+    /// Returns a typed view of the details for the layout of a dictionary, such as
+    /// `IpadicFeatures`, looking up the details if they have not been yet.
+    pub fn features<'b, F: Features<'b>>(&'b mut self) -> F {
+        self.get_details();
+        F::from_details(self.details.as_deref().unwrap_or_default())
+    }
+
+    /// Convert the token to an `OwnedToken`, which does not borrow the text or the dictionaries.
+    ///
+    /// # Arguments
+    ///
+    /// * `with_details`: If true, the details are looked up now if they have not been yet.
+    ///   Otherwise only the details that have already been looked up or set are kept.
+    ///
+    pub fn into_owned(mut self, with_details: bool) -> OwnedToken {
+        if with_details {
+            self.get_details();
+        }
+        OwnedToken {
+            text: self.text.to_string(),
+            byte_start: self.byte_start,
+            byte_end: self.byte_end,
+            position: self.position,
+            position_length: self.position_length,
+            word_id: self.word_id,
+            offsets: self.offsets,
+            edge_type: self.edge_type,
+            category: self.category().map(|category| category.to_string()),
+            details: self.details,
+        }
+    }
+}
+
+/// A token that owns its text and details, so that it can be stored or sent to other threads.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OwnedToken {
+    /// Text content of the token.
+    pub text: String,
+
+    /// Starting position of the token in bytes.
+    pub byte_start: usize,
+
+    /// Ending position of the token in bytes.
+    pub byte_end: usize,
+
+    /// Position, expressed in number of tokens.
+    pub position: usize,
+
+    /// The length expressed in terms of number of original tokens.
+    pub position_length: usize,
+
+    /// The ID of the word and a flag to indicate whether the word is registered in the dictionary.
+    pub word_id: WordId,
+
+    /// The offsets in characters, UTF-16 code units and grapheme clusters,
+    /// if the tokenizer computes them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offsets: Option<TokenOffsets>,
+
+    /// The kind of the lattice edge of the token, which tells the words of the system
+    /// dictionary, the user dictionary and the unknown words apart.
+    #[serde(default)]
+    pub edge_type: EdgeType,
+
+    /// The name of the character category in `char.def`, for the unknown words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+
+    /// Details about the token, if they were looked up.
+    pub details: Option<Vec<String>>,
+}
+
+impl OwnedToken {
+    /// Returns whether the word is not in the dictionaries.
+    pub fn is_unknown(&self) -> bool {
+        self.edge_type == EdgeType::UNKNOWN
+    }
+
+    /// Returns whether the word is in the user dictionary.
+    pub fn is_user(&self) -> bool {
+        self.edge_type == EdgeType::USER
+    }
+
+    /// Returns a typed view of the details for the layout of a dictionary, such as
+    /// `IpadicFeatures`. All the fields are `None` if the details were not looked up.
+    pub fn features<'b, F: Features<'b>>(&'b self) -> F {
+        F::from_details(self.details.as_deref().unwrap_or_default())
+    }
+}
+
+/// The details are looked up.
+impl<'a> From<Token<'a>> for OwnedToken {
+    fn from(token: Token<'a>) -> Self {
+        token.into_owned(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use lindera_core::dictionary_builder::DictionaryBuilder;
+    use lindera_core::features::IpadicFeatures;
+    use lindera_core::mode::Mode;
+    use lindera_core::user_dictionary_source::UserDictionaryFormat;
+    use lindera_core::viterbi::EdgeType;
+    use lindera_dictionary::{DictionaryBuilderResolver, DictionaryLoader};
+
+    use crate::tokenizer::Tokenizer;
+
+    /// Write a small dictionary source into `dir` and return its builder.
+    fn builder(dir: &Path) -> Box<dyn DictionaryBuilder> {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("char.def"),
+            "DEFAULT 0 1 0\nKANJI 0 0 2\n0x4E00..0x9FA5 KANJI\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("unk.def"),
+            "DEFAULT,0,0,1000,未知語\nKANJI,0,0,5000,未知語\n",
+        )
+        .unwrap();
+        fs::write(dir.join("matrix.def"), "1 1\n0 0 0\n").unwrap();
+        fs::write(
+            dir.join("lex.csv"),
+            "東京,0,0,100,地名\n都,0,0,100,接尾\n京都,0,0,100,地名\n。,0,0,100,記号\n",
+        )
+        .unwrap();
+        fs::write(dir.join("schema.json"), r#"{"fields": ["pos"]}"#).unwrap();
+
+        DictionaryBuilderResolver::resolve_builder_from_schema(&dir.join("schema.json")).unwrap()
+    }
+
+    fn tokenizer(dir: &Path) -> Tokenizer {
+        let dictionary =
+            DictionaryLoader::load_dictionary_from_source(builder(dir).as_ref(), dir).unwrap();
+        Tokenizer::new(dictionary, None, Mode::Normal)
+    }
+
+    #[test]
+    fn test_owned_token() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-token-owned");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        let owned_tokens = tokenizer.tokenize_owned("東京都。京都", true).unwrap();
+        assert_eq!(owned_tokens[0].text, "東京");
+        assert_eq!(owned_tokens[0].details, Some(vec!["地名".to_string()]));
+        assert_eq!(
+            (owned_tokens[3].byte_start, owned_tokens[3].byte_end),
+            (12, 18)
+        );
+
+        let owned_tokens = tokenizer.tokenize_owned("東京都。京都", false).unwrap();
+        assert_eq!(owned_tokens[0].details, None);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_token_features() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-token-features");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        let mut tokens = tokenizer.tokenize("東京都").unwrap();
+        assert_eq!(tokens[0].features::<IpadicFeatures>().pos, Some("地名"));
+
+        let owned_token = tokens.remove(1).into_owned(true);
+        assert_eq!(owned_token.features::<IpadicFeatures>().pos, Some("接尾"));
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_token() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-token-unknown");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir);
+
+        // Unknown words have the features of unk.def and the category of char.def.
+        let mut tokens = tokenizer.tokenize("𠮷東").unwrap();
+        assert!(tokens[0].is_unknown());
+        assert_eq!(tokens[0].category(), Some("DEFAULT"));
+        assert_eq!(tokens[0].get_details(), Some(vec!["未知語"]));
+        assert_eq!(tokens[1].category(), Some("KANJI"));
+
+        let owned_token = tokens.remove(1).into_owned(true);
+        assert!(owned_token.is_unknown());
+        assert_eq!(owned_token.category.as_deref(), Some("KANJI"));
+        assert_eq!(owned_token.details, Some(vec!["未知語".to_string()]));
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_token_offsets() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-token-offsets");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let tokenizer = tokenizer(&tmp_dir).with_offsets(true);
+
+        // Offsets counted across the sentences
+        let owned_tokens = tokenizer
+            .tokenize_owned("𠮷東京都。京都\n東京都", false)
+            .unwrap();
+        assert_eq!(
+            owned_tokens
+                .iter()
+                .map(|token| {
+                    let offsets = token.offsets.unwrap();
+                    (offsets.char_start, offsets.utf16_start, offsets.utf16_end)
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                (0, 0, 2),
+                (1, 2, 4),
+                (3, 4, 5),
+                (4, 5, 6),
+                (5, 6, 8),
+                (7, 8, 9),
+                (8, 9, 11),
+                (10, 11, 12),
+            ]
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_edge_types() {
+        let tmp_dir = std::env::temp_dir().join("lindera-tokenizer-token-edge-types");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        let builder = builder(&tmp_dir);
+        let dictionary =
+            DictionaryLoader::load_dictionary_from_source(builder.as_ref(), &tmp_dir).unwrap();
+        let user_dictionary = builder
+            .build_user_dict_from_reader(
+                &mut "東京都,0,0,-1000,都市\n".as_bytes(),
+                UserDictionaryFormat::Csv,
+            )
+            .unwrap();
+        let tokenizer = Tokenizer::new(dictionary, Some(user_dictionary), Mode::Normal);
+
+        let tokens = tokenizer.tokenize("東京都。𠮷").unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| (token.text, token.edge_type))
+                .collect::<Vec<_>>(),
+            vec![
+                ("東京都", EdgeType::USER),
+                ("。", EdgeType::KNOWN),
+                ("𠮷", EdgeType::UNKNOWN),
+            ]
+        );
+        assert!(tokens[0].is_user());
+        assert!(!tokens[1].is_user());
+
+        let owned_tokens = tokenizer.tokenize_owned("東京都", false).unwrap();
+        assert!(owned_tokens[0].is_user());
+        assert_eq!(
+            serde_json::to_value(&owned_tokens[0]).unwrap()["edge_type"],
+            "USER"
+        );
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
 }
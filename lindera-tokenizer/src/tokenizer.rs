@@ -1,27 +1,60 @@
-use std::fmt;
+use std::fs;
+use std::io::BufRead;
+use std::path::Path;
 
-use serde::de::{self, MapAccess, SeqAccess, Visitor};
-use serde::{Deserialize, Deserializer, Serialize};
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 use lindera_core::dictionary::{Dictionary, UserDictionary};
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
 use lindera_core::mode::Mode;
+use lindera_core::offsets::OffsetCounter;
 use lindera_core::viterbi::Lattice;
 use lindera_core::LinderaResult;
 use lindera_dictionary::{DictionaryConfig, DictionaryLoader, UserDictionaryConfig};
 
-use crate::token::Token;
+use crate::stream::{ReadChunks, TextChunks, TokenStream};
+use crate::token::{OwnedToken, Token};
+
+/// The characters that end a sentence. Sentences are tokenized independently.
+pub(crate) const SENTENCE_DELIMITERS: &[char] = &['。', '、', '\n', '\t'];
 
 /// Tokenizer config
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
 pub struct TokenizerConfig {
     /// The dictionary config to be used for tokenization.
     pub dictionary: DictionaryConfig,
 
     /// The user dictionary config to be used for tokenization. (Optional)
+    #[serde(default)]
     pub user_dictionary: Option<UserDictionaryConfig>,
 
     /// The tokenization mode.
+    #[serde(default = "default_mode")]
     pub mode: Mode,
+
+    /// The characters that end a sentence. The text is not split into sentences if empty.
+    #[serde(default = "default_sentence_delimiters")]
+    pub sentence_delimiters: Vec<char>,
+
+    /// Whether the details of the tokens are looked up while tokenizing.
+    #[serde(default)]
+    pub with_details: bool,
+
+    /// Whether the text is only segmented. The details of the words are not loaded, and the
+    /// tokens have no details.
+    #[serde(default)]
+    pub segmentation_only: bool,
+}
+
+fn default_mode() -> Mode {
+    Mode::Normal
+}
+
+fn default_sentence_delimiters() -> Vec<char> {
+    SENTENCE_DELIMITERS.to_vec()
 }
 
 impl Default for TokenizerConfig {
@@ -34,121 +67,115 @@ impl Default for TokenizerConfig {
                 path: None,
             },
             user_dictionary: None,
-            mode: Mode::Normal,
+            mode: default_mode(),
+            sentence_delimiters: default_sentence_delimiters(),
+            with_details: false,
+            segmentation_only: false,
         }
     }
 }
 
-impl<'de> Deserialize<'de> for TokenizerConfig {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        enum Field {
-            Dictionary,
-            UserDictionary,
-            Mode,
-        }
-
-        impl<'de> Deserialize<'de> for Field {
-            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
-            where
-                D: Deserializer<'de>,
-            {
-                struct FieldVisitor;
-
-                impl<'de> Visitor<'de> for FieldVisitor {
-                    type Value = Field;
+impl TokenizerConfig {
+    /// Read a tokenizer config from a file, of which the format is detected from the extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the JSON, TOML or YAML file.
+    ///
+    /// returns: LinderaResult<TokenizerConfig>
+    ///
+    pub fn from_file(path: &Path) -> LinderaResult<Self> {
+        let format = ConfigFormat::from_path(path)?;
+        let bytes = fs::read(path).map_err(|err| {
+            LinderaErrorKind::Io
+                .with_error(err)
+                .add_location(ErrorLocation::new().with_path(path))
+        })?;
+
+        format
+            .parse(&bytes)
+            .map_err(|err| err.add_location(ErrorLocation::new().with_path(path)))
+    }
 
-                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str("`dictionary`, `user_dictionary`, or `mode`")
-                    }
+    /// Read a tokenizer config from data in the given format.
+    pub fn from_slice(data: &[u8], format: ConfigFormat) -> LinderaResult<Self> {
+        format.parse(data)
+    }
+}
 
-                    fn visit_str<E>(self, value: &str) -> Result<Field, E>
-                    where
-                        E: de::Error,
-                    {
-                        match value {
-                            "dictionary" => Ok(Field::Dictionary),
-                            "user_dictionary" => Ok(Field::UserDictionary),
-                            "mode" => Ok(Field::Mode),
-                            _ => Err(de::Error::unknown_field(value, FIELDS)),
-                        }
-                    }
-                }
+/// The format of a config file.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
 
-                deserializer.deserialize_identifier(FieldVisitor)
-            }
+impl ConfigFormat {
+    /// Detect the format from the extension of a file.
+    pub fn from_path(path: &Path) -> LinderaResult<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("json") => Ok(ConfigFormat::Json),
+            Some("toml") => Ok(ConfigFormat::Toml),
+            Some("yaml") | Some("yml") => Ok(ConfigFormat::Yaml),
+            _ => Err(LinderaErrorKind::Args
+                .with_error(anyhow::anyhow!(
+                    "Unsupported config format: {}",
+                    path.display()
+                ))
+                .add_location(ErrorLocation::new().with_path(path))),
         }
+    }
 
-        struct DurationVisitor;
-
-        impl<'de> Visitor<'de> for DurationVisitor {
-            type Value = TokenizerConfig;
-
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("struct TokenizerConfig")
-            }
-
-            fn visit_seq<V>(self, mut seq: V) -> Result<TokenizerConfig, V::Error>
-            where
-                V: SeqAccess<'de>,
-            {
-                let dictionary = seq
-                    .next_element()?
-                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
-                let user_dictionary = seq.next_element()?.unwrap_or(None);
-                let mode = seq.next_element()?.unwrap_or(Mode::Normal);
-
-                Ok(TokenizerConfig {
-                    dictionary,
-                    user_dictionary,
-                    mode,
-                })
-            }
-
-            fn visit_map<V>(self, mut map: V) -> Result<TokenizerConfig, V::Error>
-            where
-                V: MapAccess<'de>,
-            {
-                let mut dictionary = None;
-                let mut user_dictionary = None;
-                let mut mode = None;
-                while let Some(key) = map.next_key()? {
-                    match key {
-                        Field::Dictionary => {
-                            if dictionary.is_some() {
-                                return Err(de::Error::duplicate_field("dictionary"));
-                            }
-                            dictionary = Some(map.next_value()?);
-                        }
-                        Field::UserDictionary => {
-                            if user_dictionary.is_some() {
-                                return Err(de::Error::duplicate_field("user_dictionary"));
-                            }
-                            user_dictionary = Some(map.next_value()?);
-                        }
-                        Field::Mode => {
-                            if mode.is_some() {
-                                return Err(de::Error::duplicate_field("mode"));
-                            }
-                            mode = Some(map.next_value()?);
-                        }
+    /// Deserialize a value from data in this format.
+    pub fn parse<T: DeserializeOwned>(&self, data: &[u8]) -> LinderaResult<T> {
+        match self {
+            ConfigFormat::Json => serde_json::from_slice(data).map_err(|err| {
+                let location = ErrorLocation::new()
+                    .with_line(err.line())
+                    .with_column(err.column());
+                LinderaErrorKind::Deserialize
+                    .with_error(err)
+                    .add_location(location)
+            }),
+            ConfigFormat::Toml => {
+                let text = std::str::from_utf8(data)
+                    .map_err(|err| LinderaErrorKind::Decode.with_error(err))?;
+                toml::from_str(text).map_err(|err| {
+                    // The span is in bytes, so it is converted to a line and a column as the
+                    // other formats report them, counting from 1.
+                    let location = err.span().map(|span| {
+                        let head = &text[..span.start];
+                        let line_start = head.rfind('\n').map_or(0, |pos| pos + 1);
+                        ErrorLocation::new()
+                            .with_line(head.matches('\n').count() + 1)
+                            .with_column(head[line_start..].chars().count() + 1)
+                    });
+                    let err = LinderaErrorKind::Deserialize.with_error(err);
+                    match location {
+                        Some(location) => err.add_location(location),
+                        None => err,
                     }
-                }
-                let dictionary =
-                    dictionary.ok_or_else(|| de::Error::missing_field("dictionary"))?;
-                let mode = mode.unwrap_or(Mode::Normal);
-                Ok(TokenizerConfig {
-                    dictionary,
-                    user_dictionary,
-                    mode,
                 })
             }
+            ConfigFormat::Yaml => serde_yaml::from_slice(data).map_err(|err| {
+                let location = err.location().map(|location| {
+                    ErrorLocation::new()
+                        .with_line(location.line())
+                        .with_column(location.column())
+                });
+                let err = LinderaErrorKind::Deserialize.with_error(err);
+                match location {
+                    Some(location) => err.add_location(location),
+                    None => err,
+                }
+            }),
         }
-
-        const FIELDS: &[&str] = &["dictionary", "user_dictionary", "mode", "with_details"];
-        deserializer.deserialize_struct("TokenizerConfig", FIELDS, DurationVisitor)
     }
 }
 
@@ -163,6 +190,15 @@ pub struct Tokenizer {
 
     /// The tokenization mode.
     pub mode: Mode,
+
+    /// Whether the offsets in characters, UTF-16 code units and grapheme clusters are computed.
+    pub compute_offsets: bool,
+
+    /// The characters that end a sentence. Sentences are tokenized independently.
+    pub sentence_delimiters: Vec<char>,
+
+    /// Whether the details of the tokens are looked up while tokenizing.
+    pub eager_details: bool,
 }
 
 impl Tokenizer {
@@ -175,16 +211,33 @@ impl Tokenizer {
     /// returns: LinderaResult<Tokenizer>
     ///
     pub fn from_config(config: TokenizerConfig) -> LinderaResult<Self> {
-        let dictionary = DictionaryLoader::load_dictionary_from_config(config.dictionary)?;
+        if config.segmentation_only && config.with_details {
+            return Err(LinderaErrorKind::Args.with_error(anyhow::anyhow!(
+                "The details cannot be looked up if the text is only segmented"
+            )));
+        }
+
+        let dictionary = if config.segmentation_only {
+            DictionaryLoader::load_dictionary_from_config_without_details(config.dictionary)?
+        } else {
+            DictionaryLoader::load_dictionary_from_config(config.dictionary)?
+        };
 
         let user_dictionary = match config.user_dictionary {
-            Some(user_dict_conf) => Some(DictionaryLoader::load_user_dictionary_from_config(
-                user_dict_conf,
-            )?),
+            Some(user_dict_conf) => Some(if config.segmentation_only {
+                DictionaryLoader::load_user_dictionary_from_config_without_details(user_dict_conf)?
+            } else {
+                DictionaryLoader::load_user_dictionary_from_config_with_dictionary(
+                    user_dict_conf,
+                    &dictionary,
+                )?
+            }),
             None => None,
         };
 
-        Ok(Self::new(dictionary, user_dictionary, config.mode))
+        Ok(Self::new(dictionary, user_dictionary, config.mode)
+            .with_sentence_delimiters(config.sentence_delimiters)
+            .with_details(config.with_details))
     }
 
     /// Create a new tokenizer.
@@ -206,9 +259,61 @@ impl Tokenizer {
             dictionary,
             user_dictionary,
             mode,
+            compute_offsets: false,
+            sentence_delimiters: SENTENCE_DELIMITERS.to_vec(),
+            eager_details: false,
         }
     }
 
+    /// Set whether the tokens have the offsets in characters, UTF-16 code units and grapheme
+    /// clusters, in addition to the byte offsets.
+    /// The offsets are counted while tokenizing, so this costs less than converting the byte
+    /// offsets afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `compute_offsets`: If true, `Token::offsets` is set.
+    ///
+    /// returns: Tokenizer
+    ///
+    pub fn with_offsets(mut self, compute_offsets: bool) -> Self {
+        self.compute_offsets = compute_offsets;
+        self
+    }
+
+    /// Returns whether the text is only segmented, that is the dictionary was loaded without the
+    /// details of the words and the tokens have no details.
+    pub fn is_segmentation_only(&self) -> bool {
+        !self.dictionary.has_details()
+    }
+
+    /// Set the characters that end a sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence_delimiters`: The delimiters. If empty, the text is not split into sentences.
+    ///
+    /// returns: Tokenizer
+    ///
+    pub fn with_sentence_delimiters(mut self, sentence_delimiters: Vec<char>) -> Self {
+        self.sentence_delimiters = sentence_delimiters;
+        self
+    }
+
+    /// Set whether the details of the tokens are looked up while tokenizing, instead of when
+    /// `Token::get_details` is first called.
+    ///
+    /// # Arguments
+    ///
+    /// * `eager_details`: If true, the tokens are returned with their details.
+    ///
+    /// returns: Tokenizer
+    ///
+    pub fn with_details(mut self, eager_details: bool) -> Self {
+        self.eager_details = eager_details;
+        self
+    }
+
     /// Tokenize the text
     ///
     /// # Arguments
@@ -221,63 +326,184 @@ impl Tokenizer {
     /// * LinderaError : Error message with LinderaErrorKind
     ///
     pub fn tokenize<'a>(&'a self, text: &'a str) -> LinderaResult<Vec<Token<'a>>> {
+        self.tokenize_with_lattice(&mut Lattice::default(), text)
+    }
+
+    /// Tokenize the text with a lattice that is reused across the calls, which saves its
+    /// allocations when many texts are tokenized.
+    ///
+    /// # Arguments
+    ///
+    /// * `lattice`: The lattice to be used for tokenization.
+    /// * `text`: The text to be tokenized.
+    ///
+    /// returns: LinderaResult<Vec<Token>>
+    ///
+    pub fn tokenize_with_lattice<'a>(
+        &'a self,
+        lattice: &mut Lattice,
+        text: &'a str,
+    ) -> LinderaResult<Vec<Token<'a>>> {
         let mut tokens: Vec<Token> = Vec::new();
-        let mut lattice = Lattice::default();
 
         let mut position = 0_usize;
         let mut byte_position = 0_usize;
+        let mut offset_counter = self.compute_offsets.then(|| OffsetCounter::new(text));
 
         // Split text into sentences using Japanese punctuation.
-        for sentence in text.split_inclusive(&['。', '、', '\n', '\t']) {
+        for sentence in text.split_inclusive(self.sentence_delimiters.as_slice()) {
             if text.is_empty() {
                 continue;
             }
 
-            lattice.set_text(
-                &self.dictionary.dict,
-                &self.user_dictionary.as_ref().map(|d| &d.dict),
-                &self.dictionary.char_definitions,
-                &self.dictionary.unknown_dictionary,
-                sentence,
-                &self.mode,
-            );
-            lattice.calculate_path_costs(&self.dictionary.cost_matrix, &self.mode);
-
-            let offsets = lattice.tokens_offset();
-
-            for i in 0..offsets.len() {
-                let (byte_start, word_id) = offsets[i];
-                let byte_end = if i == offsets.len() - 1 {
-                    sentence.len()
-                } else {
-                    let (next_start, _word_id) = offsets[i + 1];
-                    next_start
-                };
-
-                // retrieve token from its sentence byte positions
-                let surface = &sentence[byte_start..byte_end];
-
-                // compute the token's absolute byte positions
-                let token_start = byte_position;
-                byte_position += surface.len();
-                let token_end = byte_position;
-
-                tokens.push(Token::new(
-                    surface,
-                    token_start,
-                    token_end,
-                    position,
-                    word_id,
-                    &self.dictionary,
-                    self.user_dictionary.as_ref(),
-                ));
-
-                position += 1;
+            let first_token = tokens.len();
+            position =
+                self.tokenize_sentence(lattice, sentence, byte_position, position, &mut tokens);
+            if let Some(offset_counter) = offset_counter.as_mut() {
+                set_offsets(&mut tokens[first_token..], offset_counter, 0);
             }
+            byte_position += sentence.len();
         }
 
         Ok(tokens)
     }
+
+    /// Tokenize the texts in parallel on the rayon thread pool.
+    /// Each thread reuses a lattice, and the dictionaries are shared.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts`: The texts to be tokenized.
+    ///
+    /// returns: LinderaResult<Vec<Vec<Token>>>
+    ///
+    /// * Vec<Vec<Token>> : The tokens of each text, in the order of `texts`
+    /// * LinderaError : The error of a text that failed to be tokenized
+    ///
+    pub fn tokenize_batch<'a, T>(&'a self, texts: &'a [T]) -> LinderaResult<Vec<Vec<Token<'a>>>>
+    where
+        T: AsRef<str> + Sync,
+    {
+        texts
+            .par_iter()
+            .map_init(Lattice::default, |lattice, text| {
+                self.tokenize_with_lattice(lattice, text.as_ref())
+            })
+            .collect()
+    }
+
+    /// Tokenize the text into tokens that do not borrow the text or the tokenizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: The text to be tokenized.
+    /// * `with_details`: If true, the details of the tokens are looked up.
+    ///
+    /// returns: LinderaResult<Vec<OwnedToken>>
+    ///
+    pub fn tokenize_owned(&self, text: &str, with_details: bool) -> LinderaResult<Vec<OwnedToken>> {
+        Ok(self
+            .tokenize(text)?
+            .into_iter()
+            .map(|token| token.into_owned(with_details))
+            .collect())
+    }
+
+    /// Tokenize a sentence of which the text starts at `byte_position` and the first token is at
+    /// `position`, and return the position after the last token.
+    pub(crate) fn tokenize_sentence<'a>(
+        &'a self,
+        lattice: &mut Lattice,
+        sentence: &'a str,
+        byte_position: usize,
+        mut position: usize,
+        tokens: &mut Vec<Token<'a>>,
+    ) -> usize {
+        lattice.set_text(
+            &self.dictionary.dict,
+            &self.user_dictionary.as_ref().map(|d| &d.dict),
+            &self.dictionary.char_definitions,
+            &self.dictionary.unknown_dictionary,
+            sentence,
+            &self.mode,
+        );
+        lattice.calculate_path_costs(&self.dictionary.cost_matrix, &self.mode);
+
+        for edge in lattice.token_edges() {
+            let byte_start = edge.start_index as usize;
+            let byte_end = edge.stop_index as usize;
+
+            // retrieve token from its sentence byte positions
+            let surface = &sentence[byte_start..byte_end];
+
+            // compute the token's absolute byte positions
+            let token_start = byte_position + byte_start;
+            let token_end = byte_position + byte_end;
+
+            let mut token = Token::new(
+                surface,
+                token_start,
+                token_end,
+                position,
+                edge.word_entry.word_id,
+                &self.dictionary,
+                self.user_dictionary.as_ref(),
+            );
+            token.edge_type = edge.edge_type;
+            token.unknown_word_id = edge.unknown_word_id;
+            if self.eager_details {
+                token.get_details();
+            }
+            tokens.push(token);
+
+            position += 1;
+        }
+
+        position
+    }
+
+    /// Tokenize the text read from `reader` as a stream, one sentence at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader`: The reader of the text to be tokenized.
+    ///
+    /// returns: TokenStream
+    ///
+    pub fn tokenize_reader<R: BufRead>(&self, reader: R) -> TokenStream<'_, ReadChunks<R>> {
+        TokenStream::new(self, ReadChunks::new(reader))
+    }
+
+    /// Tokenize the text given in chunks as a stream, one sentence at a time.
+    /// The chunks may split the sentences anywhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunks`: The chunks of the text to be tokenized.
+    ///
+    /// returns: TokenStream
+    ///
+    pub fn tokenize_chunks<I>(&self, chunks: I) -> TokenStream<'_, TextChunks<I::IntoIter>>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        TokenStream::new(self, TextChunks::new(chunks.into_iter()))
+    }
+}
+
+/// Set the offsets of the tokens, of which the byte offsets are counted from `byte_position`
+/// before the start of the text of `offset_counter`.
+pub(crate) fn set_offsets(
+    tokens: &mut [Token],
+    offset_counter: &mut OffsetCounter,
+    byte_position: usize,
+) {
+    for token in tokens.iter_mut() {
+        token.offsets = Some(offset_counter.offsets(
+            token.byte_start - byte_position,
+            token.byte_end - byte_position,
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -375,6 +601,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -632,6 +859,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1043,6 +1271,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1214,6 +1443,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1331,7 +1561,7 @@ mod tests {
             assert_eq!(token.byte_end, 36);
             assert_eq!(token.position, 5);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
         }
     }
 
@@ -1350,12 +1580,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1504,12 +1738,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1774,12 +2012,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1859,12 +2101,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1953,7 +2199,7 @@ mod tests {
             assert_eq!(token.byte_end, 30);
             assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
         }
     }
 
@@ -1972,12 +2218,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2126,12 +2376,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2396,12 +2650,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2481,12 +2739,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2575,7 +2837,7 @@ mod tests {
             assert_eq!(token.byte_end, 30);
             assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
         }
     }
 
@@ -2594,12 +2856,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2748,12 +3014,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2903,12 +3173,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         Tokenizer::from_config(config).unwrap();
@@ -2930,12 +3204,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         Tokenizer::from_config(config).unwrap();
@@ -2953,6 +3231,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -3009,7 +3288,11 @@ mod tests {
             assert_eq!(token.byte_end, 36);
             assert_eq!(token.position, 2);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["名詞", "一般", "*", "*", "*", "*", "*"]
+            );
         }
     }
 
@@ -3025,6 +3308,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Decompose(Penalty::default()),
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -3103,7 +3387,11 @@ mod tests {
             assert_eq!(token.byte_end, 36);
             assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["名詞", "一般", "*", "*", "*", "*", "*"]
+            );
         }
     }
 
@@ -3130,6 +3418,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -0,0 +1,70 @@
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+/// A word of an annotated sentence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorpusToken {
+    pub surface: String,
+    pub features: Vec<String>,
+}
+
+/// Parse an annotated corpus in the MeCab output format.
+///
+/// Each line consists of a surface and its comma-separated features, separated by a tab.
+/// Sentences are terminated by `EOS`.
+pub fn parse_corpus(content: &str) -> LinderaResult<Vec<Vec<CorpusToken>>> {
+    let mut sentences = Vec::new();
+    let mut sentence = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.trim_end() == "EOS" {
+            if !sentence.is_empty() {
+                sentences.push(std::mem::take(&mut sentence));
+            }
+            continue;
+        }
+        let (surface, features) = line.split_once('\t').ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                "Invalid line {}: {}",
+                line_num + 1,
+                line
+            ))
+        })?;
+        if surface.is_empty() {
+            return Err(LinderaErrorKind::Content
+                .with_error(anyhow::anyhow!("Empty surface at line {}", line_num + 1)));
+        }
+        sentence.push(CorpusToken {
+            surface: surface.to_string(),
+            features: features
+                .trim_end()
+                .split(',')
+                .map(|s| s.to_string())
+                .collect(),
+        });
+    }
+    if !sentence.is_empty() {
+        sentences.push(sentence);
+    }
+    Ok(sentences)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::corpus::parse_corpus;
+
+    #[test]
+    fn test_parse_corpus() {
+        let sentences =
+            parse_corpus("東京\t名詞,固有名詞\nへ\t助詞,格助詞\nEOS\n\n行く\t動詞,自立\nEOS\n")
+                .unwrap();
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(sentences[0][0].surface, "東京");
+        assert_eq!(sentences[0][1].features, vec!["助詞", "格助詞"]);
+        assert_eq!(sentences[1][0].surface, "行く");
+
+        assert!(parse_corpus("東京 名詞\nEOS\n").is_err());
+    }
+}
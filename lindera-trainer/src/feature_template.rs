@@ -0,0 +1,169 @@
+use lindera_core::error::LinderaErrorKind;
+use lindera_core::LinderaResult;
+
+/// The feature templates of `feature.def`.
+///
+/// Unigram templates refer to the fields of a word with `%F[n]`.
+/// Bigram templates refer to the fields of the right attribute of the left word with `%L[n]`
+/// and to the fields of the left attribute of the right word with `%R[n]`.
+/// With `%F?[n]`, `%L?[n]` or `%R?[n]`, the feature is not generated if the field is `*`.
+/// Several fields can be referred at once, e.g. `%F[0,1]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeatureTemplates {
+    pub unigram: Vec<String>,
+    pub bigram: Vec<String>,
+}
+
+impl FeatureTemplates {
+    /// Returns the unigram features of a word.
+    pub fn unigram_features<S: AsRef<str>>(&self, fields: &[S]) -> Vec<String> {
+        self.unigram
+            .iter()
+            .filter_map(|template| expand(template, fields, &[] as &[&str], &[] as &[&str]))
+            .collect()
+    }
+
+    /// Returns the bigram features of a connection.
+    pub fn bigram_features<S: AsRef<str>, T: AsRef<str>>(
+        &self,
+        left_fields: &[S],
+        right_fields: &[T],
+    ) -> Vec<String> {
+        self.bigram
+            .iter()
+            .filter_map(|template| expand(template, &[] as &[&str], left_fields, right_fields))
+            .collect()
+    }
+}
+
+fn expand<F: AsRef<str>, L: AsRef<str>, R: AsRef<str>>(
+    template: &str,
+    fields: &[F],
+    left_fields: &[L],
+    right_fields: &[R],
+) -> Option<String> {
+    let mut feature = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            feature.push(c);
+            continue;
+        }
+
+        let target = chars.next()?;
+        let optional = chars.next_if_eq(&'?').is_some();
+        chars.next_if_eq(&'[')?;
+        let mut indices = String::new();
+        for c in chars.by_ref() {
+            if c == ']' {
+                break;
+            }
+            indices.push(c);
+        }
+
+        let mut values = Vec::new();
+        for index in indices.split(',') {
+            let index = index.trim().parse::<usize>().ok()?;
+            let value = match target {
+                'F' => fields.get(index)?.as_ref(),
+                'L' => left_fields.get(index)?.as_ref(),
+                'R' => right_fields.get(index)?.as_ref(),
+                _ => return None,
+            };
+            if optional && value == "*" {
+                return None;
+            }
+            values.push(value);
+        }
+        feature.push_str(&values.join(","));
+    }
+    Some(feature)
+}
+
+fn validate(template: &str, allowed: &[char]) -> LinderaResult<()> {
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            match chars.next() {
+                Some(target) if allowed.contains(&target) => {}
+                _ => {
+                    return Err(LinderaErrorKind::Content
+                        .with_error(anyhow::anyhow!("Unsupported template: {}", template)))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse `feature.def`.
+///
+/// Each line consists of `UNIGRAM` or `BIGRAM` and a template, separated by whitespace.
+pub fn parse_feature_def(content: &str) -> LinderaResult<FeatureTemplates> {
+    let mut templates = FeatureTemplates::default();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (kind, template) = line.split_once(char::is_whitespace).ok_or_else(|| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("Invalid line: {}", line))
+        })?;
+        let template = template.trim().to_string();
+        match kind {
+            "UNIGRAM" => {
+                validate(&template, &['F'])?;
+                templates.unigram.push(template);
+            }
+            "BIGRAM" => {
+                validate(&template, &['L', 'R'])?;
+                templates.bigram.push(template);
+            }
+            _ => {
+                return Err(
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!("Invalid line: {}", line))
+                )
+            }
+        }
+    }
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::feature_template::parse_feature_def;
+
+    #[test]
+    fn test_features() {
+        let templates = parse_feature_def(
+            r#"
+# unigram
+UNIGRAM U0:%F[0]
+UNIGRAM U1:%F[0,1]
+UNIGRAM U2:%F?[6]
+BIGRAM B0:%L[0]/%R[0]
+BIGRAM B1:%L?[1]/%R[0]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            templates.unigram_features(&["名詞", "一般", "*", "*", "*", "*", "机"]),
+            vec!["U0:名詞", "U1:名詞,一般", "U2:机"]
+        );
+        assert_eq!(
+            templates.unigram_features(&["名詞", "一般", "*", "*", "*", "*", "*"]),
+            vec!["U0:名詞", "U1:名詞,一般"]
+        );
+        assert_eq!(
+            templates.bigram_features(&["名詞", "*"], &["助詞", "格助詞"]),
+            vec!["B0:名詞/助詞"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_template() {
+        assert!(parse_feature_def("UNIGRAM U0:%L[0]\n").is_err());
+        assert!(parse_feature_def("TRIGRAM T0:%F[0]\n").is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! This library is used to train the costs of [lindera](https://github.com/lindera-morphology/lindera)
+//! dictionary sources from an annotated corpus.
+//!
+//! The trained sources can be built with one of the dictionary builders
+//! (e.g. lindera-ipadic-builder, ...).
+
+pub mod corpus;
+pub mod feature_template;
+pub mod trainer;
+
+pub use trainer::TrainerOptions;
@@ -0,0 +1,1077 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use csv::StringRecord;
+use derive_builder::Builder;
+use encoding_rs::Encoding;
+use glob::glob;
+use log::{debug, info, warn};
+use yada::builder::DoubleArrayBuilder;
+use yada::DoubleArray;
+
+use lindera_core::character_definition::{CharacterDefinitions, CharacterDefinitionsBuilder};
+use lindera_core::error::{ErrorLocation, LinderaErrorKind};
+use lindera_core::id_definition::{parse_rewrite_def, FeaturePattern, RewriteRule, RewriteRules};
+use lindera_core::mode::Mode;
+use lindera_core::prefix_dict::PrefixDict;
+use lindera_core::unknown_dictionary::UnknownDictionary;
+use lindera_core::viterbi::{Edge, EdgeId, Lattice};
+use lindera_core::word_entry::{WordEntry, WordId};
+use lindera_core::LinderaResult;
+use lindera_dictionary_builder::utils::read_file_with_encoding;
+
+use crate::corpus::{parse_corpus, CorpusToken};
+use crate::feature_template::{parse_feature_def, FeatureTemplates};
+
+const BOS_EOS_ATTRIBUTE: &str = "BOS/EOS";
+const BOS_EDGE: EdgeId = EdgeId(0);
+const EOS_EDGE: EdgeId = EdgeId(1);
+/// The number of homographs that can be stored for a surface in a prefix dictionary.
+const MAX_HOMOGRAPHS: usize = (1 << 5) - 1;
+/// Source files that are copied to the output directory as they are.
+const COPIED_FILES: [&str; 3] = ["char.def", "feature.def", "pos-id.def"];
+
+fn csv_records(content: &str) -> LinderaResult<Vec<StringRecord>> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    rdr.records()
+        .map(|result| {
+            result.map_err(|err| LinderaErrorKind::Content.with_error(anyhow::anyhow!(err)))
+        })
+        .collect()
+}
+
+fn log_sum_exp(x: f64, y: f64) -> f64 {
+    if x == f64::NEG_INFINITY {
+        return y;
+    }
+    if y == f64::NEG_INFINITY {
+        return x;
+    }
+    let max = x.max(y);
+    max + ((x - max).exp() + (y - max).exp()).ln()
+}
+
+/// The rewrite rule of the context attributes of the features that no rule of `rewrite.def`
+/// matches, including every word of a seed without `rewrite.def`. It keeps the first
+/// `pos_fields` fields, i.e. the part-of-speech, so that the number of context ids does not grow
+/// with the distinct features of the lexicon.
+fn pos_rewrite_rule(pos_fields: usize) -> RewriteRule {
+    RewriteRule {
+        pattern: FeaturePattern::new("*"),
+        rewrite: (1..=pos_fields)
+            .map(|index| format!("${}", index))
+            .collect(),
+    }
+}
+
+fn to_cost(score: f64, cost_factor: f64) -> i16 {
+    (-score * cost_factor)
+        .round()
+        .clamp(i16::MIN as f64, i16::MAX as f64) as i16
+}
+
+/// The sources of a dictionary to be trained.
+///
+/// The context ids and costs in the lexicon and in `unk.def` are ignored; they are replaced by
+/// the trained ones.
+pub struct Seed {
+    /// The rows of each lexicon CSV file, keyed by the file name.
+    pub lexicon: BTreeMap<String, Vec<StringRecord>>,
+    pub unknown: Vec<StringRecord>,
+    pub char_definitions: CharacterDefinitions,
+    pub templates: FeatureTemplates,
+    pub rewrite_rules: RewriteRules,
+}
+
+impl Seed {
+    /// Load the seed dictionary from `*.csv`, `char.def`, `unk.def`, `feature.def` and the
+    /// optional `rewrite.def` in `seed_dir`.
+    ///
+    /// Without `rewrite.def`, the context attributes are the part-of-speech fields of the
+    /// features. See [`TrainerOptions::pos_fields`].
+    pub fn load(seed_dir: &Path, encoding: &str) -> LinderaResult<Seed> {
+        let pattern = seed_dir.join("*.csv");
+        let pattern = pattern.to_str().ok_or_else(|| {
+            LinderaErrorKind::Io.with_error(anyhow::anyhow!("Failed to convert path to &str."))
+        })?;
+        let mut lexicon = BTreeMap::new();
+        for entry in
+            glob(pattern).map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?
+        {
+            let path =
+                entry.map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            let filename = path
+                .file_name()
+                .and_then(|filename| filename.to_str())
+                .ok_or_else(|| {
+                    LinderaErrorKind::Io.with_error(anyhow::anyhow!("failed to get filename"))
+                })?
+                .to_string();
+            debug!("reading {:?}", path);
+            let rows = csv_records(&read_file_with_encoding(&path, encoding)?)?;
+            lexicon.insert(filename, rows);
+        }
+
+        let unknown = csv_records(&read_file_with_encoding(
+            &seed_dir.join("unk.def"),
+            encoding,
+        )?)?;
+
+        let mut char_definitions_builder = CharacterDefinitionsBuilder::default();
+        char_definitions_builder.parse(&read_file_with_encoding(
+            &seed_dir.join("char.def"),
+            encoding,
+        )?)?;
+
+        let templates = parse_feature_def(&read_file_with_encoding(
+            &seed_dir.join("feature.def"),
+            encoding,
+        )?)?;
+
+        let rewrite_def_path = seed_dir.join("rewrite.def");
+        let rewrite_rules = if rewrite_def_path.exists() {
+            parse_rewrite_def(&read_file_with_encoding(&rewrite_def_path, encoding)?).map_err(
+                |err| err.add_location(ErrorLocation::new().with_path(&rewrite_def_path)),
+            )?
+        } else {
+            RewriteRules::default()
+        };
+
+        Ok(Seed {
+            lexicon,
+            unknown,
+            char_definitions: char_definitions_builder.build(),
+            templates,
+            rewrite_rules,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Origin {
+    /// A row of a lexicon file.
+    Lexicon,
+    /// A row of `unk.def`.
+    Unknown,
+    /// An annotated word of the corpus that is not in the lexicon.
+    Corpus,
+}
+
+#[derive(Debug)]
+struct Word {
+    surface: String,
+    origin: Origin,
+    unigram_features: Vec<usize>,
+    left_id: u16,
+    right_id: u16,
+    /// Whether the word can be looked up in the prefix dictionary of the trainer.
+    indexed: bool,
+}
+
+/// A set of context attributes, i.e. the rewritten features, and their context ids.
+#[derive(Debug)]
+struct Attributes {
+    ids: HashMap<String, u16>,
+    values: Vec<String>,
+}
+
+impl Attributes {
+    fn new() -> Self {
+        Attributes {
+            ids: HashMap::from([(BOS_EOS_ATTRIBUTE.to_string(), 0)]),
+            values: vec![BOS_EOS_ATTRIBUTE.to_string()],
+        }
+    }
+
+    fn get(&self, attribute: &str) -> Option<u16> {
+        self.ids.get(attribute).copied()
+    }
+
+    fn id(&mut self, attribute: String) -> LinderaResult<u16> {
+        if let Some(id) = self.ids.get(&attribute) {
+            return Ok(*id);
+        }
+        let id = u16::try_from(self.values.len()).map_err(|_| {
+            LinderaErrorKind::Content.with_error(anyhow::anyhow!("Too many context attributes"))
+        })?;
+        self.ids.insert(attribute.clone(), id);
+        self.values.push(attribute);
+        Ok(id)
+    }
+}
+
+/// A model trained by [`Trainer`].
+pub struct Model {
+    templates: FeatureTemplates,
+    rewrite_rules: RewriteRules,
+    feature_ids: HashMap<String, usize>,
+    weights: Vec<f64>,
+    words: Vec<Word>,
+    left_attributes: Attributes,
+    right_attributes: Attributes,
+    bigram_features: HashMap<(u16, u16), Vec<usize>>,
+    cost_factor: f64,
+    pos_fields: usize,
+}
+
+impl Model {
+    fn new(seed: &Seed, cost_factor: f64, pos_fields: usize) -> Self {
+        let mut rewrite_rules = seed.rewrite_rules.clone();
+        rewrite_rules.left.push(pos_rewrite_rule(pos_fields));
+        rewrite_rules.right.push(pos_rewrite_rule(pos_fields));
+        Model {
+            templates: seed.templates.clone(),
+            rewrite_rules,
+            feature_ids: HashMap::new(),
+            weights: Vec::new(),
+            words: Vec::new(),
+            left_attributes: Attributes::new(),
+            right_attributes: Attributes::new(),
+            bigram_features: HashMap::new(),
+            cost_factor,
+            pos_fields,
+        }
+    }
+
+    fn feature_id(&mut self, feature: String) -> usize {
+        let next_id = self.weights.len();
+        let id = *self.feature_ids.entry(feature).or_insert(next_id);
+        if id == next_id {
+            self.weights.push(0.0);
+        }
+        id
+    }
+
+    /// Add a word to the model and returns its id.
+    ///
+    /// The words of the lexicon and `unk.def` define the context attributes. A word of the corpus
+    /// only refers to them, and `None` is returned if its attributes are not defined.
+    fn add_word(
+        &mut self,
+        surface: &str,
+        features: &[&str],
+        origin: Origin,
+    ) -> LinderaResult<Option<usize>> {
+        let left = self
+            .rewrite_rules
+            .rewrite_left(features)
+            .unwrap_or_else(|| features.join(","));
+        let right = self
+            .rewrite_rules
+            .rewrite_right(features)
+            .unwrap_or_else(|| features.join(","));
+        let (left_id, right_id) = if origin == Origin::Corpus {
+            match (
+                self.left_attributes.get(&left),
+                self.right_attributes.get(&right),
+            ) {
+                (Some(left_id), Some(right_id)) => (left_id, right_id),
+                _ => return Ok(None),
+            }
+        } else {
+            (
+                self.left_attributes.id(left)?,
+                self.right_attributes.id(right)?,
+            )
+        };
+
+        let unigram = self
+            .rewrite_rules
+            .rewrite_unigram(features)
+            .unwrap_or_else(|| features.join(","));
+        let unigram = unigram.split(',').collect::<Vec<&str>>();
+        let unigram_features = self
+            .templates
+            .unigram_features(&unigram)
+            .into_iter()
+            .map(|feature| self.feature_id(feature))
+            .collect();
+
+        self.words.push(Word {
+            surface: surface.to_string(),
+            origin,
+            unigram_features,
+            left_id,
+            right_id,
+            indexed: false,
+        });
+        Ok(Some(self.words.len() - 1))
+    }
+
+    fn bigram_feature_strings(&self, right_id: u16, left_id: u16) -> Vec<String> {
+        let left_fields = self.right_attributes.values[right_id as usize]
+            .split(',')
+            .collect::<Vec<&str>>();
+        let right_fields = self.left_attributes.values[left_id as usize]
+            .split(',')
+            .collect::<Vec<&str>>();
+        self.templates.bigram_features(&left_fields, &right_fields)
+    }
+
+    /// Returns the ids of the bigram features of the connection between a word with `right_id`
+    /// and a following word with `left_id`.
+    fn bigram_feature_ids(&mut self, right_id: u16, left_id: u16) -> &[usize] {
+        if !self.bigram_features.contains_key(&(right_id, left_id)) {
+            let ids = self
+                .bigram_feature_strings(right_id, left_id)
+                .into_iter()
+                .map(|feature| self.feature_id(feature))
+                .collect();
+            self.bigram_features.insert((right_id, left_id), ids);
+        }
+        &self.bigram_features[&(right_id, left_id)]
+    }
+
+    fn score(&self, feature_ids: &[usize]) -> f64 {
+        feature_ids.iter().map(|id| self.weights[*id]).sum()
+    }
+
+    fn word_score(&self, word_id: usize) -> f64 {
+        self.score(&self.words[word_id].unigram_features)
+    }
+
+    fn connection_score(&mut self, right_id: u16, left_id: u16) -> f64 {
+        let ids = self.bigram_feature_ids(right_id, left_id).to_vec();
+        self.score(&ids)
+    }
+
+    /// Returns the trained cost of a word.
+    fn word_cost(&self, word_id: usize) -> i16 {
+        to_cost(self.word_score(word_id), self.cost_factor)
+    }
+
+    /// Returns the trained connection cost. Features that never appeared in the training lattices
+    /// have no weight.
+    pub fn connection_cost(&self, right_id: u16, left_id: u16) -> i16 {
+        let score = self
+            .bigram_feature_strings(right_id, left_id)
+            .iter()
+            .filter_map(|feature| self.feature_ids.get(feature))
+            .map(|id| self.weights[*id])
+            .sum();
+        to_cost(score, self.cost_factor)
+    }
+
+    /// Returns the number of left context ids, including the id of BOS/EOS.
+    pub fn left_id_size(&self) -> usize {
+        self.left_attributes.values.len()
+    }
+
+    /// Returns the number of right context ids, including the id of BOS/EOS.
+    pub fn right_id_size(&self) -> usize {
+        self.right_attributes.values.len()
+    }
+
+    fn word_entry(&self, word_id: usize) -> WordEntry {
+        let word = &self.words[word_id];
+        WordEntry {
+            word_id: WordId(word_id as u32, true),
+            word_cost: 0,
+            left_id: word.left_id,
+            right_id: word.right_id,
+        }
+    }
+
+    /// Build the prefix dictionary of the indexed lexicon words. The word ids of the entries
+    /// are the indices of the words in the model.
+    fn prefix_dict(&mut self) -> LinderaResult<PrefixDict> {
+        let mut word_ids_map: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (word_id, word) in self.words.iter().enumerate() {
+            if word.origin == Origin::Lexicon {
+                word_ids_map
+                    .entry(word.surface.clone())
+                    .or_default()
+                    .push(word_id);
+            }
+        }
+
+        let mut keyset: Vec<(&[u8], u32)> = Vec::new();
+        let mut vals_data = Vec::new();
+        let mut offset = 0u32;
+        for (surface, word_ids) in &word_ids_map {
+            if word_ids.len() > MAX_HOMOGRAPHS {
+                warn!(
+                    "{} has {} homographs; only the first {} appear in the training lattices",
+                    surface,
+                    word_ids.len(),
+                    MAX_HOMOGRAPHS
+                );
+            }
+            let word_ids = &word_ids[..word_ids.len().min(MAX_HOMOGRAPHS)];
+            keyset.push((surface.as_bytes(), (offset << 5) | word_ids.len() as u32));
+            offset += word_ids.len() as u32;
+            for word_id in word_ids {
+                self.words[*word_id].indexed = true;
+                self.word_entry(*word_id)
+                    .serialize(&mut vals_data)
+                    .map_err(|err| LinderaErrorKind::Serialize.with_error(anyhow::anyhow!(err)))?;
+            }
+        }
+
+        let da_bytes = DoubleArrayBuilder::build(&keyset).ok_or_else(|| {
+            LinderaErrorKind::Io.with_error(anyhow::anyhow!("DoubleArray build error."))
+        })?;
+
+        Ok(PrefixDict {
+            da: DoubleArray::new(da_bytes),
+            vals_data,
+            is_system: true,
+        })
+    }
+
+    /// Build the unknown dictionary from the `unk.def` words. The word ids of the entries are the
+    /// indices of the words in the model.
+    fn unknown_dictionary(
+        &self,
+        char_definitions: &CharacterDefinitions,
+    ) -> LinderaResult<UnknownDictionary> {
+        let mut category_references = vec![Vec::new(); char_definitions.categories().len()];
+        let mut costs = Vec::new();
+        for (word_id, word) in self.words.iter().enumerate() {
+            if word.origin != Origin::Unknown {
+                continue;
+            }
+            let category = char_definitions
+                .categories()
+                .iter()
+                .position(|category| category == &word.surface)
+                .ok_or_else(|| {
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!(
+                        "Unknown category in unk.def: {}",
+                        word.surface
+                    ))
+                })?;
+            category_references[category].push(costs.len() as u32);
+            costs.push(self.word_entry(word_id));
+        }
+        // The features are not needed to build the lattices.
+        Ok(UnknownDictionary {
+            category_references,
+            costs,
+            details: Vec::new(),
+        })
+    }
+
+    /// Write the trained dictionary sources to `output_dir`.
+    ///
+    /// The lexicon and `unk.def` are written with the trained context ids and costs, together
+    /// with `matrix.def`, `left-id.def` and `right-id.def`. `rewrite.def` is written with the
+    /// rule of the part-of-speech fields appended, so that the context ids of the features can be
+    /// looked up as in training. The other sources in `seed_dir` are copied as they are.
+    pub fn write(
+        &self,
+        seed: &Seed,
+        seed_dir: &Path,
+        output_dir: &Path,
+        encoding: &str,
+    ) -> LinderaResult<()> {
+        let encoding =
+            Encoding::for_label_no_replacement(encoding.as_bytes()).ok_or_else(|| {
+                LinderaErrorKind::Decode
+                    .with_error(anyhow::anyhow!("Invalid encoding: {}", encoding))
+            })?;
+        fs::create_dir_all(output_dir)
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        let write = |filename: &str, content: &str| -> LinderaResult<()> {
+            let path = output_dir.join(filename);
+            debug!("writing {:?}", path);
+            fs::write(path, encoding.encode(content).0)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))
+        };
+
+        // The words are added in the order of the lexicon files, then `unk.def`.
+        let mut word_ids = 0..self.words.len();
+        for (filename, rows) in &seed.lexicon {
+            write(filename, &self.records_to_csv(rows, &mut word_ids)?)?;
+        }
+        write(
+            "unk.def",
+            &self.records_to_csv(&seed.unknown, &mut word_ids)?,
+        )?;
+
+        let mut matrix_def = format!("{} {}\n", self.right_id_size(), self.left_id_size());
+        for right_id in 0..self.right_id_size() as u16 {
+            for left_id in 0..self.left_id_size() as u16 {
+                matrix_def.push_str(&format!(
+                    "{} {} {}\n",
+                    right_id,
+                    left_id,
+                    self.connection_cost(right_id, left_id)
+                ));
+            }
+        }
+        write("matrix.def", &matrix_def)?;
+
+        for (filename, attributes) in [
+            ("left-id.def", &self.left_attributes),
+            ("right-id.def", &self.right_attributes),
+        ] {
+            let id_def = attributes
+                .values
+                .iter()
+                .enumerate()
+                .map(|(id, attribute)| format!("{} {}\n", id, attribute))
+                .collect::<String>();
+            write(filename, &id_def)?;
+        }
+
+        let rewrite_def_path = seed_dir.join("rewrite.def");
+        let mut rewrite_def = if rewrite_def_path.exists() {
+            read_file_with_encoding(&rewrite_def_path, encoding.name())?
+        } else {
+            String::new()
+        };
+        let pos_rule = pos_rewrite_rule(self.pos_fields).rewrite.join(",");
+        rewrite_def.push_str(&format!(
+            "\n[left rewrite]\n*\t{}\n[right rewrite]\n*\t{}\n",
+            pos_rule, pos_rule
+        ));
+        write("rewrite.def", &rewrite_def)?;
+
+        for filename in COPIED_FILES {
+            let path = seed_dir.join(filename);
+            if path.exists() {
+                fs::copy(&path, output_dir.join(filename))
+                    .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn records_to_csv(
+        &self,
+        rows: &[StringRecord],
+        word_ids: &mut impl Iterator<Item = usize>,
+    ) -> LinderaResult<String> {
+        let mut wtr = csv::WriterBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_writer(Vec::new());
+        for row in rows {
+            let word_id = word_ids.next().ok_or_else(|| {
+                LinderaErrorKind::Content.with_error(anyhow::anyhow!("Invalid row: {:?}", row))
+            })?;
+            let word = &self.words[word_id];
+            let mut record = row.iter().collect::<Vec<&str>>();
+            let left_id = word.left_id.to_string();
+            let right_id = word.right_id.to_string();
+            let word_cost = self.word_cost(word_id).to_string();
+            record[1] = &left_id;
+            record[2] = &right_id;
+            record[3] = &word_cost;
+            wtr.write_record(&record)
+                .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        }
+        let buffer = wtr
+            .into_inner()
+            .map_err(|err| LinderaErrorKind::Io.with_error(anyhow::anyhow!(err)))?;
+        String::from_utf8(buffer)
+            .map_err(|err| LinderaErrorKind::Decode.with_error(anyhow::anyhow!(err)))
+    }
+}
+
+/// A sentence of the corpus, resolved to the words of the model.
+struct Sentence {
+    text: String,
+    /// The word id and the byte range of each annotated word.
+    words: Vec<(usize, usize, usize)>,
+}
+
+/// Trains the word costs and the connection costs of a dictionary with a CRF, in the manner of
+/// `mecab-cost-train`.
+///
+/// The lattices are built with the same lookup as the tokenizer. Annotated words that are not in
+/// the lexicon are added to the lattices, so that they contribute to the weights of their
+/// features without being written to the output lexicon. They do not add context attributes;
+/// the sentences with a word whose attributes are not defined by the lexicon or `unk.def` are
+/// skipped.
+#[derive(Builder, Debug)]
+#[builder(name = "TrainerOptions")]
+#[builder(build_fn(name = "builder"))]
+pub struct Trainer {
+    #[builder(default = "\"UTF-8\".into()", setter(into))]
+    encoding: Cow<'static, str>,
+    #[builder(default = "10")]
+    max_iterations: usize,
+    /// The inverse of the strength of the L2 regularization.
+    #[builder(default = "1.0")]
+    regularization_coefficient: f64,
+    /// The initial learning rate of AdaGrad.
+    #[builder(default = "0.1")]
+    learning_rate: f64,
+    /// The factor to convert the weights to integer costs, like `cost-factor` of MeCab.
+    #[builder(default = "700.0")]
+    cost_factor: f64,
+    /// The number of leading fields of the features, i.e. the part-of-speech, that make up the
+    /// context attributes when no rule of `rewrite.def` matches. The default is the 4
+    /// part-of-speech fields of IPADIC.
+    #[builder(default = "4")]
+    pos_fields: usize,
+}
+
+impl Trainer {
+    /// Train the costs of the seed dictionary in `seed_dir` with the annotated corpus, and write
+    /// the dictionary sources to `output_dir`.
+    pub fn train(
+        &self,
+        seed_dir: &Path,
+        corpus_file: &Path,
+        output_dir: &Path,
+    ) -> LinderaResult<()> {
+        let seed = Seed::load(seed_dir, &self.encoding)?;
+        debug!("reading {:?}", corpus_file);
+        let corpus = parse_corpus(&read_file_with_encoding(corpus_file, &self.encoding)?)?;
+        let model = self.fit(&seed, &corpus)?;
+        model.write(&seed, seed_dir, output_dir, &self.encoding)
+    }
+
+    /// Train a model of the seed dictionary with the annotated sentences.
+    pub fn fit(&self, seed: &Seed, corpus: &[Vec<CorpusToken>]) -> LinderaResult<Model> {
+        let mut model = Model::new(seed, self.cost_factor, self.pos_fields);
+
+        let mut lexicon_word_ids: HashMap<(String, String), usize> = HashMap::new();
+        for row in seed.lexicon.values().flatten() {
+            if row.len() < 5 {
+                return Err(
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!("Invalid row: {:?}", row))
+                );
+            }
+            let features = row.iter().skip(4).collect::<Vec<&str>>();
+            if let Some(word_id) = model.add_word(&row[0], &features, Origin::Lexicon)? {
+                lexicon_word_ids
+                    .entry((row[0].to_string(), features.join(",")))
+                    .or_insert(word_id);
+            }
+        }
+        for row in &seed.unknown {
+            if row.len() < 5 {
+                return Err(
+                    LinderaErrorKind::Content.with_error(anyhow::anyhow!("Invalid row: {:?}", row))
+                );
+            }
+            let features = row.iter().skip(4).collect::<Vec<&str>>();
+            model.add_word(&row[0], &features, Origin::Unknown)?;
+        }
+
+        let mut corpus_word_ids: HashMap<(String, String), Option<usize>> = HashMap::new();
+        let mut sentences = Vec::new();
+        let mut skipped_sentences = 0;
+        'sentences: for tokens in corpus {
+            let mut sentence = Sentence {
+                text: String::new(),
+                words: Vec::new(),
+            };
+            for token in tokens {
+                let key = (token.surface.clone(), token.features.join(","));
+                let word_id = match lexicon_word_ids.get(&key) {
+                    Some(word_id) => Some(*word_id),
+                    None => match corpus_word_ids.get(&key) {
+                        Some(word_id) => *word_id,
+                        None => {
+                            let features = token
+                                .features
+                                .iter()
+                                .map(|s| s.as_str())
+                                .collect::<Vec<_>>();
+                            let word_id =
+                                model.add_word(&token.surface, &features, Origin::Corpus)?;
+                            corpus_word_ids.insert(key, word_id);
+                            word_id
+                        }
+                    },
+                };
+                let Some(word_id) = word_id else {
+                    debug!(
+                        "undefined context attributes: {}\t{}",
+                        token.surface,
+                        token.features.join(",")
+                    );
+                    skipped_sentences += 1;
+                    continue 'sentences;
+                };
+                let start = sentence.text.len();
+                sentence.text.push_str(&token.surface);
+                sentence.words.push((word_id, start, sentence.text.len()));
+            }
+            sentences.push(sentence);
+        }
+        if !corpus_word_ids.is_empty() {
+            info!(
+                "{} annotated words are not in the lexicon",
+                corpus_word_ids.len()
+            );
+        }
+        if skipped_sentences > 0 {
+            warn!(
+                "{} sentences are skipped because their context attributes are not in the lexicon nor in unk.def",
+                skipped_sentences
+            );
+        }
+
+        let prefix_dict = model.prefix_dict()?;
+        let unknown_dictionary = model.unknown_dictionary(&seed.char_definitions)?;
+
+        let mut lattice = Lattice::default();
+        let mut squared_gradients = vec![0.0; model.weights.len()];
+        for iteration in 0..self.max_iterations {
+            let mut loss = 0.0;
+            for sentence in &sentences {
+                let inserted_edges = sentence
+                    .words
+                    .iter()
+                    .filter(|(word_id, _, _)| !model.words[*word_id].indexed)
+                    .map(|(word_id, start, stop)| Edge {
+                        word_entry: model.word_entry(*word_id),
+                        start_index: *start as u32,
+                        stop_index: *stop as u32,
+                        path_cost: i32::MAX,
+                        ..Edge::default()
+                    })
+                    .collect();
+                lattice.set_text_with_inserted_edges(
+                    &prefix_dict,
+                    &None,
+                    &seed.char_definitions,
+                    &unknown_dictionary,
+                    &sentence.text,
+                    &Mode::Normal,
+                    inserted_edges,
+                );
+
+                let (sentence_loss, gradients) = self.gradients(&mut model, &lattice, sentence);
+                let Some(sentence_loss) = sentence_loss else {
+                    warn!("failed to build a lattice for {}", sentence.text);
+                    continue;
+                };
+                loss += sentence_loss;
+
+                squared_gradients.resize(model.weights.len(), 0.0);
+                let regularization =
+                    1.0 / (self.regularization_coefficient * sentences.len() as f64);
+                for (feature_id, gradient) in gradients {
+                    let gradient = gradient + model.weights[feature_id] * regularization;
+                    squared_gradients[feature_id] += gradient * gradient;
+                    model.weights[feature_id] -= self.learning_rate * gradient
+                        / (squared_gradients[feature_id].sqrt() + 1e-8);
+                }
+            }
+            info!(
+                "iteration: {}, loss: {}, features: {}",
+                iteration + 1,
+                loss,
+                model.weights.len()
+            );
+        }
+
+        Ok(model)
+    }
+
+    /// Returns the negative log-likelihood of the annotated path of the sentence and the gradients
+    /// of the features, computed with the forward-backward algorithm over the lattice.
+    fn gradients(
+        &self,
+        model: &mut Model,
+        lattice: &Lattice,
+        sentence: &Sentence,
+    ) -> (Option<f64>, HashMap<usize, f64>) {
+        let len = sentence.text.len();
+        let mut edge_ids = Vec::new();
+        for index in 0..=len {
+            edge_ids.extend(
+                lattice
+                    .starts_at(index)
+                    .iter()
+                    .map(|edge_id| edge_id.0 as usize),
+            );
+        }
+        let edge_num = edge_ids.iter().max().map(|max| max + 1).unwrap_or(2).max(2);
+
+        let word_id = |edge_id: EdgeId| -> Option<usize> {
+            if edge_id == BOS_EDGE || edge_id == EOS_EDGE {
+                None
+            } else {
+                Some(lattice.edge(edge_id).word_entry.word_id.0 as usize)
+            }
+        };
+
+        let mut word_scores = vec![0.0; edge_num];
+        for edge_id in &edge_ids {
+            if let Some(word_id) = word_id(EdgeId(*edge_id as u32)) {
+                word_scores[*edge_id] = model.word_score(word_id);
+            }
+        }
+
+        let mut alpha = vec![f64::NEG_INFINITY; edge_num];
+        alpha[BOS_EDGE.0 as usize] = 0.0;
+        for index in 0..=len {
+            for right in lattice.starts_at(index) {
+                let right_edge = lattice.edge(*right);
+                let mut score = f64::NEG_INFINITY;
+                for left in lattice.ends_at(index) {
+                    if alpha[left.0 as usize] == f64::NEG_INFINITY {
+                        continue;
+                    }
+                    let connection_score = model.connection_score(
+                        lattice.edge(*left).word_entry.right_id,
+                        right_edge.word_entry.left_id,
+                    );
+                    score = log_sum_exp(score, alpha[left.0 as usize] + connection_score);
+                }
+                alpha[right.0 as usize] = score + word_scores[right.0 as usize];
+            }
+        }
+
+        let mut beta = vec![f64::NEG_INFINITY; edge_num];
+        beta[EOS_EDGE.0 as usize] = 0.0;
+        for index in (0..=len).rev() {
+            for left in lattice.ends_at(index) {
+                let left_edge = lattice.edge(*left);
+                let mut score = f64::NEG_INFINITY;
+                for right in lattice.starts_at(index) {
+                    if beta[right.0 as usize] == f64::NEG_INFINITY {
+                        continue;
+                    }
+                    let connection_score = model.connection_score(
+                        left_edge.word_entry.right_id,
+                        lattice.edge(*right).word_entry.left_id,
+                    );
+                    score = log_sum_exp(
+                        score,
+                        connection_score + word_scores[right.0 as usize] + beta[right.0 as usize],
+                    );
+                }
+                beta[left.0 as usize] = score;
+            }
+        }
+
+        let mut gradients: HashMap<usize, f64> = HashMap::new();
+        let log_z = alpha[EOS_EDGE.0 as usize];
+        if !log_z.is_finite() {
+            return (None, gradients);
+        }
+
+        // The expected counts of the features.
+        for index in 0..=len {
+            for right in lattice.starts_at(index) {
+                let right_edge = lattice.edge(*right);
+                let right_score = word_scores[right.0 as usize] + beta[right.0 as usize];
+                if right_score == f64::NEG_INFINITY {
+                    continue;
+                }
+                if let Some(word_id) = word_id(*right) {
+                    let probability =
+                        (alpha[right.0 as usize] + beta[right.0 as usize] - log_z).exp();
+                    for feature_id in &model.words[word_id].unigram_features {
+                        *gradients.entry(*feature_id).or_default() += probability;
+                    }
+                }
+                for left in lattice.ends_at(index) {
+                    if alpha[left.0 as usize] == f64::NEG_INFINITY {
+                        continue;
+                    }
+                    let right_id = lattice.edge(*left).word_entry.right_id;
+                    let left_id = right_edge.word_entry.left_id;
+                    let connection_score = model.connection_score(right_id, left_id);
+                    let probability =
+                        (alpha[left.0 as usize] + connection_score + right_score - log_z).exp();
+                    for feature_id in model.bigram_feature_ids(right_id, left_id) {
+                        *gradients.entry(*feature_id).or_default() += probability;
+                    }
+                }
+            }
+        }
+
+        // The counts of the features on the annotated path.
+        let mut gold_score = 0.0;
+        let mut right_id = 0;
+        for (word_id, _, _) in &sentence.words {
+            let word = &model.words[*word_id];
+            let (left_id, next_right_id) = (word.left_id, word.right_id);
+            for feature_id in word.unigram_features.clone() {
+                *gradients.entry(feature_id).or_default() -= 1.0;
+            }
+            gold_score += model.word_score(*word_id) + model.connection_score(right_id, left_id);
+            for feature_id in model.bigram_feature_ids(right_id, left_id) {
+                *gradients.entry(*feature_id).or_default() -= 1.0;
+            }
+            right_id = next_right_id;
+        }
+        gold_score += model.connection_score(right_id, 0);
+        for feature_id in model.bigram_feature_ids(right_id, 0) {
+            *gradients.entry(*feature_id).or_default() -= 1.0;
+        }
+
+        (Some(log_z - gold_score), gradients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use lindera_core::character_definition::CharacterDefinitionsBuilder;
+    use lindera_core::id_definition::{parse_id_def, parse_rewrite_def, RewriteRules};
+
+    use crate::corpus::parse_corpus;
+    use crate::feature_template::parse_feature_def;
+    use crate::trainer::{csv_records, Seed, TrainerOptions};
+
+    const CHAR_DEF: &str = r#"
+DEFAULT 0 1 0
+HIRAGANA 0 1 2
+KANJI 0 0 2
+0x3041..0x309F HIRAGANA
+0x4E00..0x9FFF KANJI
+"#;
+
+    fn seed() -> Seed {
+        let mut char_definitions_builder = CharacterDefinitionsBuilder::default();
+        char_definitions_builder.parse(CHAR_DEF).unwrap();
+        Seed {
+            lexicon: BTreeMap::from([(
+                "lex.csv".to_string(),
+                csv_records(
+                    "東京,0,0,0,名詞,固有名詞\n\
+                     京都,0,0,0,名詞,固有名詞\n\
+                     東,0,0,0,名詞,一般\n\
+                     都,0,0,0,名詞,一般\n\
+                     に,0,0,0,助詞,格助詞\n\
+                     行く,0,0,0,動詞,自立\n",
+                )
+                .unwrap(),
+            )]),
+            unknown: csv_records(
+                "DEFAULT,0,0,0,記号,一般\nHIRAGANA,0,0,0,名詞,一般\nKANJI,0,0,0,名詞,一般\n",
+            )
+            .unwrap(),
+            char_definitions: char_definitions_builder.build(),
+            templates: parse_feature_def(
+                "UNIGRAM U0:%F[0]\nUNIGRAM U1:%F[0,1]\nBIGRAM B0:%L[0]/%R[0]\nBIGRAM B1:%L[0,1]/%R[0,1]\n",
+            )
+            .unwrap(),
+            rewrite_rules: RewriteRules::default(),
+        }
+    }
+
+    #[test]
+    fn test_fit() {
+        let seed = seed();
+        let corpus = parse_corpus(
+            "東京\t名詞,固有名詞\nに\t助詞,格助詞\n行く\t動詞,自立\nEOS\n\
+             京都\t名詞,固有名詞\nに\t助詞,格助詞\n行く\t動詞,自立\nEOS\n",
+        )
+        .unwrap();
+
+        let model = TrainerOptions::default()
+            .max_iterations(20)
+            .pos_fields(2)
+            .builder()
+            .unwrap()
+            .fit(&seed, &corpus)
+            .unwrap();
+
+        // 東京 and 京都 are preferred to 東 and 都.
+        let cost = |surface: &str| {
+            let word_id = model
+                .words
+                .iter()
+                .position(|word| word.surface == surface)
+                .unwrap();
+            model.word_cost(word_id)
+        };
+        assert!(cost("東京") < cost("東"));
+        assert!(cost("京都") < cost("都"));
+
+        // BOS/EOS, 4 attributes of the lexicon and 1 attribute only in unk.def.
+        assert_eq!(model.left_id_size(), 6);
+        assert_eq!(model.right_id_size(), 6);
+
+        // Connecting a noun to a particle is preferred to connecting a noun to a verb.
+        let noun = model.right_attributes.ids["名詞,固有名詞"];
+        let particle = model.left_attributes.ids["助詞,格助詞"];
+        let verb = model.left_attributes.ids["動詞,自立"];
+        assert!(model.connection_cost(noun, particle) < model.connection_cost(noun, verb));
+    }
+
+    #[test]
+    fn test_fit_without_rewrite_def() {
+        let mut seed = seed();
+        seed.lexicon = BTreeMap::from([(
+            "lex.csv".to_string(),
+            csv_records(
+                "東京,0,0,0,名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー\n\
+                 京都,0,0,0,名詞,固有名詞,地域,一般,*,*,京都,キョウト,キョート\n\
+                 東,0,0,0,名詞,一般,*,*,*,*,東,ヒガシ,ヒガシ\n\
+                 都,0,0,0,名詞,一般,*,*,*,*,都,ミヤコ,ミヤコ\n\
+                 に,0,0,0,助詞,格助詞,一般,*,*,*,に,ニ,ニ\n\
+                 行く,0,0,0,動詞,自立,*,*,五段・カ行促音便,基本形,行く,イク,イク\n\
+                 行っ,0,0,0,動詞,自立,*,*,五段・カ行促音便,連用タ接続,行く,イッ,イッ\n",
+            )
+            .unwrap(),
+        )]);
+        seed.unknown = csv_records(
+            "DEFAULT,0,0,0,記号,一般,*,*,*,*,*\nHIRAGANA,0,0,0,名詞,一般,*,*,*,*,*\nKANJI,0,0,0,名詞,一般,*,*,*,*,*\n",
+        )
+        .unwrap();
+        // 大阪 is only in the corpus with the attributes of the lexicon, and へ has attributes
+        // that are neither in the lexicon nor in unk.def.
+        let corpus = parse_corpus(
+            "東京\t名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー\n\
+             に\t助詞,格助詞,一般,*,*,*,に,ニ,ニ\n\
+             行く\t動詞,自立,*,*,五段・カ行促音便,基本形,行く,イク,イク\nEOS\n\
+             大阪\t名詞,固有名詞,地域,一般,*,*,大阪,オオサカ,オーサカ\n\
+             に\t助詞,格助詞,一般,*,*,*,に,ニ,ニ\n\
+             行っ\t動詞,自立,*,*,五段・カ行促音便,連用タ接続,行く,イッ,イッ\nEOS\n\
+             京都\t名詞,固有名詞,地域,一般,*,*,京都,キョウト,キョート\n\
+             へ\t助詞,格助詞,連語,*,*,*,へ,ヘ,エ\nEOS\n",
+        )
+        .unwrap();
+
+        let model = TrainerOptions::default()
+            .max_iterations(5)
+            .builder()
+            .unwrap()
+            .fit(&seed, &corpus)
+            .unwrap();
+
+        // BOS/EOS and the 5 part-of-speech attributes of the lexicon and unk.def.
+        assert_eq!(model.left_id_size(), 6);
+        assert_eq!(model.right_id_size(), 6);
+        assert!(model.left_attributes.ids.contains_key("動詞,自立,*,*"));
+        assert!(model.words.iter().any(|word| word.surface == "大阪"));
+        assert!(!model.words.iter().any(|word| word.surface == "へ"));
+
+        let tmp_dir = std::env::temp_dir().join("lindera-trainer-without-rewrite-def");
+        let _ = fs::remove_dir_all(&tmp_dir);
+        model
+            .write(
+                &seed,
+                &tmp_dir.join("seed"),
+                &tmp_dir.join("output"),
+                "UTF-8",
+            )
+            .unwrap();
+
+        // The written rewrite.def maps the features to the written context ids.
+        let rewrite_rules =
+            parse_rewrite_def(&fs::read_to_string(tmp_dir.join("output/rewrite.def")).unwrap())
+                .unwrap();
+        let left_ids =
+            parse_id_def(&fs::read_to_string(tmp_dir.join("output/left-id.def")).unwrap()).unwrap();
+        assert_eq!(left_ids.len(), 6);
+        let features = "名詞,固有名詞,地域,一般,*,*,名古屋,ナゴヤ,ナゴヤ"
+            .split(',')
+            .collect::<Vec<&str>>();
+        let left = rewrite_rules.rewrite_left(&features).unwrap();
+        assert_eq!(left_ids[&left], model.left_attributes.ids[&left]);
+
+        fs::remove_dir_all(&tmp_dir).unwrap();
+    }
+}
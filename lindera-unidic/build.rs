@@ -7,6 +7,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         input_dir: "unidic-mecab-2.1.2",
         output_dir: "lindera-unidic",
         download_url: "https://dlwqk3ibdg1xh.cloudfront.net/unidic-mecab-2.1.2.tar.gz",
+        env_prefix: "LINDERA_UNIDIC",
+        sha256: None,
         dummy_input: "テスト,5131,5131,767,名詞,普通名詞,サ変可能,*,*,*,テスト,テスト-test,テスト,テスト,テスト,テスト,外,*,*,*,*\n",
     },
 lindera_unidic_builder::unidic_builder::UnidicBuilder::new())
@@ -39,6 +39,7 @@ fn bench_constructor(c: &mut Criterion) {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -58,6 +59,7 @@ fn bench_constructor(c: &mut Criterion) {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -77,6 +79,7 @@ fn bench_constructor(c: &mut Criterion) {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -96,6 +99,7 @@ fn bench_constructor(c: &mut Criterion) {
                     dictionary,
                     user_dictionary: None,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -121,12 +125,16 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let user_dictionary = Some(UserDictionaryConfig {
                     kind: Some(DictionaryKind::IPADIC),
                     path: userdic_file,
+                    format: None,
+                    schema: None,
+                    estimate_cost: false,
                 });
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -149,12 +157,16 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let user_dictionary = Some(UserDictionaryConfig {
                     kind: Some(DictionaryKind::UniDic),
                     path: userdic_file,
+                    format: None,
+                    schema: None,
+                    estimate_cost: false,
                 });
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -177,12 +189,16 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let user_dictionary = Some(UserDictionaryConfig {
                     kind: Some(DictionaryKind::KoDic),
                     path: userdic_file,
+                    format: None,
+                    schema: None,
+                    estimate_cost: false,
                 });
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -205,12 +221,16 @@ fn bench_constructor_with_simple_userdic(c: &mut Criterion) {
                 let user_dictionary = Some(UserDictionaryConfig {
                     kind: Some(DictionaryKind::CcCedict),
                     path: userdic_file,
+                    format: None,
+                    schema: None,
+                    estimate_cost: false,
                 });
 
                 let config = TokenizerConfig {
                     dictionary,
                     user_dictionary,
                     mode: Mode::Normal,
+                    ..TokenizerConfig::default()
                 };
                 Tokenizer::from_config(config).unwrap()
             })
@@ -231,6 +251,7 @@ fn bench_tokenize(c: &mut Criterion) {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -251,6 +272,7 @@ fn bench_tokenize(c: &mut Criterion) {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -271,6 +293,7 @@ fn bench_tokenize(c: &mut Criterion) {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -291,6 +314,7 @@ fn bench_tokenize(c: &mut Criterion) {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -317,12 +341,16 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -347,12 +375,16 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -377,12 +409,16 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -405,12 +441,16 @@ fn bench_tokenize_with_simple_userdic(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -447,12 +487,16 @@ fn bench_tokenize_long_text(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
@@ -490,12 +534,16 @@ fn bench_tokenize_long_text(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
@@ -536,12 +584,16 @@ fn bench_tokenize_details_long_text(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
@@ -584,12 +636,16 @@ fn bench_tokenize_details_long_text(c: &mut Criterion) {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
         let tokenizer = Tokenizer::from_config(config).unwrap();
 
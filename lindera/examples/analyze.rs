@@ -41,6 +41,7 @@ fn main() -> LinderaResult<()> {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -97,7 +98,7 @@ fn main() -> LinderaResult<()> {
             JapaneseStopTagsTokenFilter::new(japanese_stop_tags_token_filter_config);
         token_filters.push(BoxTokenFilter::from(japanese_stop_tags_token_filter));
 
-        let analyzer = Analyzer::new(character_filters, tokenizer, token_filters);
+        let analyzer = Analyzer::try_new(character_filters, tokenizer, token_filters)?;
 
         let mut text =
             "Ｌｉｎｄｅｒａは形態素解析ｴﾝｼﾞﾝです。ユーザー辞書も利用可能です。".to_string();
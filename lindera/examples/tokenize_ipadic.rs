@@ -14,6 +14,7 @@ fn main() -> LinderaResult<()> {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         #[allow(unused_variables)]
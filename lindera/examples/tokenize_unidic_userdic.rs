@@ -20,12 +20,16 @@ fn main() -> LinderaResult<()> {
             path: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
                 .join("../resources")
                 .join("unidic_simple_userdic.csv"),
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         #[allow(unused_variables)]
@@ -1,19 +1,51 @@
 pub type LinderaResult<T> = lindera_core::LinderaResult<T>;
 pub type LinderaError = lindera_core::error::LinderaError;
 pub type LinderaErrorKind = lindera_core::error::LinderaErrorKind;
+pub type ErrorLocation = lindera_core::error::ErrorLocation;
 pub type Mode = lindera_core::mode::Mode;
 pub type Penalty = lindera_core::mode::Penalty;
 pub type DictionaryConfig = lindera_dictionary::DictionaryConfig;
 pub type DictionaryKind = lindera_dictionary::DictionaryKind;
 pub type UserDictionaryConfig = lindera_dictionary::UserDictionaryConfig;
+pub type UserDictionaryFormat = lindera_core::user_dictionary_source::UserDictionaryFormat;
+pub type UserDictionaryEntry = lindera_core::user_dictionary_source::UserDictionaryEntry;
 pub type Tokenizer = lindera_tokenizer::tokenizer::Tokenizer;
 pub type TokenizerConfig = lindera_tokenizer::tokenizer::TokenizerConfig;
+pub type TokenizerBuilder = lindera_tokenizer::builder::TokenizerBuilder;
+pub type ConfigFormat = lindera_tokenizer::tokenizer::ConfigFormat;
 pub type Token<'a> = lindera_tokenizer::token::Token<'a>;
+pub type OwnedToken = lindera_tokenizer::token::OwnedToken;
+pub type TokenOffsets = lindera_core::offsets::TokenOffsets;
+pub type EdgeType = lindera_core::viterbi::EdgeType;
+pub type IpadicFeatures<'a> = lindera_core::features::IpadicFeatures<'a>;
+pub type UnidicFeatures<'a> = lindera_core::features::UnidicFeatures<'a>;
+pub type KoDicFeatures<'a> = lindera_core::features::KoDicFeatures<'a>;
+pub type CcCedictFeatures<'a> = lindera_core::features::CcCedictFeatures<'a>;
+pub type TokenStream<'a, I> = lindera_tokenizer::stream::TokenStream<'a, I>;
+pub type TokenizerComparison<'a> = lindera_tokenizer::comparison::TokenizerComparison<'a>;
+pub type ImpactReport = lindera_tokenizer::comparison::ImpactReport;
+pub type SentenceChange = lindera_tokenizer::comparison::SentenceChange;
+pub type ChangeKind = lindera_tokenizer::comparison::ChangeKind;
+pub type Trainer = lindera_trainer::trainer::Trainer;
+pub type TrainerOptions = lindera_trainer::trainer::TrainerOptions;
 pub type DictionaryBuilderResolver = lindera_dictionary::DictionaryBuilderResolver;
 pub type DictionaryLoader = lindera_dictionary::DictionaryLoader;
 pub type Dictionary = lindera_core::dictionary::Dictionary;
 pub type UserDictionary = lindera_core::dictionary::UserDictionary;
+pub type DictionaryDiff = lindera_dictionary::diff::DictionaryDiff;
+pub type DictionaryDiffSummary = lindera_dictionary::diff::DictionaryDiffSummary;
+pub type DictionaryEntry = lindera_dictionary::diff::DictionaryEntry;
+pub type EntryChange = lindera_dictionary::diff::EntryChange;
+pub type ValidationReport = lindera_core::validation::ValidationReport;
+pub type ValidationIssue = lindera_core::validation::ValidationIssue;
+pub type ValidationIssueKind = lindera_core::validation::ValidationIssueKind;
 pub type Analyzer = lindera_analyzer::analyzer::Analyzer;
+#[cfg(feature = "tokio")]
+pub type AsyncAnalyzer = lindera_analyzer::async_analyzer::AsyncAnalyzer;
+#[cfg(feature = "tokio")]
+pub type AnalyzeStream = lindera_analyzer::async_analyzer::AnalyzeStream;
+#[cfg(feature = "tokio")]
+pub type CancelHandle = lindera_analyzer::async_analyzer::CancelHandle;
 #[cfg(feature = "filter")]
 pub type AnalyzerConfig = lindera_analyzer::analyzer::AnalyzerConfig;
 #[cfg(feature = "filter")]
@@ -217,6 +249,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -474,6 +507,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Decompose(Penalty::default()),
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -552,7 +586,11 @@ mod tests {
             assert_eq!(token.byte_end, 36);
             assert_eq!(token.position, 3);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
+            assert_eq!(
+                token.get_details().unwrap(),
+                vec!["名詞", "一般", "*", "*", "*", "*", "*"]
+            );
         }
     }
 
@@ -571,12 +609,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -725,12 +767,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -887,6 +933,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -946,6 +993,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1360,12 +1408,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1630,12 +1682,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::UniDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -1936,6 +1992,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2110,12 +2167,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2195,12 +2256,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::KoDic),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2316,6 +2381,7 @@ mod tests {
             dictionary,
             user_dictionary: None,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2433,7 +2499,7 @@ mod tests {
             assert_eq!(token.byte_end, 36);
             assert_eq!(token.position, 5);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
         }
     }
 
@@ -2452,12 +2518,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2546,7 +2616,7 @@ mod tests {
             assert_eq!(token.byte_end, 30);
             assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
         }
     }
 
@@ -2565,12 +2635,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::CcCedict),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2659,7 +2733,7 @@ mod tests {
             assert_eq!(token.byte_end, 30);
             assert_eq!(token.position, 4);
             assert_eq!(token.position_length, 1);
-            assert_eq!(token.get_details().unwrap(), vec!["UNK"]);
+            assert!(token.is_unknown());
         }
     }
 
@@ -2678,12 +2752,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         let tokenizer = Tokenizer::from_config(config).unwrap();
@@ -2833,12 +2911,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         Tokenizer::from_config(config).unwrap();
@@ -2860,12 +2942,16 @@ mod tests {
         let user_dictionary = Some(UserDictionaryConfig {
             kind: Some(DictionaryKind::IPADIC),
             path: userdic_file,
+            format: None,
+            schema: None,
+            estimate_cost: false,
         });
 
         let config = TokenizerConfig {
             dictionary,
             user_dictionary,
             mode: Mode::Normal,
+            ..TokenizerConfig::default()
         };
 
         Tokenizer::from_config(config).unwrap();
@@ -3278,7 +3364,8 @@ mod tests {
                 assert_eq!(token.byte_end, 15);
                 assert_eq!(token.position, 0);
                 assert_eq!(token.position_length, 1);
-                assert_eq!(token.details, vec!["UNK".to_string()]);
+                assert!(token.word_id.is_unknown());
+                assert_eq!(token.details[0], "名詞");
             }
             {
                 let token = tokens_iter.next().unwrap();
@@ -3364,32 +3451,24 @@ mod tests {
             let mut tokens_iter = tokens.iter_mut();
             {
                 let token = tokens_iter.next().unwrap();
-                assert_eq!(token.text, "10".to_string());
+                // The unknown number has the features of unk.def (名詞,数), so it is compounded.
+                assert_eq!(token.text, "10ガロン".to_string());
                 assert_eq!(token.byte_start, 0);
-                assert_eq!(token.byte_end, 6);
-                assert_eq!(token.position, 0);
-                assert_eq!(token.position_length, 1);
-                assert_eq!(token.details, vec!["UNK".to_string()]);
-            }
-            {
-                let token = tokens_iter.next().unwrap();
-                assert_eq!(token.text, "ガロン".to_string());
-                assert_eq!(token.byte_start, 6);
                 assert_eq!(token.byte_end, 9);
-                assert_eq!(token.position, 1);
-                assert_eq!(token.position_length, 1);
+                assert_eq!(token.position, 0);
+                assert_eq!(token.position_length, 2);
                 assert_eq!(
                     token.details,
                     vec![
-                        "名詞".to_string(),
-                        "接尾".to_string(),
-                        "助数詞".to_string(),
+                        "複合語".to_string(),
+                        "*".to_string(),
+                        "*".to_string(),
                         "*".to_string(),
                         "*".to_string(),
                         "*".to_string(),
-                        "ガロン".to_string(),
-                        "ガロン".to_string(),
-                        "ガロン".to_string()
+                        "*".to_string(),
+                        "*".to_string(),
+                        "*".to_string()
                     ]
                 );
             }
@@ -3421,15 +3500,8 @@ mod tests {
                 let token = tokens_iter.next().unwrap();
                 let start = token.byte_start;
                 let end = token.byte_end;
-                assert_eq!(token.text, "10".to_string());
-                assert_eq!(&text[start..end], "１０");
-            }
-            {
-                let token = tokens_iter.next().unwrap();
-                let start = token.byte_start;
-                let end = token.byte_end;
-                assert_eq!(token.text, "ガロン".to_string());
-                assert_eq!(&text[start..end], "㌎");
+                assert_eq!(token.text, "10ガロン".to_string());
+                assert_eq!(&text[start..end], "１０㌎");
             }
             {
                 let token = tokens_iter.next().unwrap();